@@ -5,7 +5,7 @@
 use std::time::Duration;
 
 use eframe::egui::{self, CentralPanel, Visuals};
-use egui_plotter::{Chart, MouseConfig};
+use egui_plotter::{Chart, ContextMenu, MouseConfig};
 use plotters::prelude::*;
 
 fn main() {
@@ -13,7 +13,7 @@ fn main() {
     eframe::run_native(
         "3d Chart Example",
         native_options,
-        Box::new(|cc| Box::new(Chart3d::new(cc))),
+        Box::new(|cc| Ok(Box::new(Chart3d::new(cc)))),
     )
     .unwrap();
 }
@@ -39,6 +39,7 @@ impl Chart3d {
             .mouse(MouseConfig::enabled())
             .pitch(0.7)
             .yaw(0.7)
+            .context_menu(ContextMenu::new().reset_view(true))
             .builder_cb(Box::new(|area, transform, _d| {
                 // Build a chart like you would in any other plotter chart.
                 // The drawing area and projection transformations are provided
@@ -47,7 +48,7 @@ impl Chart3d {
                 let z_axis = (-3.0..3.0).step(0.1);
 
                 let mut chart = ChartBuilder::on(area)
-                    .caption(format!("3D Plot Test"), (FontFamily::SansSerif, 20))
+                    .caption("3D Plot Test", (FontFamily::SansSerif, 20))
                     .build_cartesian_3d(x_axis, -3.0..3.0, z_axis)
                     .unwrap();
 
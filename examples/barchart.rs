@@ -0,0 +1,67 @@
+//! Simple example using the pre-defined bar chart type
+
+use eframe::egui::{self, CentralPanel, Visuals};
+use egui::TopBottomPanel;
+use egui_plotter::charts::{BarChart, BarLayout};
+
+const QUARTERS: [&str; 4] = ["Q1", "Q2", "Q3", "Q4"];
+const HARDWARE: [f32; 4] = [12.0, 15.0, 9.0, 18.0];
+const SOFTWARE: [f32; 4] = [8.0, 11.0, 14.0, 16.0];
+const SERVICES: [f32; 4] = [4.0, -2.0, 6.0, 7.0];
+
+fn main() {
+    let native_options = eframe::NativeOptions::default();
+    eframe::run_native(
+        "BarChart Example",
+        native_options,
+        Box::new(|cc| Ok(Box::new(BarChartExample::new(cc)))),
+    )
+    .unwrap();
+}
+
+struct BarChartExample {
+    barchart: BarChart,
+    layout: BarLayout,
+}
+
+impl BarChartExample {
+    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        // Disable feathering as it causes artifacts
+        let context = &cc.egui_ctx;
+
+        context.tessellation_options_mut(|tess_options| {
+            tess_options.feathering = false;
+        });
+
+        // Also enable light mode
+        context.set_visuals(Visuals::light());
+
+        let barchart = BarChart::new(&QUARTERS, "Revenue by Quarter")
+            .y_unit("millions")
+            .palette_series("Hardware", &HARDWARE)
+            .palette_series("Software", &SOFTWARE)
+            .palette_series("Services", &SERVICES);
+
+        Self {
+            barchart,
+            layout: BarLayout::default(),
+        }
+    }
+}
+
+impl eframe::App for BarChartExample {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        TopBottomPanel::bottom("layoutmenu").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.layout, BarLayout::Grouped, "Grouped");
+                ui.radio_value(&mut self.layout, BarLayout::Stacked, "Stacked");
+            })
+        });
+
+        self.barchart.set_layout(self.layout);
+
+        CentralPanel::default().show(ctx, |ui| {
+            self.barchart.draw(ui);
+        });
+    }
+}
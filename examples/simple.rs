@@ -11,7 +11,7 @@ fn main() {
     eframe::run_native(
         "Simple Example",
         native_options,
-        Box::new(|cc| Box::new(Simple::new(cc))),
+        Box::new(|cc| Ok(Box::new(Simple::new(cc)))),
     )
     .unwrap();
 }
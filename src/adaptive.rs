@@ -0,0 +1,146 @@
+//! Controller trading a chart's drawing quality for speed while it's too slow to draw
+
+use std::time::Duration;
+
+use egui::Context;
+use instant::Instant;
+
+/// Default time a chart can take to draw a frame before its quality is lowered, in seconds.
+pub const DEFAULT_FRAME_BUDGET: f32 = 0.004;
+/// Default time a chart has to be left still before full quality is restored, in seconds.
+pub const DEFAULT_IDLE_DELAY: f32 = 0.25;
+/// Weight of the latest frame in the smoothed draw time, so a single slow frame doesn't lower
+/// the quality.
+const DRAW_TIME_SMOOTHING: f32 = 0.25;
+
+#[derive(Debug, Copy, Clone)]
+/// Measures how long a chart takes to draw, lowering its quality while it's too slow and
+/// restoring it once the chart is left still.
+///
+/// ## Usage
+/// **Ensure the `adaptive` feature is enabled to use this type.**
+///
+/// Give a chart a controller with `set_adaptive_quality` to have it tune itself, rather than
+/// setting a [`RenderQuality`](crate::RenderQuality) for each chart by hand. While the chart
+/// is moving, such as being dragged, zoomed, or played back, and its frames take longer than the
+/// budget to draw, it is drawn in fast mode and time charts decimate their points. Once the
+/// chart has been still for the idle delay, it is redrawn at full quality. It can be configured
+/// in the following ways:
+///  * `budget` - Time the chart can take to draw a frame, in seconds, smoothed over several
+///    frames.
+///  * `idle_delay` - Time the chart has to be still before full quality is restored, in
+///    seconds.
+pub struct AdaptiveQuality {
+    budget: f32,
+    idle_delay: f32,
+    /// Smoothed time taken by the frames drawn at full quality, in seconds.
+    draw_time: f32,
+    /// Quality is lowered until the chart is left still.
+    degraded: bool,
+    /// When the chart last moved. None if it hasn't yet.
+    last_active: Option<Instant>,
+}
+
+impl Default for AdaptiveQuality {
+    fn default() -> Self {
+        Self {
+            budget: DEFAULT_FRAME_BUDGET,
+            idle_delay: DEFAULT_IDLE_DELAY,
+            draw_time: 0.0,
+            degraded: false,
+            last_active: None,
+        }
+    }
+}
+
+impl AdaptiveQuality {
+    #[inline]
+    /// Create a controller with the default budget and idle delay.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Set the time the chart can take to draw a frame, in seconds.
+    pub fn set_budget(&mut self, budget: f32) {
+        self.budget = budget
+    }
+
+    #[inline]
+    /// Set the time the chart can take to draw a frame, in seconds. Consumes self.
+    pub fn budget(mut self, budget: f32) -> Self {
+        self.set_budget(budget);
+
+        self
+    }
+
+    #[inline]
+    /// Set the time the chart has to be still before full quality is restored, in seconds.
+    pub fn set_idle_delay(&mut self, idle_delay: f32) {
+        self.idle_delay = idle_delay
+    }
+
+    #[inline]
+    /// Set the time the chart has to be still before full quality is restored, in seconds.
+    /// Consumes self.
+    pub fn idle_delay(mut self, idle_delay: f32) -> Self {
+        self.set_idle_delay(idle_delay);
+
+        self
+    }
+
+    #[inline]
+    /// Get the smoothed time the chart takes to draw a frame at full quality, in seconds.
+    pub fn get_draw_time(&self) -> f32 {
+        self.draw_time
+    }
+
+    #[inline]
+    /// Return true if the chart's quality is currently lowered.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded
+    }
+
+    #[inline]
+    /// Note that the chart is moving this frame, keeping full quality from being restored.
+    pub(crate) fn mark_active(&mut self) {
+        self.last_active = Some(Instant::now());
+    }
+
+    /// Record the time taken to draw a frame, lowering the quality of the next frames if the
+    /// chart is moving and too slow to draw, or restoring it once the chart is still. Schedules
+    /// the repaint that restores full quality, as a still chart isn't otherwise redrawn.
+    pub(crate) fn record(&mut self, ctx: &Context, draw_time: Duration, active: bool) {
+        if active {
+            self.mark_active();
+        }
+
+        // Frames drawn at lowered quality don't tell how long full quality would take
+        if !self.degraded {
+            let draw_time = draw_time.as_secs_f32();
+
+            self.draw_time += (draw_time - self.draw_time) * DRAW_TIME_SMOOTHING;
+        }
+
+        let still = self
+            .last_active
+            .map_or(Duration::MAX, |last| last.elapsed());
+        let idle_delay = Duration::from_secs_f32(self.idle_delay.max(0.0));
+
+        match (still >= idle_delay, self.degraded) {
+            (true, true) => {
+                self.degraded = false;
+
+                ctx.request_repaint();
+            }
+            (true, false) => {}
+            (false, _) => {
+                self.degraded |= self.draw_time > self.budget;
+
+                if self.degraded {
+                    ctx.request_repaint_after(idle_delay - still);
+                }
+            }
+        }
+    }
+}
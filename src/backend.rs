@@ -6,9 +6,11 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::ops::{Add, AddAssign, MulAssign, Sub, SubAssign};
 
 use egui::{
-    epaint::{PathShape, TextShape},
-    Align, Align2, Color32, FontFamily as EguiFontFamily, FontId, Pos2, Rect, Stroke, Ui,
+    epaint::{PathShape, TessellationOptions, Tessellator, TextShape},
+    Align, Align2, Color32, FontFamily as EguiFontFamily, FontId, Mesh, Painter, Pos2, Rect, Shape,
+    Stroke, Ui, Vec2,
 };
+use plotters::style::{FontDesc, RGBAColor, ShapeStyle, TextStyle};
 use plotters_backend::{
     text_anchor::{HPos, Pos, VPos},
     BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingBackend, DrawingErrorKind,
@@ -68,6 +70,13 @@ impl From<EguiBackendCoord> for (u32, u32) {
     }
 }
 
+impl From<EguiBackendCoord> for BackendCoord {
+    #[inline]
+    fn from(val: EguiBackendCoord) -> Self {
+        (val.x.round() as i32, val.y.round() as i32)
+    }
+}
+
 impl From<Pos2> for EguiBackendCoord {
     #[inline]
     fn from(value: Pos2) -> Self {
@@ -149,7 +158,7 @@ impl MulAssign<f32> for EguiBackendCoord {
 
 #[derive(Debug, Clone, Copy)]
 /// Struct used to convert between Egui and Plotter's color types
-struct EguiBackendColor {
+pub(crate) struct EguiBackendColor {
     r: u8,
     g: u8,
     b: u8,
@@ -174,13 +183,185 @@ impl From<EguiBackendColor> for Color32 {
     }
 }
 
+/// Object-safe subset of a plotters backend, letting a headless EguiBackend forward what it
+/// draws to any other backend.
+pub(crate) trait HeadlessTarget {
+    fn size(&self) -> (u32, u32);
+    fn draw_pixel(&mut self, point: BackendCoord, color: BackendColor);
+    fn draw_line(&mut self, from: BackendCoord, to: BackendCoord, style: &ShapeStyle);
+    fn draw_text(&mut self, text: &str, style: &TextStyle, pos: BackendCoord);
+    fn draw_path(&mut self, path: Vec<BackendCoord>, style: &ShapeStyle);
+    fn fill_polygon(&mut self, vert: Vec<BackendCoord>, style: &ShapeStyle);
+}
+
+/// Plotters backend drawn to in place of a Ui. Keeps the first error the backend returns
+/// instead of interrupting the builder callback with it.
+pub(crate) struct HeadlessBackend<DB: DrawingBackend> {
+    backend: DB,
+    error: Option<DrawingErrorKind<DB::ErrorType>>,
+}
+
+impl<DB: DrawingBackend> HeadlessBackend<DB> {
+    #[inline]
+    pub(crate) fn new(backend: DB) -> Self {
+        Self {
+            backend,
+            error: None,
+        }
+    }
+
+    /// Present what was drawn, or return the first error the backend hit while drawing it.
+    pub(crate) fn finish(mut self) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        match self.error {
+            Some(error) => Err(error),
+            None => self.backend.present(),
+        }
+    }
+
+    #[inline]
+    fn keep(&mut self, result: Result<(), DrawingErrorKind<DB::ErrorType>>) {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+    }
+}
+
+impl<DB: DrawingBackend> HeadlessTarget for HeadlessBackend<DB> {
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        self.backend.get_size()
+    }
+
+    fn draw_pixel(&mut self, point: BackendCoord, color: BackendColor) {
+        let result = self.backend.draw_pixel(point, color);
+
+        self.keep(result)
+    }
+
+    fn draw_line(&mut self, from: BackendCoord, to: BackendCoord, style: &ShapeStyle) {
+        let result = self.backend.draw_line(from, to, style);
+
+        self.keep(result)
+    }
+
+    fn draw_text(&mut self, text: &str, style: &TextStyle, pos: BackendCoord) {
+        let result = self.backend.draw_text(text, style, pos);
+
+        self.keep(result)
+    }
+
+    fn draw_path(&mut self, path: Vec<BackendCoord>, style: &ShapeStyle) {
+        let result = self.backend.draw_path(path, style);
+
+        self.keep(result)
+    }
+
+    fn fill_polygon(&mut self, vert: Vec<BackendCoord>, style: &ShapeStyle) {
+        let result = self.backend.fill_polygon(vert, style);
+
+        self.keep(result)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// How carefully an EguiBackend draws, trading quality for speed.
+///
+/// ## Usage
+/// Start from [`RenderQuality::new`] or the [`RenderQuality::FAST`] preset and change what's
+/// needed:
+///  * `anti_alias` - Smooths the edges of lines and polygons. Disabling it draws them without
+///    egui's feathering, even if it's enabled in the context.
+///  * `pixel_snap` - Moves the points of lines onto pixel centers and the corners of polygons onto
+///    pixel edges, so thin gridlines and bars are crisp instead of blurred across two pixels.
+///  * `fast` - Drops the points of paths less than a pixel away from the last one drawn, and
+///    disables anti-aliasing. Meant for when many charts are on screen at once.
+///
+/// Only drawing to a Ui is affected, charts drawn headless are left to their backend.
+pub struct RenderQuality {
+    anti_alias: bool,
+    pixel_snap: bool,
+    fast: bool,
+}
+
+impl Default for RenderQuality {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderQuality {
+    /// Fastest drawing, without anti-aliasing and with paths thinned out to a point per pixel.
+    pub const FAST: Self = Self::new().fast(true);
+
+    #[inline]
+    /// Create the default render quality, anti-aliased and without pixel snapping.
+    pub const fn new() -> Self {
+        Self {
+            anti_alias: true,
+            pixel_snap: false,
+            fast: false,
+        }
+    }
+
+    #[inline]
+    /// Enable/disable anti-aliasing of lines and polygons.
+    pub fn set_anti_alias(&mut self, anti_alias: bool) {
+        self.anti_alias = anti_alias
+    }
+
+    #[inline]
+    /// Enable/disable anti-aliasing of lines and polygons. Consumes self.
+    pub const fn anti_alias(mut self, anti_alias: bool) -> Self {
+        self.anti_alias = anti_alias;
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable snapping lines and polygons to the pixel grid.
+    pub fn set_pixel_snap(&mut self, pixel_snap: bool) {
+        self.pixel_snap = pixel_snap
+    }
+
+    #[inline]
+    /// Enable/disable snapping lines and polygons to the pixel grid. Consumes self.
+    pub const fn pixel_snap(mut self, pixel_snap: bool) -> Self {
+        self.pixel_snap = pixel_snap;
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable fast mode, trading quality for speed.
+    pub fn set_fast(&mut self, fast: bool) {
+        self.fast = fast
+    }
+
+    #[inline]
+    /// Enable/disable fast mode, trading quality for speed. Consumes self.
+    pub const fn fast(mut self, fast: bool) -> Self {
+        self.fast = fast;
+
+        self
+    }
+}
+
+/// Where an EguiBackend draws to.
+enum Target<'a> {
+    Ui(&'a Ui),
+    /// Another plotters backend, drawn to without egui.
+    Headless(&'a mut dyn HeadlessTarget),
+}
+
 /// Plotter backend for egui; simply provide a reference to the ui element to
 /// use.
 pub struct EguiBackend<'a> {
-    ui: &'a Ui,
+    target: Target<'a>,
     x: i32,
     y: i32,
     scale: f32,
+    quality: RenderQuality,
 }
 
 impl<'a> EguiBackend<'a> {
@@ -188,10 +369,36 @@ impl<'a> EguiBackend<'a> {
     /// Create a backend given a reference to a Ui.
     pub fn new(ui: &'a mut Ui) -> Self {
         Self {
-            ui,
+            target: Target::Ui(ui),
             x: 0,
             y: 0,
             scale: 1.0,
+            quality: RenderQuality::new(),
+        }
+    }
+
+    #[inline]
+    /// Create a backend forwarding everything drawn to another plotters backend instead of a Ui.
+    pub(crate) fn headless(target: &'a mut dyn HeadlessTarget) -> Self {
+        Self {
+            target: Target::Headless(target),
+            x: 0,
+            y: 0,
+            scale: 1.0,
+            quality: RenderQuality::new(),
+        }
+    }
+
+    #[inline]
+    /// Area drawn to, in screen coordinates.
+    fn bounds(&self) -> Rect {
+        match &self.target {
+            Target::Ui(ui) => ui.max_rect(),
+            Target::Headless(target) => {
+                let (width, height) = target.size();
+
+                Rect::from_min_size(Pos2::ZERO, Vec2::new(width as f32, height as f32))
+            }
         }
     }
 
@@ -235,13 +442,102 @@ impl<'a> EguiBackend<'a> {
 
         self
     }
+
+    #[inline]
+    /// Set how carefully the backend draws. See [`RenderQuality`].
+    pub fn set_render_quality(&mut self, quality: RenderQuality) {
+        self.quality = quality
+    }
+
+    #[inline]
+    /// Set how carefully the backend draws. Consumes self.
+    pub fn render_quality(mut self, quality: RenderQuality) -> Self {
+        self.set_render_quality(quality);
+
+        self
+    }
+
+    #[inline]
+    /// Snap a point to the pixel grid if enabled, centering lines `width` points wide on pixels.
+    fn snap(&self, point: Pos2, width: f32, pixels_per_point: f32) -> Pos2 {
+        if !self.quality.pixel_snap {
+            return point;
+        }
+
+        // Lines an odd number of pixels wide are only crisp when centered on a pixel
+        let offset = match (width * pixels_per_point).round() as i32 % 2 {
+            1 => 0.5,
+            _ => 0.0,
+        };
+
+        let snap =
+            |value: f32| ((value * pixels_per_point - offset).round() + offset) / pixels_per_point;
+
+        Pos2::new(snap(point.x), snap(point.y))
+    }
+
+    /// Add a line or polygon to a painter, tessellating it without feathering if anti-aliasing
+    /// is disabled.
+    fn paint(&self, painter: &Painter, shape: Shape) {
+        if self.quality.anti_alias && !self.quality.fast {
+            painter.add(shape);
+
+            return;
+        }
+
+        let options = TessellationOptions {
+            feathering: false,
+            ..Default::default()
+        };
+
+        // Only lines and polygons are tessellated here, so no fonts or discs are needed
+        let mut tessellator = Tessellator::new(
+            painter.ctx().pixels_per_point(),
+            options,
+            [1, 1],
+            Vec::new(),
+        );
+        let mut mesh = Mesh::default();
+
+        tessellator.tessellate_shape(shape, &mut mesh);
+
+        painter.add(mesh);
+    }
+}
+
+/// Copy a style into a plotters ShapeStyle, so it can be passed on to another backend.
+fn shape_style<S: BackendStyle>(style: &S) -> ShapeStyle {
+    let BackendColor {
+        rgb: (r, g, b),
+        alpha,
+    } = style.color();
+
+    ShapeStyle {
+        color: RGBAColor(r, g, b, alpha),
+        filled: false,
+        stroke_width: style.stroke_width(),
+    }
+}
+
+/// Egui font matching the font of a plotters text style.
+fn font_id<TStyle: BackendTextStyle>(style: &TStyle) -> FontId {
+    let family = match style.family() {
+        PlottersFontFamily::Serif | PlottersFontFamily::SansSerif => EguiFontFamily::Proportional,
+        PlottersFontFamily::Monospace => EguiFontFamily::Monospace,
+        PlottersFontFamily::Name(string) => EguiFontFamily::Name(string.into()),
+    };
+
+    FontId {
+        size: style.size() as f32,
+        family,
+    }
 }
 
 impl<'a> DrawingBackend for EguiBackend<'a> {
     type ErrorType = std::io::Error;
 
     fn get_size(&self) -> (u32, u32) {
-        let bounds = self.ui.max_rect();
+        let bounds = self.bounds();
         (bounds.width() as u32, bounds.height() as u32)
     }
 
@@ -258,11 +554,21 @@ impl<'a> DrawingBackend for EguiBackend<'a> {
         point: (i32, i32),
         color: BackendColor,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+        let bounds = self.bounds();
 
         let p0 = self.point_transform(EguiBackendCoord::from(point), bounds);
 
+        let ui = match &mut self.target {
+            Target::Ui(ui) => ui,
+            Target::Headless(target) => {
+                target.draw_pixel(p0.into(), color);
+
+                return Ok(());
+            }
+        };
+
+        let painter = ui.painter().with_clip_rect(bounds);
+
         let p1 = p0 + 1.0;
 
         let color: Color32 = EguiBackendColor::from(color).into();
@@ -280,17 +586,30 @@ impl<'a> DrawingBackend for EguiBackend<'a> {
         to: (i32, i32),
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+        let bounds = self.bounds();
 
         let p0 = self.point_transform(EguiBackendCoord::from(from), bounds);
         let p1 = self.point_transform(EguiBackendCoord::from(to), bounds);
 
+        let ui = match &mut self.target {
+            Target::Ui(ui) => ui,
+            Target::Headless(target) => {
+                target.draw_line(p0.into(), p1.into(), &shape_style(style));
+
+                return Ok(());
+            }
+        };
+
+        let painter = ui.painter().with_clip_rect(bounds);
+        let pixels_per_point = painter.ctx().pixels_per_point();
+
         let color: Color32 = EguiBackendColor::from(style.color()).into();
 
         let stroke = Stroke::new(style.stroke_width() as f32, color);
 
-        painter.line_segment([p0.into(), p1.into()], stroke);
+        let points = [p0, p1].map(|point| self.snap(point.into(), stroke.width, pixels_per_point));
+
+        self.paint(&painter, Shape::line_segment(points, stroke));
 
         Ok(())
     }
@@ -301,24 +620,30 @@ impl<'a> DrawingBackend for EguiBackend<'a> {
         style: &TStyle,
         pos: (i32, i32),
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+        let bounds = self.bounds();
 
         let pos = self.point_transform(EguiBackendCoord::from(pos), bounds);
 
-        let font_size = style.size() as f32;
-        let font_family = match style.family() {
-            PlottersFontFamily::Serif | PlottersFontFamily::SansSerif => {
-                EguiFontFamily::Proportional
+        let ui = match &mut self.target {
+            Target::Ui(ui) => ui,
+            Target::Headless(target) => {
+                let font = FontDesc::new(style.family(), style.size(), style.style())
+                    .transform(style.transform());
+                let text_style = TextStyle {
+                    font,
+                    color: style.color(),
+                    pos: style.anchor(),
+                };
+
+                target.draw_text(text, &text_style, pos.into());
+
+                return Ok(());
             }
-            PlottersFontFamily::Monospace => EguiFontFamily::Monospace,
-            PlottersFontFamily::Name(string) => EguiFontFamily::Name(string.into()),
         };
 
-        let font = FontId {
-            size: font_size,
-            family: font_family,
-        };
+        let painter = ui.painter().with_clip_rect(bounds);
+
+        let font = font_id(style);
 
         let color: Color32 = EguiBackendColor::from(style.color()).into();
 
@@ -341,57 +666,133 @@ impl<'a> DrawingBackend for EguiBackend<'a> {
             },
         ]);
         fn rotate(anchor: &mut Align2) {
-            *anchor = match anchor {
-                &mut Align2::LEFT_TOP => Align2::RIGHT_TOP,
-                &mut Align2::RIGHT_TOP => Align2::RIGHT_BOTTOM,
-                &mut Align2::RIGHT_BOTTOM => Align2::LEFT_BOTTOM,
-                &mut Align2::LEFT_BOTTOM => Align2::LEFT_TOP,
-                &mut Align2::LEFT_CENTER => Align2::CENTER_TOP,
-                &mut Align2::CENTER_TOP => Align2::RIGHT_CENTER,
-                &mut Align2::RIGHT_CENTER => Align2::CENTER_BOTTOM,
-                &mut Align2::CENTER_BOTTOM => Align2::LEFT_CENTER,
-                &mut Align2::CENTER_CENTER => Align2::CENTER_CENTER,
+            *anchor = match *anchor {
+                Align2::LEFT_TOP => Align2::RIGHT_TOP,
+                Align2::RIGHT_TOP => Align2::RIGHT_BOTTOM,
+                Align2::RIGHT_BOTTOM => Align2::LEFT_BOTTOM,
+                Align2::LEFT_BOTTOM => Align2::LEFT_TOP,
+                Align2::LEFT_CENTER => Align2::CENTER_TOP,
+                Align2::CENTER_TOP => Align2::RIGHT_CENTER,
+                Align2::RIGHT_CENTER => Align2::CENTER_BOTTOM,
+                Align2::CENTER_BOTTOM => Align2::LEFT_CENTER,
+                Align2::CENTER_CENTER => Align2::CENTER_CENTER,
             }
         }
         for _ in 0..rotations {
             rotate(&mut anchor)
         }
         let galley = painter.layout_no_wrap(text.to_string(), font, color);
-        let rect = anchor.anchor_rect(Rect::from_min_size(pos.into(), galley.size()));
+
+        // Anchor the text as it appears once turned, then find where it starts from, as egui
+        // turns text around its start
+        let size = match rotations % 2 {
+            0 => galley.size(),
+            _ => galley.size().yx(),
+        };
+        let rect = anchor.anchor_rect(Rect::from_min_size(pos.into(), size));
+        let start = match rotations % 4 {
+            0 => rect.min,
+            1 => rect.right_top(),
+            2 => rect.max,
+            _ => rect.left_bottom(),
+        };
+
         if !galley.is_empty() {
             painter.add(TextShape {
                 angle,
-                ..TextShape::new(rect.min, galley, Color32::PLACEHOLDER)
+                ..TextShape::new(start, galley, Color32::PLACEHOLDER)
             });
         }
 
         Ok(())
     }
 
+    fn estimate_text_size<TStyle: BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+        let ui = match &self.target {
+            Target::Ui(ui) => ui,
+            Target::Headless(_) => {
+                let ((left, top), (right, bottom)) = style
+                    .layout_box(text)
+                    .map_err(|error| DrawingErrorKind::FontError(Box::new(error)))?;
+
+                return Ok(((right - left) as u32, (bottom - top) as u32));
+            }
+        };
+
+        // Measure the text as egui lays it out, as it isn't scaled along with the chart
+        let galley = ui.fonts(|fonts| {
+            fonts.layout_no_wrap(text.to_string(), font_id(style), Color32::PLACEHOLDER)
+        });
+        let size = galley.size() / self.scale;
+        let (width, height) = (size.x.ceil() as u32, size.y.ceil() as u32);
+
+        // Text turned on its side spans the other way
+        match style.transform() as usize % 2 {
+            0 => Ok((width, height)),
+            _ => Ok((height, width)),
+        }
+    }
+
     fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
         &mut self,
         path: I,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+        let bounds = self.bounds();
 
-        let points: Vec<Pos2> = path
+        let points: Vec<EguiBackendCoord> = path
             .into_iter()
-            .map(|point| {
-                let point = self.point_transform(EguiBackendCoord::from(point), bounds);
-
-                point.into()
-            })
+            .map(|point| self.point_transform(EguiBackendCoord::from(point), bounds))
             .collect();
 
+        let ui = match &mut self.target {
+            Target::Ui(ui) => ui,
+            Target::Headless(target) => {
+                let points = points.into_iter().map(BackendCoord::from).collect();
+
+                target.draw_path(points, &shape_style(style));
+
+                return Ok(());
+            }
+        };
+
+        let painter = ui.painter().with_clip_rect(bounds);
+        let pixels_per_point = painter.ctx().pixels_per_point();
+
         let color: Color32 = EguiBackendColor::from(style.color()).into();
 
         let stroke = Stroke::new(style.stroke_width() as f32, color);
 
+        let mut points: Vec<Pos2> = points
+            .into_iter()
+            .map(|point| self.snap(point.into(), stroke.width, pixels_per_point))
+            .collect();
+
+        // Thin the path out to about a point per pixel, always keeping where it ends
+        if self.quality.fast {
+            let pixel = 1.0 / pixels_per_point;
+            let mut kept = 0;
+
+            for index in 1..points.len() {
+                let last = index + 1 == points.len();
+
+                if last || points[kept].distance(points[index]) >= pixel {
+                    kept += 1;
+                    points[kept] = points[index];
+                }
+            }
+
+            points.truncate(kept + 1);
+        }
+
         let shape = PathShape::line(points, stroke);
 
-        painter.add(shape);
+        self.paint(&painter, shape.into());
+
         Ok(())
     }
 
@@ -400,16 +801,30 @@ impl<'a> DrawingBackend for EguiBackend<'a> {
         vert: I,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+        let bounds = self.bounds();
 
-        let points: Vec<Pos2> = vert
+        let points: Vec<EguiBackendCoord> = vert
             .into_iter()
-            .map(|point| {
-                let point = self.point_transform(EguiBackendCoord::from(point), bounds);
+            .map(|point| self.point_transform(EguiBackendCoord::from(point), bounds))
+            .collect();
+
+        let ui = match &mut self.target {
+            Target::Ui(ui) => ui,
+            Target::Headless(target) => {
+                let points = points.into_iter().map(BackendCoord::from).collect();
+
+                target.fill_polygon(points, &shape_style(style));
 
-                point.into()
-            })
+                return Ok(());
+            }
+        };
+
+        let painter = ui.painter().with_clip_rect(bounds);
+        let pixels_per_point = painter.ctx().pixels_per_point();
+
+        let points: Vec<Pos2> = points
+            .into_iter()
+            .map(|point| self.snap(point.into(), 0.0, pixels_per_point))
             .collect();
 
         let color: Color32 = EguiBackendColor::from(style.color()).into();
@@ -418,7 +833,7 @@ impl<'a> DrawingBackend for EguiBackend<'a> {
 
         let shape = PathShape::convex_polygon(points, color, stroke);
 
-        painter.add(shape);
+        self.paint(&painter, shape.into());
 
         Ok(())
     }
@@ -1,19 +1,51 @@
 //! Structs used to simplify the process of making interactive charts
 
-use egui::{PointerState, Ui};
+use std::{
+    hash::Hash,
+    ops::Range,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use egui::{Id, Margin, Painter, PointerState, Pos2, Rect, Sense, Ui, Vec2};
+#[cfg(feature = "adaptive")]
+use instant::Instant;
 use plotters::{
     coord::Shift,
     prelude::{DrawingArea, IntoDrawingArea},
 };
+use plotters_backend::{DrawingBackend, DrawingErrorKind};
 
-use crate::EguiBackend;
+#[cfg(feature = "timechart")]
+use crate::charts::AxisBreak;
+#[cfg(feature = "adaptive")]
+use crate::AdaptiveQuality;
+use crate::{
+    backend::HeadlessBackend,
+    compat::{child_ui, shrink_rect},
+    menu::{ContextMenu, MenuAction},
+    EguiBackend, Inset, RenderQuality,
+};
 
 /// Default pitch and yaw scale for mouse rotations.
 pub const DEFAULT_MOVE_SCALE: f32 = 0.01;
 /// Default zoom scale for scroll wheel zooming.
 pub const DEFAULT_SCROLL_SCALE: f32 = 0.001;
+/// Default rate, per second, at which inertia slows down the chart after a drag.
+pub const DEFAULT_FRICTION: f32 = 5.0;
+/// Speed, in pixels per second, below which a chart moving under inertia stops.
+const MIN_INERTIA_SPEED: f32 = 5.0;
 
-#[derive(Debug, Copy, Clone)]
+/// Number of charts created so far, used to give each its own egui id.
+static CHART_COUNT: AtomicU64 = AtomicU64::new(0);
+
+type BuilderCallback<Data> =
+    Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>;
+type CsvCallback<Data> = Box<dyn Fn(&Transform, &Data) -> String>;
+pub(crate) type OverlayCallback = Box<dyn FnMut(&Painter, &ScreenTransform)>;
+pub(crate) type BackgroundCallback = Box<dyn FnMut(&Painter, Rect, &ScreenTransform)>;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Transformations to be applied to your chart. Is modified by user input(if the mouse is enabled) and
 /// used by Chart::draw() and your builder callback.
 ///
@@ -56,6 +88,144 @@ impl Default for Transform {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Snapshot of the state of a chart that changes as it is used, such as how far it is zoomed
+/// and what it shows, as opposed to how it is configured. Plain data that is cheap to capture
+/// and compare, so applications can keep a history of them for undo and redo, or move a chart
+/// between workspace tabs. Serializable with the `serde` feature.
+pub struct ChartState {
+    /// Pitch, yaw, scale, and offset of the view.
+    pub transform: Transform,
+    /// What the `on_overlay` callback draws was shown.
+    pub overlay: bool,
+}
+
+impl Default for ChartState {
+    fn default() -> Self {
+        Self {
+            transform: Transform::default(),
+            overlay: true,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Maps positions between a chart's own coordinates and the screen. Given to overlay and
+/// background callbacks along with a painter, so they can draw decorations lined up with the
+/// chart.
+///
+/// For a [`Chart`], chart coordinates are the pixels the builder callback draws in, relative to
+/// the top left of the chart before its transform is applied. Premade charts map their data
+/// coordinates instead, such as the X and Y values of a time chart.
+pub struct ScreenTransform {
+    rect: Rect,
+    transform: Transform,
+    /// Screen position of a point is `point * scale + offset`.
+    scale: Vec2,
+    offset: Vec2,
+    /// Break in the Y axis of the data mapped. Y values are folded by it before being scaled.
+    #[cfg(feature = "timechart")]
+    y_break: Option<AxisBreak>,
+}
+
+impl ScreenTransform {
+    /// Transform from the pixels of a chart drawn in the given rect to the screen.
+    pub(crate) fn new(rect: Rect, transform: Transform) -> Self {
+        let scale = transform.scale as f32;
+        let center = rect.size() / 2.0;
+
+        Self {
+            rect,
+            transform,
+            scale: Vec2::splat(scale),
+            offset: center * (1.0 - scale)
+                + Vec2::new(transform.x as f32, transform.y as f32)
+                + rect.min.to_vec2(),
+            #[cfg(feature = "timechart")]
+            y_break: None,
+        }
+    }
+
+    /// Map data coordinates instead of pixels, with the X and Y ranges spanning the given plot
+    /// rect of the chart's pixels. Y values go up while pixels go down. If the Y axis has a break,
+    /// the Y range is of folded values.
+    #[cfg(feature = "timechart")]
+    pub(crate) fn with_data(
+        self,
+        x: Range<f32>,
+        y: Range<f32>,
+        y_break: Option<AxisBreak>,
+        plot: Rect,
+    ) -> Self {
+        let data_scale = Vec2::new(
+            plot.width() / (x.end - x.start),
+            -plot.height() / (y.end - y.start),
+        );
+        let data_offset = Vec2::new(
+            plot.left() - x.start * data_scale.x,
+            plot.bottom() - y.start * data_scale.y,
+        );
+
+        Self {
+            scale: self.scale * data_scale,
+            offset: self.scale * data_offset + self.offset,
+            y_break,
+            ..self
+        }
+    }
+
+    #[inline]
+    /// Map a rect in chart coordinates to the screen.
+    pub fn rect_to_screen(&self, rect: Rect) -> Rect {
+        Rect::from_two_pos(self.to_screen(rect.min), self.to_screen(rect.max))
+    }
+
+    #[inline]
+    /// Get the rect the chart is drawn in on screen.
+    pub fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    #[inline]
+    /// Get the transform the chart was drawn with.
+    pub fn get_transform(&self) -> Transform {
+        self.transform
+    }
+
+    #[inline]
+    /// Map a position in chart coordinates to the screen.
+    pub fn to_screen(&self, pos: Pos2) -> Pos2 {
+        #[cfg(feature = "timechart")]
+        let pos = match self.y_break {
+            Some(y_break) => Pos2::new(pos.x, y_break.fold(pos.y)),
+            None => pos,
+        };
+
+        Pos2::new(
+            pos.x * self.scale.x + self.offset.x,
+            pos.y * self.scale.y + self.offset.y,
+        )
+    }
+
+    #[inline]
+    /// Map a position on screen to chart coordinates.
+    pub fn from_screen(&self, pos: Pos2) -> Pos2 {
+        let pos = Pos2::new(
+            (pos.x - self.offset.x) / self.scale.x,
+            (pos.y - self.offset.y) / self.scale.y,
+        );
+
+        #[cfg(feature = "timechart")]
+        let pos = match self.y_break {
+            Some(y_break) => Pos2::new(pos.x, y_break.unfold(pos.y)),
+            None => pos,
+        };
+
+        pos
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 /// Mouse buttons that can be bound to chart actions
 pub enum MouseButton {
@@ -82,19 +252,23 @@ impl MouseButton {
 /// MouseConfig allows you to change the ways the user interacts with your chart in the following
 /// ways:
 ///  * `drag`, `rotate`, & `zoom` - Enables dragging, rotating, and zooming in on your plots with
-///  mouse controls.
+///    mouse controls.
 ///  * `pitch_scale` & `yaw_scale` - Modifies how quickly the pitch and yaw are rotated when rotating with the
-///  mouse.
+///    mouse.
 ///  * `zoom_scale` - Modifies how quickly you zoom in/out.
 ///  * `drag_bind` - Mouse button bound to dragging your plot.
 ///  * `rotate_bind` - Mouse button bound to rotating your plot.
+///  * `inertia` & `friction` - Keep the plot moving after a drag is released, slowing down
+///    exponentially at the rate set by the friction.
 pub struct MouseConfig {
     drag: bool,
     rotate: bool,
     zoom: bool,
+    inertia: bool,
     yaw_scale: f32,
     pitch_scale: f32,
     zoom_scale: f32,
+    friction: f32,
     drag_bind: MouseButton,
     rotate_bind: MouseButton,
 }
@@ -105,9 +279,11 @@ impl Default for MouseConfig {
             drag: false,
             rotate: false,
             zoom: false,
+            inertia: false,
             yaw_scale: DEFAULT_MOVE_SCALE,
             pitch_scale: DEFAULT_MOVE_SCALE,
             zoom_scale: DEFAULT_SCROLL_SCALE,
+            friction: DEFAULT_FRICTION,
             drag_bind: MouseButton::Middle,
             rotate_bind: MouseButton::Primary,
         }
@@ -122,9 +298,11 @@ impl MouseConfig {
             drag: true,
             rotate: true,
             zoom: true,
+            inertia: false,
             yaw_scale: DEFAULT_MOVE_SCALE,
             pitch_scale: DEFAULT_MOVE_SCALE,
             zoom_scale: DEFAULT_SCROLL_SCALE,
+            friction: DEFAULT_FRICTION,
             drag_bind: MouseButton::Middle,
             rotate_bind: MouseButton::Primary,
         }
@@ -188,6 +366,35 @@ impl MouseConfig {
         self
     }
 
+    #[inline]
+    /// Enable/disable inertia, keeping the chart moving after a drag is released.
+    pub fn set_inertia(&mut self, inertia: bool) {
+        self.inertia = inertia
+    }
+
+    #[inline]
+    /// Enable/disable inertia. Consumes self.
+    pub fn inertia(mut self, inertia: bool) -> Self {
+        self.set_inertia(inertia);
+
+        self
+    }
+
+    #[inline]
+    /// Change how quickly the chart slows down under inertia. Each second the chart loses this
+    /// much of its speed exponentially, so higher values stop it sooner.
+    pub fn set_friction(&mut self, friction: f32) {
+        self.friction = friction
+    }
+
+    #[inline]
+    /// Change how quickly the chart slows down under inertia. Consumes self.
+    pub fn friction(mut self, friction: f32) -> Self {
+        self.set_friction(friction);
+
+        self
+    }
+
     #[inline]
     /// Change the pitch scale.
     pub fn set_pitch_scale(&mut self, scale: f32) {
@@ -210,33 +417,127 @@ impl MouseConfig {
 /// being powerful enough for your application. You can manipulate the
 /// following properties of a chart to get the effects you want:
 ///  * `builder_cb` - Callback used to populate the chart. Is provided a DrawingArea and the
-///  chart's `data`.
+///    chart's `data`.
 ///  * `mouse` - Mouse configuration. Configure how you wish the mouse to affect/manipulate the
-///  chart.
+///    chart.
+///  * `context_menu` - Right-click menu offering actions like resetting the view. See
+///    [`ContextMenu`].
+///  * `id_source` - Source of the egui id the chart's widgets are keyed by. Every chart is given
+///    its own id when created, so charts sharing a parent Ui don't clash; set one to keep the
+///    chart's egui state across restarts.
+///  * `csv_cb` - Callback used to turn the chart's `data` into CSV, used by
+///    `visible_data_csv` and the context menu's "Export CSV" entry. Is provided the chart's
+///    current transform, so it can leave out what's out of view.
+///  * `inset` - Magnifier redrawing the chart zoomed in around the pointer in one of its
+///    corners. See [`Inset`].
+///  * `plot_insets` - Space between the edges of the chart and its plot area, such as the
+///    margins and label areas. The mouse only pans, zooms, and rotates the chart from within the
+///    plot area, whose rect on screen is given by `get_plot_rect` once drawn.
+///  * `render_quality` - How carefully the chart is drawn, trading quality for speed. See
+///    [`RenderQuality`].
+///  * `adaptive_quality` - Controller switching the chart to fast mode while it moves and takes
+///    too long to draw, restoring full quality once it's still. Requires the `adaptive`
+///    feature. See `AdaptiveQuality`.
+///  * `scale_limits` & `pan_bounds` - Constraints on the transform, keeping the user from zooming
+///    in or out too far and from dragging the chart away from its contents.
+///  * `on_overlay` - Callback drawing on top of the chart each frame, given a painter clipped to
+///    the chart and a [`ScreenTransform`] from the builder callback's pixels to the screen.
+///  * `on_background` - Callback drawing beneath the chart each frame, before the builder
+///    callback, given the rect the chart is drawn across on screen along with the painter and
+///    transform.
+///  * `draw_headless` - Draws the chart to any plotters backend instead of a Ui, so the same
+///    chart can render static images, such as with a `BitMapBackend` or `SVGBackend`.
 ///  * `data` - A Box of data of any type to be stored with the chart. Provided so that you can modify data
-///  without having to specify a new callback during runtime. For example, `examples/parachart.rs`
-///  uses it to store the range so it can be changed during runtime.
+///    without having to specify a new callback during runtime. For example, `examples/parachart.rs`
+///    uses it to store the range so it can be changed during runtime.
 ///
 ///  ## Examples
 ///  See `examples/3dchart.rs` and `examples/parachart.rs` for examples of usage.
 pub struct Chart<Data> {
+    id: Id,
     transform: Transform,
+    home: Option<Transform>,
+    /// Smallest and largest scale the chart can be zoomed to.
+    scale_limits: Option<(f64, f64)>,
+    /// Area, in pixels relative to the untransformed chart, the center of the view is kept in.
+    pan_bounds: Option<(Range<f32>, Range<f32>)>,
+    /// Speed the chart is moving at under inertia, in pixels per second.
+    velocity: (f32, f32),
+    /// Movement under inertia not yet applied to the whole pixel offset.
+    drift: (f32, f32),
     mouse: MouseConfig,
-    builder_cb: Option<Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>>,
+    quality: RenderQuality,
+    #[cfg(feature = "adaptive")]
+    adaptive: Option<AdaptiveQuality>,
+    /// Space between the edges of the chart and the area the mouse interacts with.
+    plot_insets: Margin,
+    /// Rect the chart was last drawn in.
+    rect: Option<Rect>,
+    /// Draw what the overlay callback draws.
+    overlay: bool,
+    inset: Option<Inset>,
+    context_menu: ContextMenu<Data>,
+    builder_cb: Option<BuilderCallback<Data>>,
+    csv_cb: Option<CsvCallback<Data>>,
+    /// Undoes whatever else a premade chart changes its view with when the view is reset.
+    reset_cb: Option<fn(&mut Data)>,
+    overlay_cb: Option<OverlayCallback>,
+    background_cb: Option<BackgroundCallback>,
     data: Data,
 }
 
 impl<Data> Chart<Data> {
     /// Create a new chart with default settings (if not using data supply ())
     pub fn new(data: Data) -> Self {
+        let count = CHART_COUNT.fetch_add(1, Ordering::Relaxed);
+
         Self {
+            id: Id::new(("egui_plotter_chart", count)),
             transform: Transform::default(),
+            home: None,
+            scale_limits: None,
+            pan_bounds: None,
+            velocity: (0.0, 0.0),
+            drift: (0.0, 0.0),
             mouse: MouseConfig::default(),
+            quality: RenderQuality::new(),
+            #[cfg(feature = "adaptive")]
+            adaptive: None,
+            plot_insets: Margin::ZERO,
+            rect: None,
+            overlay: true,
+            inset: None,
+            context_menu: ContextMenu::default(),
             builder_cb: None,
+            csv_cb: None,
+            reset_cb: None,
+            overlay_cb: None,
+            background_cb: None,
             data,
         }
     }
 
+    #[inline]
+    /// Set the source of the egui id the chart's widgets are keyed by, replacing the id it was
+    /// given when created. Must be unique among the charts drawn.
+    pub fn set_id_source(&mut self, id_source: impl Hash) {
+        self.id = Id::new(id_source)
+    }
+
+    #[inline]
+    /// Set the source of the egui id the chart's widgets are keyed by. Consumes self.
+    pub fn id_source(mut self, id_source: impl Hash) -> Self {
+        self.set_id_source(id_source);
+
+        self
+    }
+
+    #[inline]
+    /// Get the egui id the chart's widgets are keyed by, unique to the chart.
+    pub fn get_id(&self) -> Id {
+        self.id
+    }
+
     #[inline]
     /// Enable or disable mouse controls.
     pub fn set_mouse(&mut self, mouse: MouseConfig) {
@@ -251,26 +552,162 @@ impl<Data> Chart<Data> {
         self
     }
 
+    #[inline]
+    /// Set how carefully the chart is drawn. See [`RenderQuality`].
+    pub fn set_render_quality(&mut self, quality: RenderQuality) {
+        self.quality = quality
+    }
+
+    #[inline]
+    /// Set how carefully the chart is drawn. Consumes self.
+    pub fn render_quality(mut self, quality: RenderQuality) -> Self {
+        self.set_render_quality(quality);
+
+        self
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Set the controller lowering the chart's quality while it's too slow to draw. Set to None
+    /// to always draw at the render quality. See [`AdaptiveQuality`].
+    pub fn set_adaptive_quality(&mut self, adaptive: Option<AdaptiveQuality>) {
+        self.adaptive = adaptive
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Set the controller lowering the chart's quality while it's too slow to draw. Consumes
+    /// self.
+    pub fn adaptive_quality(mut self, adaptive: Option<AdaptiveQuality>) -> Self {
+        self.set_adaptive_quality(adaptive);
+
+        self
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Get the controller lowering the chart's quality, if any, along with how long the chart
+    /// takes to draw.
+    pub fn get_adaptive_quality(&self) -> Option<&AdaptiveQuality> {
+        self.adaptive.as_ref()
+    }
+
+    #[cfg(all(feature = "timechart", feature = "adaptive"))]
+    #[inline]
+    /// Note that the chart's contents are moving, such as during playback, so its quality
+    /// isn't restored while they do.
+    pub(crate) fn mark_active(&mut self) {
+        if let Some(adaptive) = &mut self.adaptive {
+            adaptive.mark_active();
+        }
+    }
+
+    #[inline]
+    /// Set the space between the edges of the chart and its plot area. The mouse only pans,
+    /// zooms, and rotates the chart from within the plot area.
+    pub fn set_plot_insets(&mut self, plot_insets: Margin) {
+        self.plot_insets = plot_insets
+    }
+
+    #[inline]
+    /// Set the space between the edges of the chart and its plot area. Consumes self.
+    pub fn plot_insets(mut self, plot_insets: Margin) -> Self {
+        self.set_plot_insets(plot_insets);
+
+        self
+    }
+
+    #[inline]
+    /// Get the rect of the plot area on screen, the chart's rect less its plot insets. None if
+    /// the chart hasn't been drawn yet.
+    pub fn get_plot_rect(&self) -> Option<Rect> {
+        self.rect.map(|rect| shrink_rect(rect, self.plot_insets))
+    }
+
+    #[inline]
+    /// Set the magnifying inset. Set to None to hide it.
+    pub fn set_inset(&mut self, inset: Option<Inset>) {
+        self.inset = inset
+    }
+
+    #[inline]
+    /// Set the magnifying inset. Consumes self.
+    pub fn inset(mut self, inset: Option<Inset>) -> Self {
+        self.set_inset(inset);
+
+        self
+    }
+
+    #[inline]
+    /// Set the right-click context menu.
+    pub fn set_context_menu(&mut self, context_menu: ContextMenu<Data>) {
+        self.context_menu = context_menu
+    }
+
+    #[inline]
+    /// Set the right-click context menu. Consumes self.
+    pub fn context_menu(mut self, context_menu: ContextMenu<Data>) -> Self {
+        self.set_context_menu(context_menu);
+
+        self
+    }
+
     #[inline]
     /// Set the builder callback.
-    pub fn set_builder_cb(
-        &mut self,
-        builder_cb: Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>,
-    ) {
+    pub fn set_builder_cb(&mut self, builder_cb: BuilderCallback<Data>) {
         self.builder_cb = Some(builder_cb)
     }
 
     #[inline]
     /// Set the builder callback. Consumes self.
-    pub fn builder_cb(
-        mut self,
-        builder_cb: Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>,
-    ) -> Self {
+    pub fn builder_cb(mut self, builder_cb: BuilderCallback<Data>) -> Self {
         self.set_builder_cb(builder_cb);
 
         self
     }
 
+    #[inline]
+    /// Set the callback used to export the chart's data as CSV.
+    pub fn set_csv_cb(&mut self, csv_cb: CsvCallback<Data>) {
+        self.csv_cb = Some(csv_cb)
+    }
+
+    #[inline]
+    /// Set the callback used to export the chart's data as CSV. Consumes self.
+    pub fn csv_cb(mut self, csv_cb: CsvCallback<Data>) -> Self {
+        self.set_csv_cb(csv_cb);
+
+        self
+    }
+
+    #[inline]
+    /// Set the callback drawing on top of the chart after it is drawn.
+    pub fn set_on_overlay(&mut self, overlay_cb: OverlayCallback) {
+        self.overlay_cb = Some(overlay_cb)
+    }
+
+    #[inline]
+    /// Set the callback drawing on top of the chart after it is drawn. Consumes self.
+    pub fn on_overlay(mut self, overlay_cb: OverlayCallback) -> Self {
+        self.set_on_overlay(overlay_cb);
+
+        self
+    }
+
+    #[inline]
+    /// Set the callback drawing beneath the chart before it is drawn.
+    pub fn set_on_background(&mut self, background_cb: BackgroundCallback) {
+        self.background_cb = Some(background_cb)
+    }
+
+    #[inline]
+    /// Set the callback drawing beneath the chart before it is drawn. Consumes self.
+    pub fn on_background(mut self, background_cb: BackgroundCallback) -> Self {
+        self.set_on_background(background_cb);
+
+        self
+    }
+
     #[inline]
     /// Set the pitch of the chart.
     pub fn set_pitch(&mut self, pitch: f64) {
@@ -313,6 +750,118 @@ impl<Data> Chart<Data> {
         self
     }
 
+    #[inline]
+    /// Get the chart's current transform.
+    pub fn get_transform(&self) -> Transform {
+        self.transform
+    }
+
+    #[inline]
+    /// Replace the chart's transform, moving the view.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform
+    }
+
+    #[inline]
+    /// Show/hide what the `on_overlay` callback draws.
+    pub fn set_show_overlay(&mut self, overlay: bool) {
+        self.overlay = overlay
+    }
+
+    #[inline]
+    /// Show/hide what the `on_overlay` callback draws. Consumes self.
+    pub fn show_overlay(mut self, overlay: bool) -> Self {
+        self.set_show_overlay(overlay);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if what the `on_overlay` callback draws is shown.
+    pub fn get_show_overlay(&self) -> bool {
+        self.overlay
+    }
+
+    #[inline]
+    /// Capture the view and what the chart shows. See [`ChartState`].
+    pub fn state(&self) -> ChartState {
+        ChartState {
+            transform: self.transform,
+            overlay: self.overlay,
+        }
+    }
+
+    /// Restore the view and what the chart shows from a captured state, stopping any movement
+    /// under inertia.
+    pub fn restore(&mut self, state: &ChartState) {
+        self.transform = state.transform;
+        self.velocity = (0.0, 0.0);
+        self.drift = (0.0, 0.0);
+        self.overlay = state.overlay;
+    }
+
+    #[inline]
+    /// Limit how far the chart can be zoomed, as the smallest and largest scale allowed. Set to
+    /// None to remove the limits.
+    pub fn set_scale_limits(&mut self, scale_limits: Option<(f64, f64)>) {
+        self.scale_limits = scale_limits
+    }
+
+    #[inline]
+    /// Limit how far the chart can be zoomed, as the smallest and largest scale allowed.
+    /// Consumes self.
+    pub fn scale_limits(mut self, scale_limits: Option<(f64, f64)>) -> Self {
+        self.set_scale_limits(scale_limits);
+
+        self
+    }
+
+    #[inline]
+    /// Limit how far the chart can be dragged. The point at the center of the view is kept
+    /// within the given X and Y ranges, in pixels relative to the top left of the chart before
+    /// it is transformed. Set to None to remove the bounds.
+    pub fn set_pan_bounds(&mut self, pan_bounds: Option<(Range<f32>, Range<f32>)>) {
+        self.pan_bounds = pan_bounds
+    }
+
+    #[inline]
+    /// Limit how far the chart can be dragged. Consumes self.
+    pub fn pan_bounds(mut self, pan_bounds: Option<(Range<f32>, Range<f32>)>) -> Self {
+        self.set_pan_bounds(pan_bounds);
+
+        self
+    }
+
+    #[inline]
+    /// Restore the pitch, yaw, scale, and offset the chart had when it was first drawn.
+    pub fn reset_view(&mut self) {
+        if let Some(home) = self.home {
+            self.transform = home;
+        }
+
+        if let Some(reset_cb) = self.reset_cb {
+            reset_cb(&mut self.data);
+        }
+    }
+
+    #[cfg(feature = "timechart")]
+    #[inline]
+    /// Set the callback undoing whatever else a premade chart changes its view with, such as
+    /// the ranges a time chart was zoomed to fit, whenever the view is reset. Consumes self.
+    pub(crate) fn on_reset(mut self, reset_cb: fn(&mut Data)) -> Self {
+        self.reset_cb = Some(reset_cb);
+
+        self
+    }
+
+    #[inline]
+    /// Return the chart's data as CSV, or None if no CSV callback has been set.
+    pub fn visible_data_csv(&self) -> Option<String> {
+        self.csv_cb
+            .as_ref()
+            .map(|csv_cb| csv_cb(&self.transform, &self.data))
+    }
+
     #[inline]
     /// Get the data of the chart as a reference.
     pub fn get_data(&self) -> &Data {
@@ -327,15 +876,33 @@ impl<Data> Chart<Data> {
 
     /// Call the callback and draw the chart to a UI element.
     pub fn draw(&mut self, ui: &mut Ui) {
+        let rect = ui.max_rect();
+        let plot_rect = shrink_rect(rect, self.plot_insets);
+        #[cfg(feature = "adaptive")]
+        let previous = self.transform;
         let transform = &mut self.transform;
 
+        // Remember the view the chart started with so it can be reset to later
+        self.home.get_or_insert(*transform);
+        self.rect = Some(rect);
+
+        let mut moving = false;
+
         // First, get mouse data
         ui.input(|input| {
             let pointer = &input.pointer;
             let delta = pointer.delta();
 
+            // Only move the chart with the mouse if it was pressed or is hovering over the plot
+            let pressed = pointer
+                .press_origin()
+                .is_some_and(|pos| plot_rect.contains(pos));
+            let hovered = pointer
+                .hover_pos()
+                .is_some_and(|pos| plot_rect.contains(pos));
+
             // Adjust the pitch/yaw if the primary button is pressed and rotation is enabled
-            if self.mouse.rotate && self.mouse.rotate_bind.is_down(pointer) {
+            if self.mouse.rotate && pressed && self.mouse.rotate_bind.is_down(pointer) {
                 let pitch_delta = delta.y * self.mouse.pitch_scale;
                 let yaw_delta = delta.x * self.mouse.yaw_scale;
 
@@ -344,16 +911,43 @@ impl<Data> Chart<Data> {
             }
 
             // Adjust the x/y if the middle button is down and dragging is enabled
-            if self.mouse.drag && self.mouse.drag_bind.is_down(pointer) {
+            if self.mouse.drag && pressed && self.mouse.drag_bind.is_down(pointer) {
                 let x_delta = delta.x;
                 let y_delta = delta.y;
 
                 transform.x += x_delta as i32;
                 transform.y += y_delta as i32;
+
+                // Track the speed of the drag so it can be carried on once released
+                let dt = input.stable_dt.max(f32::EPSILON);
+
+                self.velocity = (x_delta / dt, y_delta / dt);
+                self.drift = (0.0, 0.0);
+            } else if self.mouse.drag && self.mouse.inertia {
+                let (x_velocity, y_velocity) = self.velocity;
+
+                if x_velocity.hypot(y_velocity) >= MIN_INERTIA_SPEED {
+                    let dt = input.stable_dt;
+                    let x_drift = self.drift.0 + x_velocity * dt;
+                    let y_drift = self.drift.1 + y_velocity * dt;
+
+                    transform.x += x_drift.trunc() as i32;
+                    transform.y += y_drift.trunc() as i32;
+
+                    let decay = (-self.mouse.friction * dt).exp();
+
+                    self.velocity = (x_velocity * decay, y_velocity * decay);
+                    self.drift = (x_drift.fract(), y_drift.fract());
+                    moving = true;
+                } else {
+                    self.velocity = (0.0, 0.0);
+                }
+            } else {
+                self.velocity = (0.0, 0.0);
             }
 
             // Adjust zoom if zoom is enabled
-            if self.mouse.zoom {
+            if self.mouse.zoom && hovered {
                 let scale_delta = input.smooth_scroll_delta.y * self.mouse.zoom_scale;
 
                 // !TODO! make scaling exponential
@@ -361,9 +955,71 @@ impl<Data> Chart<Data> {
             }
         });
 
+        // Inertia moves the chart without any input, so keep redrawing until it stops
+        if moving {
+            ui.ctx().request_repaint();
+        }
+
+        // Keep the view within its limits, however it was changed
+        if let Some((min_scale, max_scale)) = self.scale_limits {
+            transform.scale = transform.scale.min(max_scale).max(min_scale);
+        }
+
+        if let Some((x_bounds, y_bounds)) = &self.pan_bounds {
+            let scale = transform.scale as f32;
+            let (center_x, center_y) = (rect.width() / 2.0, rect.height() / 2.0);
+
+            // The point at the center of the view is `center - offset / scale`
+            let clamp = |offset: i32, center: f32, bounds: &Range<f32>| {
+                let min = (center - bounds.end) * scale;
+                let max = (center - bounds.start) * scale;
+
+                match min <= max {
+                    true => (offset as f32).clamp(min, max) as i32,
+                    false => offset,
+                }
+            };
+
+            let (x, y) = (
+                clamp(transform.x, center_x, x_bounds),
+                clamp(transform.y, center_y, y_bounds),
+            );
+
+            // Stop any inertia carrying the chart into its bounds
+            if x != transform.x {
+                self.velocity.0 = 0.0;
+            }
+
+            if y != transform.y {
+                self.velocity.1 = 0.0;
+            }
+
+            (transform.x, transform.y) = (x, y);
+        }
+
+        // Time the frame, drawing it in fast mode while the controller has lowered the quality
+        #[cfg(feature = "adaptive")]
+        let (active, started) = (moving || *transform != previous, Instant::now());
+        #[cfg(feature = "adaptive")]
+        let quality = match &self.adaptive {
+            Some(adaptive) if adaptive.is_degraded() => self.quality.fast(true),
+            _ => self.quality,
+        };
+        #[cfg(not(feature = "adaptive"))]
+        let quality = self.quality;
+
+        if let Some(background_cb) = &mut self.background_cb {
+            let screen_transform = ScreenTransform::new(rect, *transform);
+            let area =
+                screen_transform.rect_to_screen(Rect::from_min_size(Pos2::ZERO, rect.size()));
+
+            background_cb(&ui.painter_at(rect), area, &screen_transform);
+        }
+
         let mut area = EguiBackend::new(ui)
             .offset((transform.x, transform.y))
             .scale(transform.scale as f32)
+            .render_quality(quality)
             .into_drawing_area();
 
         if let Some(cb) = &mut self.builder_cb {
@@ -371,5 +1027,118 @@ impl<Data> Chart<Data> {
         }
 
         area.present().unwrap();
+
+        // Redraw the chart zoomed in within the inset, clipped to it
+        let inset = self
+            .inset
+            .as_ref()
+            .and_then(|inset| Some((inset, inset.layout(ui, transform)?)));
+
+        if let (Some((inset, (inset_rect, inset_transform))), Some(cb)) =
+            (inset, &mut self.builder_cb)
+        {
+            let mut inset_ui = child_ui(ui, rect, self.id.with("inset"));
+
+            inset_ui.set_clip_rect(inset_rect.intersect(ui.clip_rect()));
+            inset.draw_background(&inset_ui, inset_rect);
+
+            let mut area = EguiBackend::new(&mut inset_ui)
+                .offset((inset_transform.x, inset_transform.y))
+                .scale(inset_transform.scale as f32)
+                .render_quality(quality)
+                .into_drawing_area();
+
+            cb(&mut area, &inset_transform, &self.data);
+
+            area.present().unwrap();
+
+            inset.draw_border(ui, inset_rect);
+        }
+
+        if let (Some(overlay_cb), true) = (&mut self.overlay_cb, self.overlay) {
+            let painter = ui.painter_at(rect);
+
+            overlay_cb(&painter, &ScreenTransform::new(rect, *transform));
+        }
+
+        #[cfg(feature = "adaptive")]
+        if let Some(adaptive) = &mut self.adaptive {
+            adaptive.record(ui.ctx(), started.elapsed(), active);
+        }
+
+        if self.context_menu.is_empty() {
+            return;
+        }
+
+        let response = ui.interact(rect, self.id, Sense::click());
+
+        match self.context_menu.show(ui, &response, &mut self.data) {
+            MenuAction::None => {}
+            MenuAction::ResetView => self.reset_view(),
+            MenuAction::ExportCsv => {
+                if let Some(csv) = self.visible_data_csv() {
+                    ui.ctx().copy_text(csv);
+                }
+            }
+        }
+    }
+
+    /// Call the callback and draw the chart to any plotters backend, such as a `BitMapBackend`
+    /// or `SVGBackend`, without egui. The chart is drawn with its current transform, as no
+    /// mouse input, inset, background, overlay, or context menu is handled, and over whatever
+    /// the backend already holds, so fill the backend first for an opaque background. The
+    /// chart's render quality is ignored, as the backend decides how it draws.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let transform = &self.transform;
+
+        self.home.get_or_insert(*transform);
+
+        let mut headless = HeadlessBackend::new(backend);
+
+        let mut area = EguiBackend::headless(&mut headless)
+            .offset((transform.x, transform.y))
+            .scale(transform.scale as f32)
+            .into_drawing_area();
+
+        if let Some(cb) = &mut self.builder_cb {
+            cb(&mut area, transform, &self.data);
+        }
+
+        drop(area);
+
+        headless.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charts_get_their_own_ids() {
+        let (a, b) = (Chart::new(()), Chart::new(()));
+
+        assert_ne!(a.get_id(), b.get_id());
+        assert_eq!(a.id_source("a").get_id(), Id::new("a"));
+    }
+
+    #[test]
+    fn restoring_brings_back_the_view_and_overlay() {
+        let mut chart = Chart::new(());
+        let shown = chart.state();
+
+        chart.set_transform(Transform {
+            scale: 2.0,
+            ..Default::default()
+        });
+        chart.set_show_overlay(false);
+        assert!(!chart.get_show_overlay());
+
+        chart.restore(&shown);
+        assert_eq!(chart.state(), shown);
+        assert!(chart.get_show_overlay());
     }
 }
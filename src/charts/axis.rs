@@ -0,0 +1,61 @@
+//! Axis placement and scaling options shared by the premade charts.
+
+use plotters::chart::LabelAreaPosition;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// Side of the chart the X axis and its labels are placed on.
+pub enum XAxisPosition {
+    #[default]
+    Bottom,
+    Top,
+    /// Don't draw the axis or its labels, and don't reserve any space for them.
+    Hidden,
+}
+
+impl XAxisPosition {
+    #[inline]
+    /// Label area the axis is drawn in, or None if the axis is hidden.
+    pub(crate) fn label_area(&self) -> Option<LabelAreaPosition> {
+        match self {
+            Self::Bottom => Some(LabelAreaPosition::Bottom),
+            Self::Top => Some(LabelAreaPosition::Top),
+            Self::Hidden => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// Side of the chart the Y axis and its labels are placed on.
+pub enum YAxisPosition {
+    #[default]
+    Left,
+    Right,
+    /// Don't draw the axis or its labels, and don't reserve any space for them.
+    Hidden,
+}
+
+impl YAxisPosition {
+    #[inline]
+    /// Label area the axis is drawn in, or None if the axis is hidden.
+    pub(crate) fn label_area(&self) -> Option<LabelAreaPosition> {
+        match self {
+            Self::Left => Some(LabelAreaPosition::Left),
+            Self::Right => Some(LabelAreaPosition::Right),
+            Self::Hidden => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// How the ranges of a time chart's axes follow its playback.
+pub enum AutoscaleMode {
+    /// Fit every point, including the ones yet to be played.
+    Full,
+    #[default]
+    /// Fit the points played so far, growing as new extremes appear.
+    GrowOnly,
+    /// Fit the Y axis to the points played so far within the X range in view, as the chart is
+    /// zoomed and dragged. The X axis grows with playback until the chart is zoomed or dragged,
+    /// after which the window in view is kept.
+    VisibleWindow,
+}
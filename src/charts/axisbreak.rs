@@ -0,0 +1,256 @@
+//! Breaks in an axis, skipping a range of values so widely separated clusters of data can share a
+//! plot without a log scale.
+
+use std::ops::Range;
+
+use plotters::{
+    coord::{
+        ranged1d::{DefaultFormatting, KeyPointHint, Ranged},
+        types::RangedCoordf32,
+        Shift,
+    },
+    element::{PathElement, Rectangle},
+    prelude::DrawingArea,
+    style::{Color, RGBAColor, ShapeStyle},
+};
+use plotters_backend::DrawingBackend;
+
+/// Height of the gap a break is drawn as, relative to the height of the plot.
+const BREAK_GAP: f32 = 0.03;
+/// Distance the break extends past the sides of the plot, in pixels, so it cuts through the axes.
+const BREAK_OVERHANG: i32 = 4;
+/// Width and height of each zig of the lines drawn along the break, in pixels.
+const BREAK_WAVE: i32 = 6;
+const BREAK_AMPLITUDE: i32 = 2;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Range of values skipped by an axis, folded into a gap a fraction of the plot high. Values are
+/// mapped to the plot through their folded value, which is continuous across the break.
+pub(crate) struct AxisBreak {
+    start: f32,
+    end: f32,
+    /// Span of folded values the skipped range is squeezed into.
+    gap: f32,
+}
+
+impl AxisBreak {
+    /// Break skipping the part of `skipped` within the range shown. None if none of it is shown,
+    /// or if it would leave nothing else to show.
+    pub(crate) fn new(range: &Range<f32>, skipped: &Range<f32>) -> Option<Self> {
+        let start = skipped.start.max(range.start);
+        let end = skipped.end.min(range.end);
+        let kept = (range.end - range.start) - (end - start);
+
+        if !(end > start && kept > 0.0) {
+            return None;
+        }
+
+        Some(Self {
+            start,
+            end,
+            gap: kept * BREAK_GAP / (1.0 - BREAK_GAP),
+        })
+    }
+
+    #[inline]
+    /// Get the first value skipped.
+    pub(crate) fn start(&self) -> f32 {
+        self.start
+    }
+
+    #[inline]
+    /// Get the last value skipped.
+    pub(crate) fn end(&self) -> f32 {
+        self.end
+    }
+
+    /// Fold a value, moving the values past the break down to close the range it skips.
+    pub(crate) fn fold(&self, value: f32) -> f32 {
+        if value <= self.start {
+            value
+        } else if value >= self.end {
+            value - (self.end - self.start) + self.gap
+        } else {
+            self.start + (value - self.start) / (self.end - self.start) * self.gap
+        }
+    }
+
+    /// Unfold a folded value back to the value it was folded from.
+    pub(crate) fn unfold(&self, folded: f32) -> f32 {
+        if folded <= self.start {
+            folded
+        } else if folded >= self.start + self.gap {
+            folded + (self.end - self.start) - self.gap
+        } else {
+            self.start + (folded - self.start) / self.gap * (self.end - self.start)
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Linear axis with an optional break, for building plotters charts with. Key points are placed
+/// on either side of the break, never within it.
+pub(crate) struct BrokenAxis {
+    range: Range<f32>,
+    axis_break: Option<AxisBreak>,
+    /// Folded values of the range, mapped linearly to pixels.
+    folded: RangedCoordf32,
+}
+
+impl BrokenAxis {
+    /// Axis spanning the range, skipping the `skipped` values within it if any.
+    pub(crate) fn new(range: Range<f32>, skipped: Option<&Range<f32>>) -> Self {
+        let axis_break = skipped.and_then(|skipped| AxisBreak::new(&range, skipped));
+
+        let folded = match axis_break {
+            Some(axis_break) => axis_break.fold(range.start)..axis_break.fold(range.end),
+            None => range.clone(),
+        };
+
+        Self {
+            range,
+            axis_break,
+            folded: folded.into(),
+        }
+    }
+
+    #[inline]
+    /// Get the break of the axis, if it's shown.
+    pub(crate) fn get_break(&self) -> Option<AxisBreak> {
+        self.axis_break
+    }
+
+    #[inline]
+    /// Fold a value by the break of the axis, if any.
+    pub(crate) fn fold(&self, value: f32) -> f32 {
+        self.axis_break
+            .map_or(value, |axis_break| axis_break.fold(value))
+    }
+}
+
+impl Ranged for BrokenAxis {
+    type FormatOption = DefaultFormatting;
+    type ValueType = f32;
+
+    fn map(&self, value: &f32, limit: (i32, i32)) -> i32 {
+        self.folded.map(&self.fold(*value), limit)
+    }
+
+    fn key_points<Hint: KeyPointHint>(&self, hint: Hint) -> Vec<f32> {
+        let Some(axis_break) = self.axis_break else {
+            return self.folded.key_points(hint);
+        };
+
+        // Share the key points between the values below and above the break by their spans
+        let below = axis_break.start - self.range.start;
+        let above = self.range.end - axis_break.end;
+
+        let count = hint.max_num_points();
+        let below_count = (count as f32 * below / (below + above)).round() as usize;
+
+        let segments = [
+            (self.range.start..axis_break.start, below_count),
+            (axis_break.end..self.range.end, count - below_count),
+        ];
+
+        segments
+            .into_iter()
+            .filter(|(range, count)| range.end > range.start && *count > 0)
+            .flat_map(|(range, count)| RangedCoordf32::from(range).key_points(count))
+            .collect()
+    }
+
+    fn range(&self) -> Range<f32> {
+        self.range.clone()
+    }
+}
+
+/// Draw a break across the plot, between the given rows of pixels of the drawing area, as a gap
+/// of the background color edged with zigzags.
+pub(crate) fn draw_break<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    columns: Range<i32>,
+    rows: Range<i32>,
+    style: ShapeStyle,
+    background_color: RGBAColor,
+) {
+    let (base_x, base_y) = area.get_base_pixel();
+
+    // Pixels are given in the backend's coordinates, while the area draws relative to its corner
+    let left = columns.start - base_x - BREAK_OVERHANG;
+    let right = columns.end - base_x + BREAK_OVERHANG;
+    let (top, bottom) = (rows.start - base_y, rows.end - base_y);
+
+    let gap = Rectangle::new([(left, top), (right, bottom)], background_color.filled());
+
+    area.draw(&gap).unwrap();
+
+    let style = ShapeStyle {
+        stroke_width: 1,
+        ..style
+    };
+
+    for edge in [top, bottom] {
+        let zigzag = (left..=right).step_by(BREAK_WAVE as usize).enumerate();
+        let points = zigzag.map(|(index, x)| match index % 2 {
+            0 => (x, edge - BREAK_AMPLITUDE),
+            _ => (x, edge + BREAK_AMPLITUDE),
+        });
+
+        area.draw(&PathElement::new(points.collect::<Vec<_>>(), style))
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folding_round_trips_on_both_sides_of_the_break() {
+        let axis_break = AxisBreak::new(&(0.0..100.0), &(20.0..80.0)).unwrap();
+
+        // The 40 values kept are folded around a gap of 3% of the plot
+        assert!((axis_break.fold(100.0) - 40.0 / (1.0 - BREAK_GAP)).abs() < 1e-4);
+        assert_eq!(axis_break.fold(10.0), 10.0);
+
+        for value in [0.0, 10.0, 20.0, 35.0, 50.0, 79.0, 80.0, 90.0, 100.0] {
+            let folded = axis_break.fold(value);
+
+            assert!((axis_break.unfold(folded) - value).abs() < 1e-3, "{value}");
+        }
+    }
+
+    #[test]
+    fn breaks_are_clamped_to_the_range() {
+        let axis_break = AxisBreak::new(&(0.0..100.0), &(50.0..200.0)).unwrap();
+
+        assert_eq!((axis_break.start(), axis_break.end()), (50.0, 100.0));
+
+        // Breaks outside the range, or skipping all of it, aren't shown
+        assert_eq!(AxisBreak::new(&(0.0..100.0), &(150.0..200.0)), None);
+        assert_eq!(AxisBreak::new(&(0.0..100.0), &(-10.0..110.0)), None);
+        assert_eq!(AxisBreak::new(&(0.0..100.0), &(50.0..50.0)), None);
+    }
+
+    #[test]
+    fn key_points_are_split_across_the_break() {
+        let axis = BrokenAxis::new(0.0..1000.0, Some(&(10.0..990.0)));
+        let key_points = axis.key_points(10);
+
+        assert!(key_points.iter().any(|point| *point <= 10.0));
+        assert!(key_points.iter().any(|point| *point >= 990.0));
+        assert!(key_points
+            .iter()
+            .all(|point| *point <= 10.0 || *point >= 990.0));
+
+        // Without a break the key points spread over the whole range
+        let axis = BrokenAxis::new(0.0..1000.0, None);
+
+        assert!(axis.get_break().is_none());
+        assert!(axis
+            .key_points(10)
+            .iter()
+            .any(|point| *point > 10.0 && *point < 990.0));
+    }
+}
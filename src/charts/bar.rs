@@ -0,0 +1,999 @@
+//! Bar chart for categorical data. Can show multiple series grouped or stacked.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use egui::{CursorIcon, DragAndDrop, Id, Rect, Sense, Stroke, Ui};
+use plotters::{
+    coord::ReverseCoordTranslate,
+    element::Rectangle,
+    prelude::{ChartBuilder, LabelAreaPosition, SeriesLabelPosition},
+    style::{
+        full_palette::GREY_700, Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK, WHITE,
+    },
+};
+use plotters_backend::{DrawingBackend, DrawingErrorKind, FontFamily, FontStyle};
+
+use crate::{
+    charts::{
+        grid::{draw_grid, GridConfig},
+        hover::{draw_tooltip, hover_pos, untransform},
+        textfit::{legend_entries, TextFit},
+    },
+    compat::show_tooltip_text,
+    locale::locale,
+    palette::{default_palette, Palette},
+    Chart, ScreenTransform,
+};
+
+const DEFAULT_MARGIN: i32 = 25;
+const DEFAULT_LABEL_AREA: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+/// Width of every category's bars, relative to the space between categories.
+const BAR_WIDTH: f32 = 0.8;
+/// Headroom above and below the tallest bars, relative to the value range.
+const VALUE_PADDING: f32 = 0.05;
+const HOVER_OPACITY: f64 = 0.15;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+/// How the bars of multiple series are laid out within a category.
+pub enum BarLayout {
+    /// Bars of each series are placed side by side.
+    #[default]
+    Grouped,
+    /// Bars of each series are stacked on top of each other. Negative values are stacked
+    /// downwards from zero.
+    Stacked,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Change made to the series of a bar chart by dragging a series from one chart to another.
+pub enum SeriesEvent {
+    /// A series with the given name was dropped onto the chart and added to it.
+    Added(Arc<str>),
+    /// A series with the given name was dropped onto another chart and removed from this one.
+    Removed(Arc<str>),
+}
+
+/// Drag-and-drop payload of a series dragged out of a bar chart's legend.
+struct SeriesDrag {
+    /// Id of the chart the series was dragged out of.
+    source: Id,
+    series: BarSeries,
+    /// Set by the chart the series is dropped onto, so the chart it was dragged from removes it.
+    dropped: Arc<AtomicBool>,
+}
+
+/// Series being dragged out of a bar chart, as the chart itself keeps track of it.
+struct Dragging {
+    /// Index of the series in the chart.
+    index: usize,
+    name: Arc<str>,
+    /// Set by the chart the series is dropped onto.
+    dropped: Arc<AtomicBool>,
+}
+
+#[derive(Clone)]
+struct BarSeries {
+    /// Name of the series, shown in the legend and hover breakdown.
+    name: Arc<str>,
+    /// Value of the series in each category.
+    values: Arc<[f32]>,
+    /// Color of the series' bars. Taken from the palette if None.
+    color: Option<RGBAColor>,
+}
+
+#[derive(Clone)]
+struct BarConfig {
+    /// Names of the categories along the X axis.
+    categories: Arc<[Arc<str>]>,
+    /// Every series plotted.
+    series: Arc<[BarSeries]>,
+    /// Layout of the series within a category.
+    layout: BarLayout,
+    /// Grid lines of the value axis.
+    y_grid: GridConfig,
+    /// Style of the axes.
+    axes_style: ShapeStyle,
+    /// Style of the text
+    text_color: RGBAColor,
+    /// Unit of the value axis.
+    y_unit: Arc<str>,
+    /// Caption of the chart. Hidden if None.
+    caption: Option<Arc<str>>,
+    /// Captions, axis descriptions, and legend entries shortened to fit the chart.
+    text_fit: TextFit,
+    /// Palette series without a color are colored from. Uses the default palette if None.
+    palette: Option<Palette>,
+    /// Show a legend with an entry for every series.
+    legend: bool,
+    /// Show a breakdown of the values of the hovered category.
+    hover: bool,
+    /// Position of the pointer relative to the chart, if it is hovering over it.
+    pointer: Option<(f32, f32)>,
+    /// Rects of the legend's entries drawn the last frame, in pixels of the drawing area.
+    legend_entries: RefCell<Vec<Rect>>,
+}
+
+impl BarConfig {
+    /// Remove the series at an index, if it is still the series with the given name rather than
+    /// another one since moved into its place.
+    fn remove_series(&mut self, index: usize, name: &Arc<str>) {
+        if self
+            .series
+            .get(index)
+            .is_some_and(|series| Arc::ptr_eq(&series.name, name))
+        {
+            let mut series = self.series.to_vec();
+            series.remove(index);
+
+            self.series = series.into();
+        }
+    }
+
+    /// Value of a series in a category. Categories a series has no value for are zero.
+    fn value(series: &BarSeries, category: usize) -> f32 {
+        series.values.get(category).copied().unwrap_or(0.0)
+    }
+
+    /// Range of the value axis, always including zero.
+    fn value_range(&self) -> (f32, f32) {
+        let mut min: f32 = 0.0;
+        let mut max: f32 = 0.0;
+
+        for category in 0..self.categories.len() {
+            let values = self
+                .series
+                .iter()
+                .map(|series| Self::value(series, category));
+
+            match self.layout {
+                BarLayout::Grouped => {
+                    for value in values {
+                        min = min.min(value);
+                        max = max.max(value);
+                    }
+                }
+                BarLayout::Stacked => {
+                    let (negative, positive) =
+                        values.fold((0.0, 0.0), |(neg, pos), value| match value < 0.0 {
+                            true => (neg + value, pos),
+                            false => (neg, pos + value),
+                        });
+
+                    min = min.min(negative);
+                    max = max.max(positive);
+                }
+            }
+        }
+
+        // Leave some headroom, and make sure empty charts can still be drawn
+        let padding = match max > min {
+            true => (max - min) * VALUE_PADDING,
+            false => 1.0,
+        };
+
+        (
+            match min < 0.0 {
+                true => min - padding,
+                false => min,
+            },
+            max + padding,
+        )
+    }
+
+    /// Corners of every bar of a series, by category.
+    fn bars(&self, series_index: usize) -> Vec<(usize, [(f32, f32); 2])> {
+        let series_count = self.series.len();
+        let series = &self.series[series_index];
+
+        (0..self.categories.len())
+            .map(|category| {
+                let value = Self::value(series, category);
+                let center = category as f32;
+
+                let corners = match self.layout {
+                    BarLayout::Grouped => {
+                        let width = BAR_WIDTH / series_count as f32;
+                        let left = center - BAR_WIDTH / 2.0 + width * series_index as f32;
+
+                        [(left, 0.0), (left + width, value)]
+                    }
+                    BarLayout::Stacked => {
+                        // Stack on top of the earlier series with the same sign
+                        let base: f32 = self.series[..series_index]
+                            .iter()
+                            .map(|series| Self::value(series, category))
+                            .filter(|other| (*other < 0.0) == (value < 0.0))
+                            .sum();
+                        let left = center - BAR_WIDTH / 2.0;
+
+                        [(left, base), (left + BAR_WIDTH, base + value)]
+                    }
+                };
+
+                (category, corners)
+            })
+            .collect()
+    }
+}
+
+/// Bar chart with one or more series.
+///
+/// ## Usage
+/// Creating the chart only requires the names of the categories along the X axis and a caption.
+///
+///  * `categories`: Names of each category, in the order they are shown.
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// Series are then added with `.add_series()`, each with a value for every category. Multiple
+/// series can either be grouped side by side or stacked on top of each other, see [`BarLayout`].
+/// Every series gets a legend entry, and hovering over a category shows a breakdown of its
+/// values.
+///
+/// With `.series_drag(true)`, series can be dragged by their legend entry and dropped onto
+/// another bar chart also accepting them, moving the series between the two. Charts record the
+/// series added to or removed from them this way as [`SeriesEvent`]s, returned by
+/// `.take_series_events()`.
+pub struct BarChart {
+    /// Drag series out of the legend and accept series dropped onto the chart.
+    series_drag: bool,
+    /// Series being dragged out of the chart, and whether it has been dropped.
+    dragging: Option<Dragging>,
+    series_events: Vec<SeriesEvent>,
+    chart: Chart<BarConfig>,
+}
+
+impl BarChart {
+    /// Create a new BarChart. See [Usage](#usage).
+    pub fn new(categories: &[&str], caption: &str) -> Self {
+        let categories: Arc<[Arc<str>]> = categories
+            .iter()
+            .map(|category| Arc::from(*category))
+            .collect();
+
+        let config = BarConfig {
+            categories,
+            series: Arc::new([]),
+            layout: BarLayout::default(),
+            y_grid: GridConfig::default(),
+            axes_style: GREY_700.into(),
+            text_color: BLACK.to_rgba(),
+            y_unit: "".into(),
+            caption: Some(caption.into()),
+            text_fit: TextFit::default(),
+            palette: None,
+            legend: true,
+            hover: true,
+            pointer: None,
+            legend_entries: RefCell::new(Vec::new()),
+        };
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, transform, data| {
+            let category_count = data.categories.len();
+
+            data.legend_entries.borrow_mut().clear();
+            data.text_fit.clear();
+
+            // Nothing to show without categories
+            if category_count == 0 {
+                return;
+            }
+
+            let x_range = -0.5..(category_count as f32 - 0.5);
+            let (y_min, y_max) = data.value_range();
+
+            let font_desc = FontDesc::new(
+                FontFamily::Monospace,
+                CAPTION_SIZE as f64,
+                FontStyle::Normal,
+            );
+
+            let text_style = TextStyle::from(font_desc).color(&data.text_color);
+
+            let mut builder = ChartBuilder::on(area);
+
+            builder
+                .margin(DEFAULT_MARGIN)
+                .x_label_area_size(DEFAULT_LABEL_AREA)
+                .y_label_area_size(DEFAULT_LABEL_AREA);
+
+            if let Some(caption) = &data.caption {
+                let margins = (DEFAULT_MARGIN, DEFAULT_MARGIN);
+                let caption = data.text_fit.caption(area, margins, caption, &text_style);
+
+                builder.caption(caption, text_style.clone());
+            }
+
+            let mut chart = match builder.build_cartesian_2d(x_range, y_min..y_max) {
+                Ok(chart) => chart,
+                // Area too small to be drawn
+                Err(_) => return,
+            };
+
+            draw_grid(&mut chart, &GridConfig::disabled(), &data.y_grid);
+
+            let plot = chart.plotting_area();
+            let y_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Left,
+                DEFAULT_LABEL_AREA,
+                &data.y_unit,
+                &text_style,
+            );
+
+            // Only label the category centers
+            let category_label = |x: &f32| {
+                let index = x.round();
+
+                match (x - index).abs() < f32::EPSILON && index >= 0.0 {
+                    true => data
+                        .categories
+                        .get(index as usize)
+                        .map_or_else(String::new, |category| category.to_string()),
+                    false => String::new(),
+                }
+            };
+            let locale = locale();
+            let value_label = |y: &f32| locale.format_tick(*y as f64);
+
+            chart
+                .configure_mesh()
+                .disable_mesh()
+                .x_labels(category_count)
+                .x_label_formatter(&category_label)
+                .y_labels(data.y_grid.get_major_lines())
+                .y_label_formatter(&value_label)
+                .label_style(text_style.clone())
+                .axis_style(data.axes_style)
+                .y_desc(y_desc)
+                .draw()
+                .unwrap();
+
+            // Find the category under the pointer, undoing the chart's transform
+            let hovered = data
+                .pointer
+                .filter(|_| data.hover)
+                .and_then(|pos| {
+                    chart
+                        .as_coord_spec()
+                        .reverse_translate(untransform(area, transform, pos))
+                })
+                .map(|(x, _)| x.round())
+                .filter(|x| *x >= 0.0 && (*x as usize) < category_count)
+                .map(|x| x as usize);
+
+            if let Some(category) = hovered {
+                let center = category as f32;
+                let highlight = data.text_color.mix(HOVER_OPACITY).filled();
+
+                chart
+                    .draw_series([Rectangle::new(
+                        [(center - 0.5, y_min), (center + 0.5, y_max)],
+                        highlight,
+                    )])
+                    .unwrap();
+            }
+
+            let palette = data.palette.clone().unwrap_or_else(default_palette);
+
+            let names: Vec<&str> = data.series.iter().map(|series| &*series.name).collect();
+            let labels = data
+                .text_fit
+                .legend(chart.plotting_area(), &names, &text_style);
+
+            for (index, series) in data.series.iter().enumerate() {
+                let color = series.color.unwrap_or_else(|| palette.color(index));
+                let bars = data
+                    .bars(index)
+                    .into_iter()
+                    .map(move |(_, corners)| Rectangle::new(corners, color.filled()));
+
+                let series_anno = chart.draw_series(bars).unwrap();
+
+                if data.legend {
+                    series_anno
+                        .label(labels[index].to_string())
+                        .legend(move |(x, y)| {
+                            Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled())
+                        });
+                }
+            }
+
+            if data.legend && !data.series.is_empty() {
+                chart
+                    .configure_series_labels()
+                    .position(SeriesLabelPosition::UpperRight)
+                    .label_font(text_style.clone())
+                    .background_style(WHITE.mix(0.8))
+                    .border_style(data.axes_style)
+                    .draw()
+                    .unwrap();
+
+                *data.legend_entries.borrow_mut() =
+                    legend_entries(chart.plotting_area(), &labels, &text_style);
+            }
+
+            let Some(category) = hovered else {
+                return;
+            };
+
+            // Breakdown of the hovered category, next to the top of its bars
+            let mut lines = vec![data.categories[category].to_string()];
+
+            lines.extend(data.series.iter().map(|series| {
+                let value = BarConfig::value(series, category);
+
+                format!("{}: {}", series.name, locale.format_number(value))
+            }));
+
+            if data.layout == BarLayout::Stacked {
+                let total: f32 = data
+                    .series
+                    .iter()
+                    .map(|series| BarConfig::value(series, category))
+                    .sum();
+
+                lines.push(format!("Total: {}", locale.format_number(total)));
+            }
+
+            let pos = chart.backend_coord(&(category as f32 + BAR_WIDTH / 2.0, y_max));
+
+            draw_tooltip(area, pos, lines, &text_style);
+        }));
+
+        Self {
+            series_drag: false,
+            dragging: None,
+            series_events: Vec::new(),
+            chart,
+        }
+    }
+
+    #[inline]
+    /// Add a series with a value for every category. Missing values are treated as zero.
+    pub fn add_series<T>(&mut self, name: &str, values: &[f32], color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.push_series(name, values, Some(color.into()))
+    }
+
+    #[inline]
+    /// Add a series with a value for every category. Consumes self.
+    pub fn series<T>(mut self, name: &str, values: &[f32], color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.add_series(name, values, color);
+
+        self
+    }
+
+    #[inline]
+    /// Add a series colored by its position in the chart's palette. Missing values are treated
+    /// as zero.
+    pub fn add_palette_series(&mut self, name: &str, values: &[f32]) {
+        self.push_series(name, values, None)
+    }
+
+    #[inline]
+    /// Add a series colored by its position in the chart's palette. Consumes self.
+    pub fn palette_series(mut self, name: &str, values: &[f32]) -> Self {
+        self.add_palette_series(name, values);
+
+        self
+    }
+
+    fn push_series(&mut self, name: &str, values: &[f32], color: Option<RGBAColor>) {
+        let config = self.chart.get_data_mut();
+
+        let mut series = config.series.to_vec();
+        series.push(BarSeries {
+            name: name.into(),
+            values: values.into(),
+            color,
+        });
+
+        config.series = series.into();
+    }
+
+    #[inline]
+    /// Remove every series from the chart.
+    pub fn clear_series(&mut self) {
+        self.chart.get_data_mut().series = Arc::new([]);
+    }
+
+    #[inline]
+    /// Set the palette series without a color are colored from. Set to None to use the default
+    /// palette.
+    pub fn set_palette(&mut self, palette: Option<Palette>) {
+        self.chart.get_data_mut().palette = palette
+    }
+
+    #[inline]
+    /// Set the palette series without a color are colored from. Consumes self.
+    pub fn palette(mut self, palette: Option<Palette>) -> Self {
+        self.set_palette(palette);
+
+        self
+    }
+
+    #[inline]
+    /// Set how the bars of multiple series are laid out.
+    pub fn set_layout(&mut self, layout: BarLayout) {
+        self.chart.get_data_mut().layout = layout
+    }
+
+    #[inline]
+    /// Set how the bars of multiple series are laid out. Consumes self.
+    pub fn layout(mut self, layout: BarLayout) -> Self {
+        self.set_layout(layout);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide the legend.
+    pub fn set_legend(&mut self, legend: bool) {
+        self.chart.get_data_mut().legend = legend
+    }
+
+    #[inline]
+    /// Show/hide the legend. Consumes self.
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.set_legend(legend);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable dragging series out of the chart by their legend entry, and dropping series
+    /// dragged from other bar charts onto it.
+    pub fn set_series_drag(&mut self, series_drag: bool) {
+        self.series_drag = series_drag
+    }
+
+    #[inline]
+    /// Enable/disable dragging series between bar charts. Consumes self.
+    pub fn series_drag(mut self, series_drag: bool) -> Self {
+        self.set_series_drag(series_drag);
+
+        self
+    }
+
+    #[inline]
+    /// Take the series added to and removed from the chart by dragging them between charts since
+    /// this was last called.
+    pub fn take_series_events(&mut self) -> Vec<SeriesEvent> {
+        std::mem::take(&mut self.series_events)
+    }
+
+    #[inline]
+    /// Enable/disable the breakdown of values shown when hovering over a category.
+    pub fn set_hover(&mut self, hover: bool) {
+        self.chart.get_data_mut().hover = hover
+    }
+
+    #[inline]
+    /// Enable/disable the breakdown of values shown when hovering over a category. Consumes
+    /// self.
+    pub fn hover(mut self, hover: bool) -> Self {
+        self.set_hover(hover);
+
+        self
+    }
+
+    #[inline]
+    /// Set the grid lines of the value axis.
+    pub fn set_y_grid(&mut self, y_grid: GridConfig) {
+        self.chart.get_data_mut().y_grid = y_grid
+    }
+
+    #[inline]
+    /// Set the grid lines of the value axis. Consumes self.
+    pub fn y_grid(mut self, y_grid: GridConfig) -> Self {
+        self.set_y_grid(y_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the axes.
+    pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
+        self.chart.get_data_mut().axes_style = axes_style
+    }
+
+    #[inline]
+    /// Set the style of the axes. Consumes self.
+    pub fn axes_style(mut self, axes_style: ShapeStyle) -> Self {
+        self.set_axes_style(axes_style);
+
+        self
+    }
+
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the unit of the value axis.
+    pub fn set_y_unit(&mut self, y_unit: &str) {
+        self.chart.get_data_mut().y_unit = y_unit.into()
+    }
+
+    #[inline]
+    /// Set the unit of the value axis. Consumes self.
+    pub fn y_unit(mut self, y_unit: &str) -> Self {
+        self.set_y_unit(y_unit);
+
+        self
+    }
+
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening captions, axis descriptions, and legend entries too long for
+    /// the chart with an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.get_data_mut().text_fit.set_enabled(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_data().text_fit.is_enabled()
+    }
+
+    /// Draw the chart to a Ui.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        self.chart.get_data_mut().pointer = hover_pos(ui);
+
+        self.chart.draw(ui);
+
+        let transform = self.chart.get_transform();
+
+        self.chart.get_data().text_fit.show_full_text(ui, transform);
+
+        if self.series_drag {
+            self.drag_series(ui);
+        }
+    }
+
+    /// Drag series out of the legend, and take series dropped onto the chart.
+    fn drag_series(&mut self, ui: &Ui) {
+        let ctx = ui.ctx();
+        let rect = ui.max_rect();
+
+        // Remove the series being dragged out once another chart takes it. Charts drawn after
+        // this one only get the chance to once this frame is over.
+        if let Some(dragging) = &self.dragging {
+            if dragging.dropped.load(Ordering::Relaxed) {
+                let name = dragging.name.clone();
+
+                self.chart
+                    .get_data_mut()
+                    .remove_series(dragging.index, &name);
+                self.series_events.push(SeriesEvent::Removed(name));
+                self.dragging = None;
+            } else if DragAndDrop::has_payload_of_type::<SeriesDrag>(ctx) {
+                show_tooltip_text(ui, "egui_plotter_series_drag", dragging.name.to_string());
+
+                ctx.request_repaint();
+            } else {
+                self.dragging = None;
+            }
+        }
+
+        let id = self.chart.get_id();
+        let response = ui.interact(rect, id.with("series_drop"), Sense::hover());
+
+        if let Some(drag) = response.dnd_hover_payload::<SeriesDrag>() {
+            if drag.source != id {
+                let color = ui.visuals().selection.stroke.color;
+
+                ui.painter().rect_stroke(rect, 0.0, Stroke::new(2.0, color));
+            }
+        }
+
+        if let Some(drag) = response.dnd_release_payload::<SeriesDrag>() {
+            if drag.source != id {
+                let config = self.chart.get_data_mut();
+
+                let mut series = config.series.to_vec();
+                series.push(drag.series.clone());
+
+                config.series = series.into();
+
+                drag.dropped.store(true, Ordering::Relaxed);
+                self.series_events
+                    .push(SeriesEvent::Added(drag.series.name.clone()));
+            }
+        }
+
+        let transform = ScreenTransform::new(rect, self.chart.get_transform());
+        let config = self.chart.get_data();
+        let entries = config.legend_entries.borrow();
+
+        for (index, (entry, series)) in entries.iter().zip(config.series.iter()).enumerate() {
+            let response = ui.interact(
+                transform.rect_to_screen(*entry),
+                id.with(("legend_entry", index)),
+                Sense::drag(),
+            );
+
+            if response.hovered() {
+                ctx.set_cursor_icon(CursorIcon::Grab);
+            }
+
+            if response.drag_started() {
+                // Keep the color the series had, rather than taking one from the other chart's
+                // palette
+                let palette = config.palette.clone().unwrap_or_else(default_palette);
+                let dropped = Arc::new(AtomicBool::new(false));
+
+                DragAndDrop::set_payload(
+                    ctx,
+                    SeriesDrag {
+                        source: id,
+                        series: BarSeries {
+                            color: Some(series.color.unwrap_or_else(|| palette.color(index))),
+                            ..series.clone()
+                        },
+                        dropped: dropped.clone(),
+                    },
+                );
+
+                self.dragging = Some(Dragging {
+                    index,
+                    name: series.name.clone(),
+                    dropped,
+                });
+            }
+        }
+    }
+
+    /// Draw the chart to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui. No bar is highlighted, as there is no pointer.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.chart.get_data_mut().pointer = None;
+
+        self.chart.draw_headless(backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{CentralPanel, Context, Event, Modifiers, PointerButton, Pos2, RawInput, Vec2};
+
+    use super::*;
+
+    fn names(chart: &BarChart) -> Vec<String> {
+        let config = chart.chart.get_data();
+
+        config
+            .series
+            .iter()
+            .map(|series| series.name.to_string())
+            .collect()
+    }
+
+    fn assert_close(actual: (f32, f32), expected: (f32, f32)) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+
+        assert!(
+            close(actual.0, expected.0) && close(actual.1, expected.1),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn assert_bars(actual: Vec<(usize, [(f32, f32); 2])>, expected: &[[(f32, f32); 2]]) {
+        assert_eq!(actual.len(), expected.len());
+
+        for ((category, [start, end]), (index, [expected_start, expected_end])) in
+            actual.into_iter().zip(expected.iter().enumerate())
+        {
+            assert_eq!(category, index);
+            assert_close(start, *expected_start);
+            assert_close(end, *expected_end);
+        }
+    }
+
+    #[test]
+    fn value_range_includes_zero_and_leaves_headroom() {
+        let empty = BarChart::new(&["a", "b"], "");
+        assert_close(empty.chart.get_data().value_range(), (0.0, 1.0));
+
+        let positive = BarChart::new(&["a", "b"], "").palette_series("A", &[1.0, 3.0]);
+        assert_close(positive.chart.get_data().value_range(), (0.0, 3.15));
+
+        let grouped = BarChart::new(&["a", "b"], "")
+            .palette_series("A", &[2.0, -4.0])
+            .palette_series("B", &[10.0, 1.0]);
+        assert_close(grouped.chart.get_data().value_range(), (-4.7, 10.7));
+    }
+
+    #[test]
+    fn stacked_value_range_sums_each_sign_apart() {
+        let chart = BarChart::new(&["a", "b"], "")
+            .palette_series("A", &[3.0, -2.0])
+            .palette_series("B", &[-1.0, 5.0])
+            .palette_series("C", &[4.0, -3.0])
+            .layout(BarLayout::Stacked);
+
+        // Up to 7 above zero in the first category and 5 below it in the second
+        assert_close(chart.chart.get_data().value_range(), (-5.6, 7.6));
+    }
+
+    #[test]
+    fn uneven_series_are_zero_where_they_have_no_value() {
+        let chart = BarChart::new(&["a", "b", "c"], "")
+            .palette_series("A", &[1.0])
+            .palette_series("B", &[1.0, 2.0, 3.0, 100.0]);
+        let config = chart.chart.get_data();
+
+        // Values past the last category are ignored
+        assert_close(config.value_range(), (0.0, 3.15));
+
+        assert_bars(
+            config.bars(0),
+            &[
+                [(-0.4, 0.0), (0.0, 1.0)],
+                [(0.6, 0.0), (1.0, 0.0)],
+                [(1.6, 0.0), (2.0, 0.0)],
+            ],
+        );
+    }
+
+    #[test]
+    fn grouped_bars_share_their_category() {
+        let chart = BarChart::new(&["a", "b"], "")
+            .palette_series("A", &[2.0, -4.0])
+            .palette_series("B", &[10.0, 1.0]);
+        let config = chart.chart.get_data();
+
+        assert_bars(
+            config.bars(0),
+            &[[(-0.4, 0.0), (0.0, 2.0)], [(0.6, 0.0), (1.0, -4.0)]],
+        );
+        assert_bars(
+            config.bars(1),
+            &[[(0.0, 0.0), (0.4, 10.0)], [(1.0, 0.0), (1.4, 1.0)]],
+        );
+    }
+
+    #[test]
+    fn stacked_bars_stack_on_earlier_bars_of_the_same_sign() {
+        let chart = BarChart::new(&["a", "b"], "")
+            .palette_series("A", &[3.0, -2.0])
+            .palette_series("B", &[-1.0, 5.0])
+            .palette_series("C", &[4.0, -3.0])
+            .layout(BarLayout::Stacked);
+        let config = chart.chart.get_data();
+
+        assert_bars(
+            config.bars(1),
+            &[[(-0.4, 0.0), (0.4, -1.0)], [(0.6, 0.0), (1.4, 5.0)]],
+        );
+        assert_bars(
+            config.bars(2),
+            &[[(-0.4, 3.0), (0.4, 7.0)], [(0.6, -2.0), (1.4, -5.0)]],
+        );
+    }
+
+    #[test]
+    fn series_are_dragged_between_charts_sharing_a_ui() {
+        // Both charts have a series named the same, so only the one dragged can be removed
+        let mut charts = [
+            BarChart::new(&["a", "b"], "Left")
+                .palette_series("Same", &[1.0, 2.0])
+                .palette_series("Same", &[3.0, 4.0])
+                .series_drag(true),
+            BarChart::new(&["a", "b"], "Right")
+                .palette_series("Other", &[1.0, 2.0])
+                .series_drag(true),
+        ];
+
+        let ctx = Context::default();
+        let frame = |events: Vec<Event>, charts: &mut [BarChart; 2]| {
+            let mut rects = [Rect::NOTHING; 2];
+            let input = RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(800.0, 300.0))),
+                events,
+                ..Default::default()
+            };
+
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    ui.columns(2, |columns| {
+                        for (index, ui) in columns.iter_mut().enumerate() {
+                            rects[index] = ui.max_rect();
+                            charts[index].draw(ui);
+                        }
+                    })
+                });
+            });
+
+            rects
+        };
+
+        let rects = frame(vec![], &mut charts);
+
+        // Grab the second legend entry of the left chart
+        let entry = {
+            let config = charts[0].chart.get_data();
+            let entry = config.legend_entries.borrow()[1];
+
+            ScreenTransform::new(rects[0], charts[0].chart.get_transform())
+                .rect_to_screen(entry)
+                .center()
+        };
+        let target = rects[1].center();
+
+        let button = |pos, pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        };
+
+        frame(vec![Event::PointerMoved(entry)], &mut charts);
+        frame(vec![button(entry, true)], &mut charts);
+        frame(
+            vec![Event::PointerMoved(entry + Vec2::new(20.0, 0.0))],
+            &mut charts,
+        );
+        frame(vec![Event::PointerMoved(target)], &mut charts);
+        frame(vec![button(target, false)], &mut charts);
+        frame(vec![], &mut charts);
+
+        assert_eq!(names(&charts[0]), ["Same"]);
+        assert_eq!(names(&charts[1]), ["Other", "Same"]);
+
+        // The series left behind is the one that wasn't dragged
+        assert_eq!(&*charts[0].chart.get_data().series[0].values, [1.0, 2.0]);
+        assert_eq!(&*charts[1].chart.get_data().series[1].values, [3.0, 4.0]);
+    }
+}
@@ -0,0 +1,218 @@
+//! Badges at the edges of a plot counting the points clipped out of view, so zoomed or fitted
+//! views don't hide data silently.
+
+use std::ops::Range;
+
+use egui::{Color32, CursorIcon, FontId, Id, Pos2, Rect, Sense, Shape, Stroke, Ui, Vec2};
+
+use crate::locale::locale;
+
+/// Space between the badges and the edges of the plot, in pixels.
+const BADGE_MARGIN: f32 = 4.0;
+const BADGE_PADDING: f32 = 3.0;
+const BADGE_FONT_SIZE: f32 = 10.0;
+/// Length of the sides of the arrows, in pixels.
+const ARROW_SIZE: f32 = 8.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Edge of the plot points were clipped past.
+pub(crate) enum ClipEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl ClipEdge {
+    const ALL: [Self; 4] = [Self::Top, Self::Bottom, Self::Left, Self::Right];
+
+    /// Unit vector pointing out of the plot through the edge.
+    fn outward(self) -> Vec2 {
+        match self {
+            Self::Top => Vec2::new(0.0, -1.0),
+            Self::Bottom => Vec2::new(0.0, 1.0),
+            Self::Left => Vec2::new(-1.0, 0.0),
+            Self::Right => Vec2::new(1.0, 0.0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+/// Number of points clipped past each edge of the plot. Points outside of the view along both
+/// axes aren't counted, as they aren't past any one edge.
+pub(crate) struct ClipCounts {
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+}
+
+impl ClipCounts {
+    /// Count the points past each edge of the X and Y ranges in view.
+    pub(crate) fn new(points: &[(f32, f32)], view: &(Range<f32>, Range<f32>)) -> Self {
+        let (x_view, y_view) = view;
+        let mut counts = Self::default();
+
+        for &(x, y) in points {
+            let in_x = x >= x_view.start && x <= x_view.end;
+            let in_y = y >= y_view.start && y <= y_view.end;
+
+            match (in_x, in_y) {
+                (true, false) if y > y_view.end => counts.top += 1,
+                (true, false) if y < y_view.start => counts.bottom += 1,
+                (false, true) if x < x_view.start => counts.left += 1,
+                (false, true) if x > x_view.end => counts.right += 1,
+                _ => {}
+            }
+        }
+
+        counts
+    }
+
+    #[inline]
+    fn get(&self, edge: ClipEdge) -> usize {
+        match edge {
+            ClipEdge::Top => self.top,
+            ClipEdge::Bottom => self.bottom,
+            ClipEdge::Left => self.left,
+            ClipEdge::Right => self.right,
+        }
+    }
+}
+
+/// Draw a badge with an arrow and a count in the middle of every edge of the plot with points
+/// clipped past it. `plot` is the rect of the plot on screen and `id` the id of the chart the
+/// badges belong to. Returns the edge whose badge was clicked, if any.
+pub(crate) fn draw_clip_indicators(
+    ui: &Ui,
+    id: Id,
+    plot: Rect,
+    counts: &ClipCounts,
+    color: Color32,
+) -> Option<ClipEdge> {
+    let painter = ui.painter();
+    let locale = locale();
+    let mut clicked = None;
+
+    for edge in ClipEdge::ALL {
+        let count = counts.get(edge);
+
+        if count == 0 {
+            continue;
+        }
+
+        let galley = painter.layout_no_wrap(
+            locale.format_number(count),
+            FontId::monospace(BADGE_FONT_SIZE),
+            color,
+        );
+
+        let text_size = galley.size();
+        let size = Vec2::new(
+            ARROW_SIZE + BADGE_PADDING * 3.0 + text_size.x,
+            ARROW_SIZE.max(text_size.y) + BADGE_PADDING * 2.0,
+        );
+
+        // Tuck the badge inside the edge it points out of
+        let center = match edge {
+            ClipEdge::Top => Pos2::new(plot.center().x, plot.top() + BADGE_MARGIN + size.y / 2.0),
+            ClipEdge::Bottom => {
+                Pos2::new(plot.center().x, plot.bottom() - BADGE_MARGIN - size.y / 2.0)
+            }
+            ClipEdge::Left => Pos2::new(plot.left() + BADGE_MARGIN + size.x / 2.0, plot.center().y),
+            ClipEdge::Right => {
+                Pos2::new(plot.right() - BADGE_MARGIN - size.x / 2.0, plot.center().y)
+            }
+        };
+
+        let badge = Rect::from_center_size(center, size);
+
+        let response = ui.interact(
+            badge,
+            id.with(("clip_indicator", edge as u8)),
+            Sense::click(),
+        );
+
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+        }
+
+        if response.clicked() {
+            clicked = Some(edge);
+        }
+
+        painter.rect(badge, 2.0, Color32::WHITE, Stroke::new(1.0, color));
+
+        // Arrow pointing out of the plot, followed by the count
+        let arrow_center = Pos2::new(
+            badge.left() + BADGE_PADDING + ARROW_SIZE / 2.0,
+            badge.center().y,
+        );
+        let outward = edge.outward() * (ARROW_SIZE / 2.0);
+        let across = outward.rot90();
+
+        painter.add(Shape::convex_polygon(
+            vec![
+                arrow_center + outward,
+                arrow_center - outward + across,
+                arrow_center - outward - across,
+            ],
+            color,
+            Stroke::NONE,
+        ));
+
+        let text_pos = Pos2::new(
+            arrow_center.x + ARROW_SIZE / 2.0 + BADGE_PADDING,
+            badge.center().y - text_size.y / 2.0,
+        );
+
+        painter.galley(text_pos, galley, color);
+    }
+
+    clicked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(counts: ClipCounts) -> [usize; 4] {
+        ClipEdge::ALL.map(|edge| counts.get(edge))
+    }
+
+    #[test]
+    fn points_are_counted_past_the_edge_they_left_through() {
+        let points = [
+            (0.5, 0.5),
+            (0.5, 2.0),
+            (0.2, 1.5),
+            (0.5, -1.0),
+            (-3.0, 0.5),
+            (4.0, 0.5),
+            (5.0, 0.0),
+        ];
+
+        let counts = counts(ClipCounts::new(&points, &(0.0..1.0, 0.0..1.0)));
+
+        // [Top, Bottom, Left, Right]
+        assert_eq!(counts, [2, 1, 1, 2]);
+    }
+
+    #[test]
+    fn points_on_the_edges_are_in_view() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (0.0, 1.0), (1.0, 0.0)];
+
+        let counts = counts(ClipCounts::new(&points, &(0.0..1.0, 0.0..1.0)));
+
+        assert_eq!(counts, [0; 4]);
+    }
+
+    #[test]
+    fn points_out_of_view_along_both_axes_are_not_counted() {
+        let points = [(-1.0, -1.0), (2.0, 2.0), (-1.0, 2.0), (2.0, -1.0)];
+
+        let counts = counts(ClipCounts::new(&points, &(0.0..1.0, 0.0..1.0)));
+
+        assert_eq!(counts, [0; 4]);
+    }
+}
@@ -0,0 +1,761 @@
+//! Chart comparing two runs sharing an X axis, with their difference or ratio in a panel below.
+
+use std::{cmp::Ordering, ops::Range, sync::Arc};
+
+use egui::Ui;
+use plotters::{
+    element::PathElement,
+    prelude::{ChartBuilder, LabelAreaPosition, SeriesLabelPosition},
+    series::LineSeries,
+    style::{
+        full_palette::GREY_700, Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK, WHITE,
+    },
+};
+use plotters_backend::{DrawingBackend, DrawingErrorKind, FontFamily, FontStyle};
+
+use crate::{
+    charts::{
+        grid::{draw_grid, GridConfig},
+        hover::hover_pos,
+        textfit::TextFit,
+        units::si_axis,
+    },
+    locale::locale,
+    palette::default_palette,
+    Chart,
+};
+
+const DEFAULT_MARGIN: i32 = 25;
+const DEFAULT_LABEL_AREA: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+/// Default height of the comparison panel, relative to the whole chart.
+const DEFAULT_PANEL_SIZE: f32 = 0.3;
+/// Headroom above and below the lines, relative to the value range.
+const VALUE_PADDING: f32 = 0.05;
+/// Smallest width of the visible X range, relative to the whole chart.
+const MIN_VIEW_WIDTH: f32 = 0.001;
+const DEFAULT_ZOOM_SCALE: f32 = 0.002;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+/// How the second run is compared to the first in the lower panel.
+pub enum ComparisonMode {
+    /// Second run minus the first.
+    #[default]
+    Difference,
+    /// Second run divided by the first.
+    Ratio,
+}
+
+impl ComparisonMode {
+    /// Compare two values.
+    fn compare(self, a: f32, b: f32) -> f32 {
+        match self {
+            Self::Difference => b - a,
+            Self::Ratio => b / a,
+        }
+    }
+
+    /// Value the runs compare to when they are equal.
+    fn baseline(self) -> f32 {
+        match self {
+            Self::Difference => 0.0,
+            Self::Ratio => 1.0,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ComparisonConfig {
+    /// Points of both runs, sorted by X.
+    runs: [Arc<[(f32, f32)]>; 2],
+    /// Names of both runs, shown in the legend.
+    names: [Arc<str>; 2],
+    /// Colors of both runs.
+    colors: [RGBAColor; 2],
+    /// Comparison of the runs wherever they overlap.
+    comparison: Arc<[(f32, f32)]>,
+    mode: ComparisonMode,
+    /// Style of the comparison line.
+    comparison_style: ShapeStyle,
+    /// X range shown by both panels.
+    view: Range<f32>,
+    /// Height of the comparison panel, relative to the whole chart.
+    panel_size: f32,
+    /// Grid lines of the X axis.
+    x_grid: GridConfig,
+    /// Grid lines of the value axes.
+    y_grid: GridConfig,
+    /// Style of the axes.
+    axes_style: ShapeStyle,
+    /// Style of the text
+    text_color: RGBAColor,
+    /// Unit of the X axis.
+    x_unit: Arc<str>,
+    /// Unit of the value axis.
+    y_unit: Arc<str>,
+    /// SI units of the X and value axes, their labels scaled with a prefix to fit the range.
+    /// Unscaled if None.
+    si_units: (Option<Arc<str>>, Option<Arc<str>>),
+    /// Caption of the chart. Hidden if None.
+    caption: Option<Arc<str>>,
+    /// Caption, axis descriptions, and legend entries shortened to fit the chart.
+    text_fit: TextFit,
+    /// Show a legend naming both runs.
+    legend: bool,
+}
+
+/// Value of a run at an X position, interpolating linearly between its points. None outside of
+/// the run.
+fn interpolate(run: &[(f32, f32)], x: f32) -> Option<f32> {
+    let index = run.partition_point(|(point_x, _)| *point_x < x);
+
+    let (next_x, next_y) = *run.get(index)?;
+
+    if next_x == x {
+        return Some(next_y);
+    }
+
+    let (prev_x, prev_y) = run[index.checked_sub(1)?];
+
+    Some(prev_y + (next_y - prev_y) * (x - prev_x) / (next_x - prev_x))
+}
+
+/// Compare two runs at every X value of either run where both have a value.
+fn compare(runs: &[Arc<[(f32, f32)]>; 2], mode: ComparisonMode) -> Arc<[(f32, f32)]> {
+    let [a, b] = runs;
+
+    let mut xs: Vec<f32> = a.iter().chain(b.iter()).map(|(x, _)| *x).collect();
+
+    xs.sort_by(|x1, x2| x1.partial_cmp(x2).unwrap_or(Ordering::Equal));
+    xs.dedup();
+
+    xs.into_iter()
+        .filter_map(|x| {
+            let value = mode.compare(interpolate(a, x)?, interpolate(b, x)?);
+
+            value.is_finite().then_some((x, value))
+        })
+        .collect()
+}
+
+/// Points of a run within the X range, cut at its edges, as nothing outside of the plot is
+/// clipped.
+fn visible(run: &[(f32, f32)], view: &Range<f32>) -> Vec<(f32, f32)> {
+    let first = run.partition_point(|(x, _)| *x <= view.start);
+    let last = run.partition_point(|(x, _)| *x < view.end);
+
+    let start = interpolate(run, view.start).map(|y| (view.start, y));
+    let end = interpolate(run, view.end).map(|y| (view.end, y));
+
+    start
+        .into_iter()
+        .chain(run[first..last.max(first)].iter().copied())
+        .chain(end)
+        .collect()
+}
+
+/// Range of the values of every point, with some headroom.
+fn value_range<'a>(points: impl IntoIterator<Item = &'a (f32, f32)>) -> Range<f32> {
+    let (min, max) = points
+        .into_iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), (_, y)| {
+            (min.min(*y), max.max(*y))
+        });
+
+    match max > min {
+        true => {
+            let padding = (max - min) * VALUE_PADDING;
+
+            (min - padding)..(max + padding)
+        }
+        // Make sure flat and empty panels can still be drawn
+        false if min.is_finite() => (min - 1.0)..(min + 1.0),
+        false => -1.0..1.0,
+    }
+}
+
+/// Two runs plotted together, along with their difference or ratio.
+///
+/// ## Usage
+/// Creating the chart requires the two runs to compare and a caption.
+///
+///  * `a` & `b`: The runs to compare, as slices of X, Y pairs. They don't have to share X
+///    values; each run is interpolated to the X values of the other.
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// The upper panel shows both runs, and the lower panel shows how the second run compares to
+/// the first, see [`ComparisonMode`]. Both panels always show the same X range: dragging either
+/// panel pans both, and scrolling zooms both around the pointer. Commonly used to compare
+/// benchmark runs or the traces of an A/B experiment.
+pub struct Comparison {
+    /// X range of both runs together.
+    range: Range<f32>,
+    zoom_scale: f32,
+    chart: Chart<ComparisonConfig>,
+}
+
+impl Comparison {
+    /// Create a new Comparison chart. See [Usage](#usage).
+    pub fn new(a: &[(f32, f32)], b: &[(f32, f32)], caption: &str) -> Self {
+        let sorted = |run: &[(f32, f32)]| {
+            let mut run = run.to_vec();
+
+            run.sort_by(|(x1, _), (x2, _)| x1.partial_cmp(x2).unwrap_or(Ordering::Equal));
+
+            Arc::<[(f32, f32)]>::from(run)
+        };
+
+        let runs = [sorted(a), sorted(b)];
+
+        let (start, end) = runs
+            .iter()
+            .flat_map(|run| run.iter())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), (x, _)| {
+                (min.min(*x), max.max(*x))
+            });
+
+        let range = match end > start {
+            true => start..end,
+            false if start.is_finite() => (start - 1.0)..(start + 1.0),
+            false => 0.0..1.0,
+        };
+
+        let mode = ComparisonMode::default();
+        let palette = default_palette();
+
+        let config = ComparisonConfig {
+            comparison: compare(&runs, mode),
+            runs,
+            names: ["A".into(), "B".into()],
+            colors: [palette.color(0), palette.color(1)],
+            mode,
+            comparison_style: ShapeStyle {
+                color: BLACK.to_rgba(),
+                filled: false,
+                stroke_width: 2,
+            },
+            view: range.clone(),
+            panel_size: DEFAULT_PANEL_SIZE,
+            x_grid: GridConfig::default(),
+            y_grid: GridConfig::default(),
+            axes_style: GREY_700.into(),
+            text_color: BLACK.to_rgba(),
+            x_unit: "".into(),
+            y_unit: "".into(),
+            si_units: (None, None),
+            caption: Some(caption.into()),
+            text_fit: TextFit::default(),
+            legend: true,
+        };
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, _t, data| {
+            data.text_fit.clear();
+
+            let font_desc = FontDesc::new(
+                FontFamily::Monospace,
+                CAPTION_SIZE as f64,
+                FontStyle::Normal,
+            );
+
+            let text_style = TextStyle::from(font_desc).color(&data.text_color);
+
+            let (_, height) = area.dim_in_pixel();
+            let split = (height as f32 * (1.0 - data.panel_size.clamp(0.0, 1.0))) as i32;
+            let (upper, lower) = area.split_vertically(split);
+
+            // Runs, sharing the X axis with the panel below so it isn't labeled twice
+            let mut builder = ChartBuilder::on(&upper);
+
+            builder
+                .margin_top(DEFAULT_MARGIN)
+                .margin_left(DEFAULT_MARGIN)
+                .margin_right(DEFAULT_MARGIN)
+                .y_label_area_size(DEFAULT_LABEL_AREA);
+
+            if let Some(caption) = &data.caption {
+                let margins = (DEFAULT_MARGIN, DEFAULT_MARGIN);
+                let caption = data.text_fit.caption(&upper, margins, caption, &text_style);
+
+                builder.caption(caption, text_style.clone());
+            }
+
+            let runs = data.runs.clone().map(|run| visible(&run, &data.view));
+            let y_range = value_range(runs.iter().flatten());
+
+            let Ok(mut chart) = builder.build_cartesian_2d(data.view.clone(), y_range) else {
+                // Area too small to be drawn
+                return;
+            };
+
+            draw_grid(&mut chart, &data.x_grid, &data.y_grid);
+
+            let locale = locale();
+            let (x_si_unit, y_si_unit) = (data.si_units.0.as_deref(), data.si_units.1.as_deref());
+
+            let (y_scale, y_desc) = si_axis(&chart.y_range(), &data.y_unit, y_si_unit);
+            let y_desc = data.text_fit.axis_desc(
+                chart.plotting_area(),
+                LabelAreaPosition::Left,
+                DEFAULT_LABEL_AREA,
+                &y_desc,
+                &text_style,
+            );
+            let y_label = |y: &f32| {
+                y_scale.map_or_else(|| locale.format_tick(*y as f64), |scale| scale.format(*y))
+            };
+
+            let mut mesh = chart.configure_mesh();
+
+            mesh.disable_mesh()
+                .disable_x_axis()
+                .y_labels(data.y_grid.get_major_lines())
+                .label_style(text_style.clone())
+                .axis_style(data.axes_style)
+                .y_desc(y_desc)
+                .y_label_formatter(&y_label);
+
+            mesh.draw().unwrap();
+
+            let names = [&*data.names[0], &*data.names[1]];
+            let labels = data
+                .text_fit
+                .legend(chart.plotting_area(), &names, &text_style);
+
+            for (run, (label, color)) in runs.into_iter().zip(labels.iter().zip(data.colors)) {
+                let style = color.stroke_width(2);
+
+                let series_anno = chart.draw_series(LineSeries::new(run, style)).unwrap();
+
+                if data.legend {
+                    series_anno
+                        .label(label.to_string())
+                        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], style));
+                }
+            }
+
+            if data.legend {
+                chart
+                    .configure_series_labels()
+                    .position(SeriesLabelPosition::UpperRight)
+                    .label_font(text_style.clone())
+                    .background_style(WHITE.mix(0.8))
+                    .border_style(data.axes_style)
+                    .draw()
+                    .unwrap();
+            }
+
+            // Comparison of the runs
+            let mut builder = ChartBuilder::on(&lower);
+
+            builder
+                .margin_bottom(DEFAULT_MARGIN)
+                .margin_left(DEFAULT_MARGIN)
+                .margin_right(DEFAULT_MARGIN)
+                .x_label_area_size(DEFAULT_LABEL_AREA)
+                .y_label_area_size(DEFAULT_LABEL_AREA);
+
+            let baseline = data.mode.baseline();
+            let comparison = visible(&data.comparison, &data.view);
+            let y_range = value_range(comparison.iter().chain([&(data.view.start, baseline)]));
+
+            let Ok(mut chart) = builder.build_cartesian_2d(data.view.clone(), y_range) else {
+                return;
+            };
+
+            draw_grid(&mut chart, &data.x_grid, &GridConfig::disabled());
+
+            let (x_scale, x_desc) = si_axis(&data.view, &data.x_unit, x_si_unit);
+            let x_label = |x: &f32| {
+                x_scale.map_or_else(|| locale.format_tick(*x as f64), |scale| scale.format(*x))
+            };
+
+            // Differences share the unit of the runs, ratios have none
+            let (y_scale, y_desc) = match data.mode {
+                ComparisonMode::Difference => si_axis(
+                    &chart.y_range(),
+                    &format!("{} - {}", data.names[1], data.names[0]),
+                    y_si_unit,
+                ),
+                ComparisonMode::Ratio => (None, format!("{} / {}", data.names[1], data.names[0])),
+            };
+
+            let plot = chart.plotting_area();
+            let x_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Bottom,
+                DEFAULT_LABEL_AREA,
+                &x_desc,
+                &text_style,
+            );
+            let y_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Left,
+                DEFAULT_LABEL_AREA,
+                &y_desc,
+                &text_style,
+            );
+            let y_label = |y: &f32| {
+                y_scale.map_or_else(|| locale.format_tick(*y as f64), |scale| scale.format(*y))
+            };
+
+            let mut mesh = chart.configure_mesh();
+
+            mesh.disable_mesh()
+                .x_labels(data.x_grid.get_major_lines())
+                .y_labels(2)
+                .label_style(text_style.clone())
+                .axis_style(data.axes_style)
+                .x_desc(x_desc)
+                .y_desc(y_desc)
+                .x_label_formatter(&x_label)
+                .y_label_formatter(&y_label);
+
+            mesh.draw().unwrap();
+
+            // Mark where the runs are equal
+            chart
+                .draw_series([PathElement::new(
+                    vec![(data.view.start, baseline), (data.view.end, baseline)],
+                    data.axes_style,
+                )])
+                .unwrap();
+
+            chart
+                .draw_series(LineSeries::new(comparison, data.comparison_style))
+                .unwrap();
+        }));
+
+        Self {
+            range,
+            zoom_scale: DEFAULT_ZOOM_SCALE,
+            chart,
+        }
+    }
+
+    #[inline]
+    /// Set the names of the runs, shown in the legend and the comparison panel.
+    pub fn set_names(&mut self, a: &str, b: &str) {
+        self.chart.get_data_mut().names = [a.into(), b.into()]
+    }
+
+    #[inline]
+    /// Set the names of the runs. Consumes self.
+    pub fn names(mut self, a: &str, b: &str) -> Self {
+        self.set_names(a, b);
+
+        self
+    }
+
+    #[inline]
+    /// Set the colors of the runs.
+    pub fn set_colors<A, B>(&mut self, a: A, b: B)
+    where
+        A: Into<RGBAColor>,
+        B: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().colors = [a.into(), b.into()]
+    }
+
+    #[inline]
+    /// Set the colors of the runs. Consumes self.
+    pub fn colors<A, B>(mut self, a: A, b: B) -> Self
+    where
+        A: Into<RGBAColor>,
+        B: Into<RGBAColor>,
+    {
+        self.set_colors(a, b);
+
+        self
+    }
+
+    /// Set how the second run is compared to the first.
+    pub fn set_mode(&mut self, mode: ComparisonMode) {
+        let config = self.chart.get_data_mut();
+
+        config.mode = mode;
+        config.comparison = compare(&config.runs, mode);
+    }
+
+    #[inline]
+    /// Set how the second run is compared to the first. Consumes self.
+    pub fn mode(mut self, mode: ComparisonMode) -> Self {
+        self.set_mode(mode);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the comparison line.
+    pub fn set_comparison_style(&mut self, comparison_style: ShapeStyle) {
+        self.chart.get_data_mut().comparison_style = comparison_style
+    }
+
+    #[inline]
+    /// Set the style of the comparison line. Consumes self.
+    pub fn comparison_style(mut self, comparison_style: ShapeStyle) -> Self {
+        self.set_comparison_style(comparison_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the height of the comparison panel, relative to the whole chart.
+    pub fn set_panel_size(&mut self, panel_size: f32) {
+        self.chart.get_data_mut().panel_size = panel_size
+    }
+
+    #[inline]
+    /// Set the height of the comparison panel, relative to the whole chart. Consumes self.
+    pub fn panel_size(mut self, panel_size: f32) -> Self {
+        self.set_panel_size(panel_size);
+
+        self
+    }
+
+    #[inline]
+    /// Set the X range shown by both panels.
+    pub fn set_view(&mut self, view: Range<f32>) {
+        self.chart.get_data_mut().view = view
+    }
+
+    #[inline]
+    /// Set the X range shown by both panels. Consumes self.
+    pub fn view(mut self, view: Range<f32>) -> Self {
+        self.set_view(view);
+
+        self
+    }
+
+    #[inline]
+    /// Return the X range shown by both panels.
+    pub fn get_view(&self) -> Range<f32> {
+        self.chart.get_data().view.clone()
+    }
+
+    #[inline]
+    /// Zoom out to show both runs entirely.
+    pub fn reset_view(&mut self) {
+        self.set_view(self.range.clone());
+    }
+
+    #[inline]
+    /// Set how quickly scrolling zooms in/out.
+    pub fn set_zoom_scale(&mut self, zoom_scale: f32) {
+        self.zoom_scale = zoom_scale
+    }
+
+    #[inline]
+    /// Set how quickly scrolling zooms in/out. Consumes self.
+    pub fn zoom_scale(mut self, zoom_scale: f32) -> Self {
+        self.set_zoom_scale(zoom_scale);
+
+        self
+    }
+
+    #[inline]
+    /// Set the grid lines of the X axis.
+    pub fn set_x_grid(&mut self, x_grid: GridConfig) {
+        self.chart.get_data_mut().x_grid = x_grid
+    }
+
+    #[inline]
+    /// Set the grid lines of the X axis. Consumes self.
+    pub fn x_grid(mut self, x_grid: GridConfig) -> Self {
+        self.set_x_grid(x_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Set the grid lines of the value axis of the upper panel.
+    pub fn set_y_grid(&mut self, y_grid: GridConfig) {
+        self.chart.get_data_mut().y_grid = y_grid
+    }
+
+    #[inline]
+    /// Set the grid lines of the value axis of the upper panel. Consumes self.
+    pub fn y_grid(mut self, y_grid: GridConfig) -> Self {
+        self.set_y_grid(y_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the axes.
+    pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
+        self.chart.get_data_mut().axes_style = axes_style
+    }
+
+    #[inline]
+    /// Set the style of the axes. Consumes self.
+    pub fn axes_style(mut self, axes_style: ShapeStyle) -> Self {
+        self.set_axes_style(axes_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the units of the X and value axes.
+    pub fn set_units(&mut self, x_unit: &str, y_unit: &str) {
+        let config = self.chart.get_data_mut();
+
+        config.x_unit = x_unit.into();
+        config.y_unit = y_unit.into();
+    }
+
+    #[inline]
+    /// Set the units of the X and value axes. Consumes self.
+    pub fn units(mut self, x_unit: &str, y_unit: &str) -> Self {
+        self.set_units(x_unit, y_unit);
+
+        self
+    }
+
+    #[inline]
+    /// Set the SI units of the X and value axes, such as "s", "B", or "V". Tick labels of an
+    /// axis with a unit are scaled with an SI prefix to fit its range, such as ms, kB, or MV,
+    /// and the prefixed unit is added to its description. Axes with None are left unscaled.
+    pub fn set_si_units(&mut self, x_si_unit: Option<&str>, y_si_unit: Option<&str>) {
+        self.chart.get_data_mut().si_units = (
+            x_si_unit.map(|unit| unit.into()),
+            y_si_unit.map(|unit| unit.into()),
+        )
+    }
+
+    #[inline]
+    /// Set the SI units of the X and value axes. Consumes self.
+    pub fn si_units(mut self, x_si_unit: Option<&str>, y_si_unit: Option<&str>) -> Self {
+        self.set_si_units(x_si_unit, y_si_unit);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide the legend naming both runs.
+    pub fn set_legend(&mut self, legend: bool) {
+        self.chart.get_data_mut().legend = legend
+    }
+
+    #[inline]
+    /// Show/hide the legend naming both runs. Consumes self.
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.set_legend(legend);
+
+        self
+    }
+
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening the caption, axis descriptions, and legend entries too long for
+    /// the chart with an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.get_data_mut().text_fit.set_enabled(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_data().text_fit.is_enabled()
+    }
+
+    /// Draw the chart to a Ui.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        if let Some((x, _)) = hover_pos(ui) {
+            self.pan_zoom(ui, x);
+        }
+
+        self.chart.draw(ui);
+
+        let transform = self.chart.get_transform();
+
+        self.chart.get_data().text_fit.show_full_text(ui, transform);
+    }
+
+    /// Draw the chart to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.chart.draw_headless(backend)
+    }
+
+    /// Pan both panels by dragging, and zoom them around the pointer by scrolling.
+    fn pan_zoom(&mut self, ui: &Ui, pointer_x: f32) {
+        let plot_left = (DEFAULT_MARGIN + DEFAULT_LABEL_AREA) as f32;
+        let plot_width = ui.max_rect().width() - plot_left - DEFAULT_MARGIN as f32;
+
+        if plot_width <= 0.0 {
+            return;
+        }
+
+        let Range { start, end } = self.chart.get_data().view;
+        let width = end - start;
+        let units_per_pixel = width / plot_width;
+
+        let (drag, scroll) = ui.input(|input| {
+            let drag = match input.pointer.primary_down() {
+                true => input.pointer.delta().x,
+                false => 0.0,
+            };
+
+            (drag, input.smooth_scroll_delta.y)
+        });
+
+        let shift = -drag * units_per_pixel;
+
+        // Zoom around the X value under the pointer
+        let min_width = (self.range.end - self.range.start).abs() * MIN_VIEW_WIDTH;
+        let anchor = start + (pointer_x - plot_left) * units_per_pixel;
+        let zoom = (-scroll * self.zoom_scale).exp();
+        let new_width = (width * zoom).max(min_width.max(f32::EPSILON));
+        let ratio = new_width / width;
+
+        let new_start = anchor - (anchor - start) * ratio + shift;
+        let new_end = new_start + new_width;
+
+        if new_start != start || new_end != end {
+            self.set_view(new_start..new_end);
+        }
+    }
+}
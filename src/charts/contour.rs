@@ -0,0 +1,714 @@
+//! Contour chart drawing iso-lines and filled bands over a 2D scalar field.
+
+use std::{ops::Range, sync::Arc};
+
+use egui::Ui;
+use plotters::{
+    element::{PathElement, Polygon},
+    prelude::{ChartBuilder, LabelAreaPosition},
+    style::{full_palette::GREY_700, Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK},
+};
+use plotters_backend::{DrawingBackend, DrawingErrorKind, FontFamily, FontStyle};
+
+use crate::{
+    charts::textfit::TextFit,
+    colormap::{Colormap, VIRIDIS},
+    locale::locale,
+    Chart,
+};
+
+const DEFAULT_LEVEL_COUNT: usize = 10;
+const DEFAULT_MARGIN: i32 = 25;
+const DEFAULT_LABEL_AREA: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+
+/// Corner of a triangle of the field, as its X, Y, and value.
+type Vertex = (f32, f32, f32);
+
+#[derive(Clone)]
+struct ContourConfig {
+    /// Values of the field, row by row from the start of the Y range.
+    field: Arc<[f32]>,
+    /// Number of values in each row of the field.
+    columns: usize,
+    /// X range covered by the columns of the field.
+    x_range: Range<f32>,
+    /// Y range covered by the rows of the field.
+    y_range: Range<f32>,
+    /// Values to draw iso-lines at. Evenly spaced levels are used if None.
+    levels: Option<Arc<[f32]>>,
+    /// Number of evenly spaced levels used when no levels are set.
+    level_count: usize,
+    /// Colormap the lines and bands are colored with.
+    colormap: Arc<dyn Colormap>,
+    /// Draw the iso-lines.
+    lines: bool,
+    /// Fill the bands between the iso-lines.
+    filled: bool,
+    /// Style of the iso-lines, colored with the colormap if None.
+    line_style: Option<ShapeStyle>,
+    /// Style of the axes.
+    axes_style: ShapeStyle,
+    /// Style of the text
+    text_color: RGBAColor,
+    /// Unit of the X axis.
+    x_unit: Arc<str>,
+    /// Unit of the Y axis.
+    y_unit: Arc<str>,
+    /// Caption of the chart. Hidden if None.
+    caption: Option<Arc<str>>,
+    /// Caption and axis descriptions shortened to fit the chart.
+    text_fit: TextFit,
+}
+
+impl ContourConfig {
+    /// Smallest and largest finite values of the field.
+    fn value_range(&self) -> (f32, f32) {
+        self.field
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f32::MAX, f32::MIN), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            })
+    }
+
+    /// Levels to draw iso-lines at, in ascending order.
+    fn levels(&self, min: f32, max: f32) -> Vec<f32> {
+        match &self.levels {
+            Some(levels) => {
+                let mut levels = levels.to_vec();
+
+                levels.sort_by(f32::total_cmp);
+
+                levels
+            }
+            None => {
+                let step = (max - min) / (self.level_count + 1) as f32;
+
+                (1..=self.level_count)
+                    .map(|level| min + step * level as f32)
+                    .collect()
+            }
+        }
+    }
+
+    /// Split the field into triangles, two per cell, skipping cells with non-finite values.
+    fn triangles(&self) -> Vec<[Vertex; 3]> {
+        let columns = self.columns;
+        let rows = self.field.len() / columns.max(1);
+
+        if columns < 2 || rows < 2 {
+            return Vec::new();
+        }
+
+        let x_step = (self.x_range.end - self.x_range.start) / (columns - 1) as f32;
+        let y_step = (self.y_range.end - self.y_range.start) / (rows - 1) as f32;
+
+        let vertex = |column: usize, row: usize| {
+            (
+                self.x_range.start + x_step * column as f32,
+                self.y_range.start + y_step * row as f32,
+                self.field[row * columns + column],
+            )
+        };
+
+        let mut triangles = Vec::with_capacity((columns - 1) * (rows - 1) * 2);
+
+        for row in 0..rows - 1 {
+            for column in 0..columns - 1 {
+                let corners = [
+                    vertex(column, row),
+                    vertex(column + 1, row),
+                    vertex(column + 1, row + 1),
+                    vertex(column, row + 1),
+                ];
+
+                if corners.iter().any(|(_, _, value)| !value.is_finite()) {
+                    continue;
+                }
+
+                let [a, b, c, d] = corners;
+
+                triangles.push([a, b, c]);
+                triangles.push([a, c, d]);
+            }
+        }
+
+        triangles
+    }
+}
+
+/// Point along the edge from a to b where the value crosses the level.
+fn crossing(a: Vertex, b: Vertex, level: f32) -> Vertex {
+    let (ax, ay, av) = a;
+    let (bx, by, bv) = b;
+
+    let fraction = match bv != av {
+        true => (level - av) / (bv - av),
+        false => 0.0,
+    };
+
+    (ax + (bx - ax) * fraction, ay + (by - ay) * fraction, level)
+}
+
+/// Clip a convex polygon to the vertices on one side of a level. As values are linear across
+/// each triangle the result is still convex.
+fn clip(polygon: Vec<Vertex>, level: f32, above: bool) -> Vec<Vertex> {
+    let inside = |(_, _, value): Vertex| match above {
+        true => value >= level,
+        false => value <= level,
+    };
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (index, a) in polygon.iter().enumerate() {
+        let b = polygon[(index + 1) % polygon.len()];
+
+        if inside(*a) {
+            clipped.push(*a);
+        }
+
+        if inside(*a) != inside(b) {
+            clipped.push(crossing(*a, b, level));
+        }
+    }
+
+    clipped
+}
+
+/// Segment of a triangle lying on a level, if the level crosses it.
+fn iso_segment(triangle: &[Vertex; 3], level: f32) -> Option<[(f32, f32); 2]> {
+    let mut points = (0..3).filter_map(|index| {
+        let a = triangle[index];
+        let b = triangle[(index + 1) % 3];
+
+        let (_, _, av) = a;
+        let (_, _, bv) = b;
+
+        match (av >= level) != (bv >= level) {
+            true => {
+                let (x, y, _) = crossing(a, b, level);
+
+                Some((x, y))
+            }
+            false => None,
+        }
+    });
+
+    Some([points.next()?, points.next()?])
+}
+
+/// Contour chart.
+///
+/// ## Usage
+/// Creating the chart requires a 2D scalar field, the area it covers, and a caption.
+///
+///  * `field`: Values of the field, row by row. Rows run along the X axis, and go up the Y axis.
+///  * `columns`: Number of values in each row.
+///  * `x_range` & `y_range`: Area covered by the field. The values are evenly spaced across it.
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// Iso-lines are drawn at evenly spaced levels by default, which can be replaced with specific
+/// levels using `.set_levels()`. The bands between the levels can be filled with `.set_filled()`,
+/// and both lines and bands are colored with a [`Colormap`], viridis by default. Non-finite
+/// values leave holes in the chart.
+pub struct Contour {
+    chart: Chart<ContourConfig>,
+}
+
+impl Contour {
+    /// Create a new Contour chart. See [Usage](#usage).
+    pub fn new(
+        field: &[f32],
+        columns: usize,
+        x_range: Range<f32>,
+        y_range: Range<f32>,
+        caption: &str,
+    ) -> Self {
+        let config = ContourConfig {
+            field: field.into(),
+            columns,
+            x_range,
+            y_range,
+            levels: None,
+            level_count: DEFAULT_LEVEL_COUNT,
+            colormap: Arc::new(VIRIDIS),
+            lines: true,
+            filled: false,
+            line_style: None,
+            axes_style: GREY_700.into(),
+            text_color: BLACK.to_rgba(),
+            x_unit: "".into(),
+            y_unit: "".into(),
+            caption: Some(caption.into()),
+            text_fit: TextFit::default(),
+        };
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, _t, data| {
+            data.text_fit.clear();
+
+            let font_desc = FontDesc::new(
+                FontFamily::Monospace,
+                CAPTION_SIZE as f64,
+                FontStyle::Normal,
+            );
+
+            let text_style = TextStyle::from(font_desc).color(&data.text_color);
+
+            let mut builder = ChartBuilder::on(area);
+
+            builder
+                .margin(DEFAULT_MARGIN)
+                .x_label_area_size(DEFAULT_LABEL_AREA)
+                .y_label_area_size(DEFAULT_LABEL_AREA);
+
+            if let Some(caption) = &data.caption {
+                let margins = (DEFAULT_MARGIN, DEFAULT_MARGIN);
+                let caption = data.text_fit.caption(area, margins, caption, &text_style);
+
+                builder.caption(caption, text_style.clone());
+            }
+
+            let mut chart =
+                match builder.build_cartesian_2d(data.x_range.clone(), data.y_range.clone()) {
+                    Ok(chart) => chart,
+                    // Area too small to be drawn
+                    Err(_) => return,
+                };
+
+            let plot = chart.plotting_area();
+            let x_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Bottom,
+                DEFAULT_LABEL_AREA,
+                &data.x_unit,
+                &text_style,
+            );
+            let y_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Left,
+                DEFAULT_LABEL_AREA,
+                &data.y_unit,
+                &text_style,
+            );
+
+            let locale = locale();
+            let tick_label = |value: &f32| locale.format_tick(*value as f64);
+
+            chart
+                .configure_mesh()
+                .disable_mesh()
+                .x_label_formatter(&tick_label)
+                .y_label_formatter(&tick_label)
+                .label_style(text_style.clone())
+                .axis_style(data.axes_style)
+                .x_desc(x_desc)
+                .y_desc(y_desc)
+                .draw()
+                .unwrap();
+
+            let (min, max) = data.value_range();
+
+            // Nothing to draw if the field has no finite values
+            if min > max {
+                return;
+            }
+
+            let levels = data.levels(min, max);
+            let triangles = data.triangles();
+
+            let normalize = |value: f32| match max > min {
+                true => (value - min) / (max - min),
+                false => 0.0,
+            };
+
+            if data.filled {
+                // Bands from the smallest value to the first level, between each level, and from
+                // the last level to the largest value
+                let bounds: Vec<f32> = [min]
+                    .into_iter()
+                    .chain(
+                        levels
+                            .iter()
+                            .copied()
+                            .filter(|level| *level > min && *level < max),
+                    )
+                    .chain([max])
+                    .collect();
+
+                for band in bounds.windows(2) {
+                    let (low, high) = (band[0], band[1]);
+                    let color = data.colormap.color(normalize((low + high) / 2.0));
+
+                    let pieces = triangles.iter().filter_map(|triangle| {
+                        let polygon = clip(triangle.to_vec(), low, true);
+                        let polygon = clip(polygon, high, false);
+
+                        match polygon.len() >= 3 {
+                            true => Some(Polygon::new(
+                                polygon
+                                    .into_iter()
+                                    .map(|(x, y, _)| (x, y))
+                                    .collect::<Vec<_>>(),
+                                color.filled(),
+                            )),
+                            false => None,
+                        }
+                    });
+
+                    chart.draw_series(pieces).unwrap();
+                }
+            }
+
+            if data.lines {
+                for level in levels {
+                    let style = data
+                        .line_style
+                        .unwrap_or_else(|| data.colormap.color(normalize(level)).stroke_width(1));
+
+                    let segments = triangles.iter().filter_map(|triangle| {
+                        iso_segment(triangle, level)
+                            .map(|segment| PathElement::new(segment.to_vec(), style))
+                    });
+
+                    chart.draw_series(segments).unwrap();
+                }
+            }
+        }));
+
+        Self { chart }
+    }
+
+    /// Replace the field. Its dimensions may differ from the previous field.
+    pub fn set_field(&mut self, field: &[f32], columns: usize) {
+        let config = self.chart.get_data_mut();
+
+        config.field = field.into();
+        config.columns = columns;
+    }
+
+    #[inline]
+    /// Set the area covered by the field.
+    pub fn set_ranges(&mut self, x_range: Range<f32>, y_range: Range<f32>) {
+        let config = self.chart.get_data_mut();
+
+        config.x_range = x_range;
+        config.y_range = y_range;
+    }
+
+    #[inline]
+    /// Set the area covered by the field. Consumes self.
+    pub fn ranges(mut self, x_range: Range<f32>, y_range: Range<f32>) -> Self {
+        self.set_ranges(x_range, y_range);
+
+        self
+    }
+
+    #[inline]
+    /// Set the values to draw iso-lines at.
+    pub fn set_levels(&mut self, levels: &[f32]) {
+        self.chart.get_data_mut().levels = Some(levels.into())
+    }
+
+    #[inline]
+    /// Set the values to draw iso-lines at. Consumes self.
+    pub fn levels(mut self, levels: &[f32]) -> Self {
+        self.set_levels(levels);
+
+        self
+    }
+
+    #[inline]
+    /// Draw a number of evenly spaced iso-lines between the smallest and largest values of the
+    /// field, replacing any levels set.
+    pub fn set_level_count(&mut self, level_count: usize) {
+        let config = self.chart.get_data_mut();
+
+        config.levels = None;
+        config.level_count = level_count;
+    }
+
+    #[inline]
+    /// Draw a number of evenly spaced iso-lines. Consumes self.
+    pub fn level_count(mut self, level_count: usize) -> Self {
+        self.set_level_count(level_count);
+
+        self
+    }
+
+    /// Set the colormap the lines and bands are colored with.
+    pub fn set_colormap<C>(&mut self, colormap: C)
+    where
+        C: Colormap + 'static,
+    {
+        self.chart.get_data_mut().colormap = Arc::new(colormap)
+    }
+
+    #[inline]
+    /// Set the colormap the lines and bands are colored with. Consumes self.
+    pub fn colormap<C>(mut self, colormap: C) -> Self
+    where
+        C: Colormap + 'static,
+    {
+        self.set_colormap(colormap);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide the iso-lines.
+    pub fn set_lines(&mut self, lines: bool) {
+        self.chart.get_data_mut().lines = lines
+    }
+
+    #[inline]
+    /// Show/hide the iso-lines. Consumes self.
+    pub fn lines(mut self, lines: bool) -> Self {
+        self.set_lines(lines);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable filling the bands between the iso-lines.
+    pub fn set_filled(&mut self, filled: bool) {
+        self.chart.get_data_mut().filled = filled
+    }
+
+    #[inline]
+    /// Enable/disable filling the bands between the iso-lines. Consumes self.
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.set_filled(filled);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the iso-lines, or color them with the colormap with None.
+    pub fn set_line_style(&mut self, line_style: Option<ShapeStyle>) {
+        self.chart.get_data_mut().line_style = line_style
+    }
+
+    #[inline]
+    /// Set the style of the iso-lines, or color them with the colormap with None. Consumes self.
+    pub fn line_style(mut self, line_style: Option<ShapeStyle>) -> Self {
+        self.set_line_style(line_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the axes.
+    pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
+        self.chart.get_data_mut().axes_style = axes_style
+    }
+
+    #[inline]
+    /// Set the style of the axes. Consumes self.
+    pub fn axes_style(mut self, axes_style: ShapeStyle) -> Self {
+        self.set_axes_style(axes_style);
+
+        self
+    }
+
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the units of the X and Y axes.
+    pub fn set_units(&mut self, x_unit: &str, y_unit: &str) {
+        let config = self.chart.get_data_mut();
+
+        config.x_unit = x_unit.into();
+        config.y_unit = y_unit.into();
+    }
+
+    #[inline]
+    /// Set the units of the X and Y axes. Consumes self.
+    pub fn units(mut self, x_unit: &str, y_unit: &str) -> Self {
+        self.set_units(x_unit, y_unit);
+
+        self
+    }
+
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening the caption and axis descriptions too long for
+    /// the chart with an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.get_data_mut().text_fit.set_enabled(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_data().text_fit.is_enabled()
+    }
+
+    #[inline]
+    /// Draw the chart to a Ui.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        self.chart.draw(ui);
+
+        let transform = self.chart.get_transform();
+
+        self.chart.get_data().text_fit.show_full_text(ui, transform);
+    }
+
+    /// Draw the chart to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.chart.draw_headless(backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Field of one cell, with its corners valued from the lower left, counterclockwise.
+    fn cell(values: [f32; 4]) -> ContourConfig {
+        let [a, b, c, d] = values;
+
+        Contour::new(&[a, b, d, c], 2, 0.0..1.0, 0.0..1.0, "")
+            .chart
+            .get_data()
+            .clone()
+    }
+
+    fn area(polygon: &[Vertex]) -> f32 {
+        let twice: f32 = (0..polygon.len())
+            .map(|index| {
+                let (ax, ay, _) = polygon[index];
+                let (bx, by, _) = polygon[(index + 1) % polygon.len()];
+
+                ax * by - bx * ay
+            })
+            .sum();
+
+        twice.abs() / 2.0
+    }
+
+    #[test]
+    fn levels_are_evenly_spaced_unless_set() {
+        let mut config = cell([0.0, 2.0, 4.0, 10.0]);
+        config.level_count = 4;
+
+        assert_eq!(config.value_range(), (0.0, 10.0));
+        assert_eq!(config.levels(0.0, 10.0), [2.0, 4.0, 6.0, 8.0]);
+
+        config.levels = Some([5.0, -1.0, 3.0].into());
+
+        assert_eq!(config.levels(0.0, 10.0), [-1.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn saddle_cells_are_crossed_twice() {
+        // Opposite corners are high, so the level crosses every edge of the cell
+        let config = cell([1.0, 0.0, 1.0, 0.0]);
+        let triangles = config.triangles();
+
+        assert_eq!(triangles.len(), 2);
+
+        let mut crossings: Vec<_> = triangles
+            .iter()
+            .flat_map(|triangle| iso_segment(triangle, 0.5).unwrap())
+            .collect();
+
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert_eq!(crossings, [(0.0, 0.5), (0.5, 0.0), (0.5, 1.0), (1.0, 0.5)]);
+    }
+
+    #[test]
+    fn levels_outside_the_field_cross_nothing() {
+        let config = cell([1.0, 0.0, 1.0, 0.0]);
+
+        for triangle in config.triangles() {
+            assert_eq!(iso_segment(&triangle, 2.0), None);
+            assert_eq!(iso_segment(&triangle, -1.0), None);
+
+            // Bands past the values of the field are empty
+            assert!(clip(triangle.to_vec(), 2.0, true).is_empty());
+            assert!(clip(triangle.to_vec(), -1.0, false).is_empty());
+        }
+    }
+
+    #[test]
+    fn bands_tile_the_field() {
+        let config = cell([1.0, 0.0, 1.0, 0.0]);
+        let bounds = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+        let total: f32 = bounds
+            .windows(2)
+            .flat_map(|band| {
+                config.triangles().into_iter().map(move |triangle| {
+                    let polygon = clip(triangle.to_vec(), band[0], true);
+
+                    area(&clip(polygon, band[1], false))
+                })
+            })
+            .sum();
+
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cells_with_nan_values_are_left_out() {
+        // Three cells in a row, the last two sharing a NaN corner
+        let field = [0.0, 1.0, 2.0, 3.0, 0.0, 1.0, f32::NAN, 3.0];
+        let config = Contour::new(&field, 4, 0.0..3.0, 0.0..1.0, "")
+            .chart
+            .get_data()
+            .clone();
+
+        assert_eq!(config.value_range(), (0.0, 3.0));
+
+        let triangles = config.triangles();
+
+        assert_eq!(triangles.len(), 2);
+        assert!(triangles
+            .iter()
+            .flatten()
+            .all(|(x, _, value)| *x <= 1.0 && value.is_finite()));
+    }
+}
@@ -0,0 +1,124 @@
+//! Decimation of dense series down to one column of points per pixel.
+
+use std::ops::Range;
+
+/// Every point falling within one pixel column of the chart.
+pub(crate) struct Column {
+    /// X range covered by the column.
+    pub(crate) x: Range<f32>,
+    /// Smallest Y value in the column.
+    pub(crate) min: f32,
+    /// Largest Y value in the column.
+    pub(crate) max: f32,
+    /// Index of the last point in the column.
+    pub(crate) last: usize,
+}
+
+/// Decimated series, along with the indices of the closest points either side of the X range so
+/// lines leaving the chart stay connected.
+pub(crate) struct Decimated {
+    pub(crate) before: Option<usize>,
+    pub(crate) columns: Vec<Column>,
+    pub(crate) after: Option<usize>,
+}
+
+impl Decimated {
+    /// Indices of the points of the plain decimated line, one per column.
+    pub(crate) fn line(&self) -> Vec<usize> {
+        self.before
+            .into_iter()
+            .chain(self.columns.iter().map(|column| column.last))
+            .chain(self.after)
+            .collect()
+    }
+}
+
+/// Bucket points sorted by X into evenly sized columns spanning the X range. Empty columns are
+/// left out.
+pub(crate) fn decimate(points: &[(f32, f32)], x_range: Range<f32>, columns: usize) -> Decimated {
+    let Range { start, end } = x_range;
+    let width = (end - start) / columns.max(1) as f32;
+
+    let first = points.partition_point(|(x, _)| *x < start);
+    let last = points.partition_point(|(x, _)| *x <= end);
+
+    let mut decimated = Decimated {
+        before: first.checked_sub(1),
+        columns: Vec::with_capacity(columns),
+        after: (last < points.len()).then_some(last),
+    };
+
+    for (index, (x, y)) in points.iter().enumerate().take(last).skip(first) {
+        let (x, y) = (*x, *y);
+
+        let column_index = match width > 0.0 {
+            true => (((x - start) / width) as usize).min(columns.saturating_sub(1)),
+            false => 0,
+        };
+        let column_start = start + width * column_index as f32;
+
+        match decimated.columns.last_mut() {
+            Some(column) if column.x.start == column_start => {
+                column.min = column.min.min(y);
+                column.max = column.max.max(y);
+                column.last = index;
+            }
+            _ => decimated.columns.push(Column {
+                x: column_start..(column_start + width),
+                min: y,
+                max: y,
+                last: index,
+            }),
+        }
+    }
+
+    decimated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_are_bucketed_into_columns() {
+        let points = [
+            (-1.0, 5.0),
+            (0.0, 1.0),
+            (0.4, 3.0),
+            (0.9, 2.0),
+            (2.5, -1.0),
+            (4.0, 7.0),
+            (6.0, 0.0),
+        ];
+
+        let decimated = decimate(&points, 0.0..4.0, 4);
+        let columns: Vec<_> = decimated
+            .columns
+            .iter()
+            .map(|column| (column.x.clone(), column.min, column.max, column.last))
+            .collect();
+
+        // The column from 1 to 2 is empty, and the end of the range falls in the last column
+        assert_eq!(
+            columns,
+            [
+                (0.0..1.0, 1.0, 3.0, 3),
+                (2.0..3.0, -1.0, -1.0, 4),
+                (3.0..4.0, 7.0, 7.0, 5),
+            ]
+        );
+        assert_eq!(decimated.line(), [0, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn empty_ranges_decimate_to_one_column() {
+        let points = [(1.0, 2.0), (1.0, 4.0)];
+
+        let decimated = decimate(&points, 1.0..1.0, 10);
+
+        assert_eq!(decimated.columns.len(), 1);
+        assert_eq!(decimated.line(), [1]);
+
+        assert!(decimate(&[], 0.0..1.0, 10).line().is_empty());
+    }
+}
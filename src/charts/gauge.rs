@@ -0,0 +1,488 @@
+//! Radial gauge that can follow the playback of a time chart.
+
+use std::{cell::RefCell, ops::Range, rc::Rc, sync::Arc};
+
+use egui::Ui;
+use plotters::{
+    element::{Circle, PathElement, Polygon, Text},
+    style::{
+        full_palette::{GREY_300, RED_900},
+        Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK,
+    },
+};
+use plotters_backend::{
+    text_anchor::{HPos, Pos, VPos},
+    DrawingBackend, DrawingErrorKind, FontFamily, FontStyle,
+};
+
+use crate::{
+    charts::{timeindex::time_index, xytime::SharedSeries, XyTimeData},
+    locale::locale,
+    Chart,
+};
+
+/// Angle of the start of the dial, in degrees counter-clockwise from the right.
+const START_ANGLE: f32 = 225.0;
+/// Angle swept by the dial from start to end, in degrees clockwise.
+const SWEEP_ANGLE: f32 = 270.0;
+/// Degrees covered by each segment of the dial's bands.
+const SEGMENT_ANGLE: f32 = 3.0;
+/// Width of the dial's band relative to the radius.
+const BAND_WIDTH: f32 = 0.15;
+/// Size of the dial relative to the drawing area.
+const DIAL_SIZE: f32 = 0.4;
+const LABEL_SIZE: i32 = 10;
+
+#[derive(Clone)]
+struct GaugeConfig {
+    /// Range of values covered by the dial.
+    range: Range<f32>,
+    /// Value the needle points at.
+    value: f32,
+    /// Colored zones along the dial.
+    zones: Arc<[(Range<f32>, RGBAColor)]>,
+    /// Color of the dial where no zone is set.
+    dial_color: RGBAColor,
+    /// Style of the needle.
+    needle_style: ShapeStyle,
+    /// Style of the text
+    text_color: RGBAColor,
+    /// Caption of the gauge. Hidden if None.
+    caption: Option<Arc<str>>,
+}
+
+impl GaugeConfig {
+    /// Angle of a value on the dial in radians, clamped to the ends of the dial.
+    fn angle(&self, value: f32) -> f32 {
+        let delta = self.range.end - self.range.start;
+        let fraction = match delta > 0.0 {
+            true => ((value - self.range.start) / delta).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+
+        (START_ANGLE - fraction * SWEEP_ANGLE).to_radians()
+    }
+}
+
+/// Point at the given angle and radius from the center, in pixels.
+fn polar(center: (i32, i32), radius: f32, angle: f32) -> (i32, i32) {
+    let (x, y) = center;
+
+    (
+        x + (radius * angle.cos()) as i32,
+        y - (radius * angle.sin()) as i32,
+    )
+}
+
+/// Time chart a gauge follows the playback of, read through the series the chart shares so the
+/// gauge keeps up with its data, time mapping, and playback clock as they're replaced.
+struct GaugeBinding {
+    series: Rc<RefCell<SharedSeries>>,
+}
+
+impl GaugeBinding {
+    /// Y value of the bound chart at the given time.
+    fn value_at(&self, time: f32) -> f32 {
+        let series = self.series.borrow();
+        let (_, y) = series.points[time_index(&series.times, time)];
+
+        y
+    }
+
+    /// Y value of the bound chart at the current playback time. Like the chart itself, shows
+    /// the last point when playback is disabled.
+    fn current_value(&self) -> f32 {
+        let series = self.series.borrow();
+        let time_start = *series.times.first().unwrap();
+        let time_end = *series.times.last().unwrap();
+
+        let time = match series.playback.borrow_mut().elapsed() {
+            Some(elapsed) => {
+                let elapsed = series.member.elapsed(elapsed);
+
+                (series.time_map.to_data(elapsed) + time_start).min(time_end)
+            }
+            None => time_end,
+        };
+
+        self.value_at(time)
+    }
+}
+
+/// Radial gauge with colored zones.
+///
+/// ## Usage
+/// **Ensure the `timechart` feature is enabled to use this type.**
+///
+/// Creating the gauge only requires the range of values covered by the dial and a caption.
+///
+///  * `range`: Values at the start and end of the dial.
+///  * `caption`: String to be shown as the caption of the gauge.
+///
+/// The needle can either be set to a live value with `.set_value()`, or bound to an
+/// `XyTimeData` chart with `.bind()`, after which it follows the Y value of the chart as it is
+/// played back. `TimeData` charts are bound through `.as_xy()`. Colored zones can be added
+/// along the dial with `.add_zone()`.
+pub struct Gauge {
+    binding: Option<GaugeBinding>,
+    chart: Chart<GaugeConfig>,
+}
+
+impl Gauge {
+    /// Create a new Gauge. See [Usage](#usage).
+    pub fn new(range: Range<f32>, caption: &str) -> Self {
+        let needle_style = ShapeStyle {
+            color: RED_900.to_rgba(),
+            filled: true,
+            stroke_width: 2,
+        };
+
+        let config = GaugeConfig {
+            value: range.start,
+            range,
+            zones: Arc::new([]),
+            dial_color: GREY_300.to_rgba(),
+            needle_style,
+            text_color: BLACK.to_rgba(),
+            caption: Some(caption.into()),
+        };
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, _t, data| {
+            let (width, height) = area.dim_in_pixel();
+
+            let radius = width.min(height) as f32 * DIAL_SIZE;
+            let center = (width as i32 / 2, (height as f32 * 0.55) as i32);
+
+            // Return if the gauge is too small to be drawn
+            if radius < 1.0 {
+                return;
+            }
+
+            let font_desc =
+                |size: i32| FontDesc::new(FontFamily::Monospace, size as f64, FontStyle::Normal);
+            let text_style = |size: i32| {
+                TextStyle::from(font_desc(size))
+                    .color(&data.text_color)
+                    .pos(Pos::new(HPos::Center, VPos::Center))
+            };
+
+            // Draw the dial's band segment by segment, as each segment needs to be convex
+            let inner_radius = radius * (1.0 - BAND_WIDTH);
+            let segments = (SWEEP_ANGLE / SEGMENT_ANGLE) as usize;
+            let value_delta = (data.range.end - data.range.start) / segments as f32;
+
+            for segment in 0..segments {
+                let value_start = data.range.start + value_delta * segment as f32;
+                let value_end = value_start + value_delta;
+                let value_mid = value_start + value_delta / 2.0;
+
+                // Later zones are drawn over earlier ones
+                let color = data
+                    .zones
+                    .iter()
+                    .rev()
+                    .find(|(range, _)| range.contains(&value_mid))
+                    .map_or(data.dial_color, |(_, color)| *color);
+
+                let angle_start = data.angle(value_start);
+                let angle_end = data.angle(value_end);
+
+                let quad = vec![
+                    polar(center, radius, angle_start),
+                    polar(center, radius, angle_end),
+                    polar(center, inner_radius, angle_end),
+                    polar(center, inner_radius, angle_start),
+                ];
+
+                area.draw(&Polygon::new(quad, color.filled())).unwrap();
+            }
+
+            let locale = locale();
+
+            // Label the ends of the dial
+            let label_radius = inner_radius - LABEL_SIZE as f32;
+
+            for value in [data.range.start, data.range.end] {
+                let position = polar(center, label_radius, data.angle(value));
+
+                area.draw(&Text::new(
+                    locale.format_number(value),
+                    position,
+                    text_style(LABEL_SIZE),
+                ))
+                .unwrap();
+            }
+
+            let tip = polar(center, inner_radius, data.angle(data.value));
+
+            area.draw(&PathElement::new(vec![center, tip], data.needle_style))
+                .unwrap();
+            area.draw(&Circle::new(
+                center,
+                data.needle_style.stroke_width * 2,
+                data.needle_style.filled(),
+            ))
+            .unwrap();
+
+            // Show the value under the needle, scaled with the dial
+            let value_size = ((radius / 4.0) as i32).max(LABEL_SIZE);
+            let (x, y) = center;
+
+            area.draw(&Text::new(
+                locale.format_fixed(data.value as f64, 2),
+                (x, y + (radius / 2.0) as i32),
+                text_style(value_size),
+            ))
+            .unwrap();
+
+            if let Some(caption) = &data.caption {
+                area.draw(&Text::new(
+                    caption.to_string(),
+                    (x, y - radius as i32 - LABEL_SIZE),
+                    text_style(LABEL_SIZE),
+                ))
+                .unwrap();
+            }
+        }));
+
+        Self {
+            binding: None,
+            chart,
+        }
+    }
+
+    #[inline]
+    /// Set the value the needle points at. Has no effect while the gauge is bound to a chart.
+    pub fn set_value(&mut self, value: f32) {
+        self.chart.get_data_mut().value = value
+    }
+
+    #[inline]
+    /// Set the value the needle points at. Consumes self.
+    pub fn value(mut self, value: f32) -> Self {
+        self.set_value(value);
+
+        self
+    }
+
+    #[inline]
+    /// Return the value the needle points at.
+    pub fn get_value(&self) -> f32 {
+        self.chart.get_data().value
+    }
+
+    #[inline]
+    /// Set the range of values covered by the dial.
+    pub fn set_range(&mut self, range: Range<f32>) {
+        self.chart.get_data_mut().range = range
+    }
+
+    #[inline]
+    /// Set the range of values covered by the dial. Consumes self.
+    pub fn range(mut self, range: Range<f32>) -> Self {
+        self.set_range(range);
+
+        self
+    }
+
+    /// Add a colored zone along the dial. Zones added later are drawn over earlier ones.
+    pub fn add_zone<T>(&mut self, range: Range<f32>, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        let config = self.chart.get_data_mut();
+
+        let mut zones = config.zones.to_vec();
+        zones.push((range, color.into()));
+
+        config.zones = zones.into();
+    }
+
+    #[inline]
+    /// Add a colored zone along the dial. Consumes self.
+    pub fn zone<T>(mut self, range: Range<f32>, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.add_zone(range, color);
+
+        self
+    }
+
+    #[inline]
+    /// Remove every colored zone from the dial.
+    pub fn clear_zones(&mut self) {
+        self.chart.get_data_mut().zones = Arc::new([]);
+    }
+
+    /// Set the color of the dial where no zone is set.
+    pub fn set_dial_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().dial_color = color.into()
+    }
+
+    #[inline]
+    /// Set the color of the dial where no zone is set. Consumes self.
+    pub fn dial_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_dial_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the needle.
+    pub fn set_needle_style(&mut self, needle_style: ShapeStyle) {
+        self.chart.get_data_mut().needle_style = needle_style
+    }
+
+    #[inline]
+    /// Set the style of the needle. Consumes self.
+    pub fn needle_style(mut self, needle_style: ShapeStyle) -> Self {
+        self.set_needle_style(needle_style);
+
+        self
+    }
+
+    /// Set the text color of the gauge.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the gauge. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    /// Set the caption of the gauge, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the gauge, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    /// Bind the needle to the Y value of a time chart, following it as it is played back. The
+    /// needle keeps following the chart as its points, time mapping, or playback group change.
+    pub fn set_binding(&mut self, chart: &XyTimeData) {
+        self.binding = Some(GaugeBinding {
+            series: chart.shared().clone(),
+        });
+    }
+
+    #[inline]
+    /// Bind the needle to the Y value of a time chart. Consumes self.
+    pub fn bind(mut self, chart: &XyTimeData) -> Self {
+        self.set_binding(chart);
+
+        self
+    }
+
+    #[inline]
+    /// Unbind the needle from its time chart, if any, leaving it at its current value.
+    pub fn unbind(&mut self) {
+        self.binding = None;
+    }
+
+    /// Draw the gauge to a Ui. If bound to a time chart, the needle is first moved to the
+    /// chart's current value.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        if let Some(binding) = &self.binding {
+            let value = binding.current_value();
+
+            self.set_value(value);
+        }
+
+        self.chart.draw(ui);
+    }
+
+    /// Draw the gauge to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        if let Some(binding) = &self.binding {
+            let value = binding.current_value();
+
+            self.set_value(value);
+        }
+
+        self.chart.draw_headless(backend)
+    }
+
+    /// Draw the gauge to a Ui as it would appear at the given time, bypassing the bound chart's
+    /// playback clock. Time is in the same units as the bound chart's points. Unbound gauges
+    /// are drawn as is.
+    pub fn draw_at_time(&mut self, ui: &mut Ui, time: f32) {
+        if let Some(binding) = &self.binding {
+            let value = binding.value_at(time);
+
+            self.set_value(value);
+        }
+
+        self.chart.draw(ui);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charts::{PlaybackGroup, TimeMapping};
+
+    /// Value the gauge's needle would be moved to when next drawn.
+    fn bound_value(gauge: &Gauge) -> f32 {
+        gauge.binding.as_ref().unwrap().current_value()
+    }
+
+    #[test]
+    fn bound_gauge_follows_replaced_points() {
+        let mut chart = XyTimeData::new(&[(0.0, 1.0, 0.0), (1.0, 2.0, 1.0)], "", "", "");
+        let gauge = Gauge::new(0.0..10.0, "").bind(&chart);
+
+        assert_eq!(bound_value(&gauge), 2.0);
+
+        chart.set_points(&[(0.0, 5.0, 0.0), (1.0, 7.0, 1.0), (2.0, 9.0, 2.0)]);
+        assert_eq!(bound_value(&gauge), 9.0);
+
+        chart.set_time(1.5);
+        assert_eq!(bound_value(&gauge), 7.0);
+    }
+
+    #[test]
+    fn bound_gauge_follows_time_mapping_and_group() {
+        let points = [(0.0, 1.0, 0.0), (1.0, 2.0, 100.0), (2.0, 3.0, 101.0)];
+        let mut chart = XyTimeData::new(&points, "", "", "");
+        let gauge = Gauge::new(0.0..10.0, "").bind(&chart);
+
+        chart.set_time_mapping(TimeMapping::SkipGaps { max_gap: 1.0 });
+
+        // Two seconds into the compressed playback is the end of the data
+        let mut group = PlaybackGroup::new();
+
+        chart.set_playback_group(&group);
+        group.set_time(2.0);
+        assert_eq!(bound_value(&gauge), 3.0);
+
+        group.set_time(0.5);
+        assert_eq!(bound_value(&gauge), 1.0);
+    }
+}
@@ -0,0 +1,243 @@
+//! Grid line configuration shared by the premade charts.
+
+use std::ops::Range;
+
+use plotters::{
+    chart::ChartContext,
+    coord::{
+        cartesian::Cartesian2d,
+        ranged1d::{BoldPoints, LightPoints, Ranged},
+    },
+    element::PathElement,
+    style::{
+        full_palette::{GREY, GREY_700},
+        Color, ShapeStyle,
+    },
+};
+use plotters_backend::DrawingBackend;
+
+/// Default maximum number of major grid lines, and labels, per axis.
+pub const DEFAULT_MAJOR_LINES: usize = 10;
+/// Default maximum number of minor grid lines between each major grid line.
+pub const DEFAULT_MINOR_LINES: usize = 10;
+
+#[derive(Debug, Copy, Clone)]
+/// Used to configure the grid lines of a single axis.
+///
+/// ## Usage
+/// GridConfig allows you to change the grid lines drawn for an axis in the following ways:
+///  * `major` & `minor` - Enables/disables the major and minor grid lines independently.
+///  * `major_style` & `minor_style` - Style of the major and minor grid lines.
+///  * `major_lines` - Maximum number of major grid lines. Also controls how many labels are
+///    shown on the axis.
+///  * `minor_lines` - Maximum number of minor grid lines between each major grid line.
+pub struct GridConfig {
+    major: bool,
+    minor: bool,
+    major_style: ShapeStyle,
+    minor_style: ShapeStyle,
+    major_lines: usize,
+    minor_lines: usize,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            major: true,
+            minor: true,
+            major_style: ShapeStyle {
+                color: GREY.to_rgba(),
+                filled: false,
+                stroke_width: 2,
+            },
+            minor_style: ShapeStyle {
+                color: GREY_700.to_rgba(),
+                filled: false,
+                stroke_width: 1,
+            },
+            major_lines: DEFAULT_MAJOR_LINES,
+            minor_lines: DEFAULT_MINOR_LINES,
+        }
+    }
+}
+
+impl GridConfig {
+    #[inline]
+    /// Create a new GridConfig with both major and minor grid lines disabled.
+    pub fn disabled() -> Self {
+        Self {
+            major: false,
+            minor: false,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    /// Enable/disable the major grid lines.
+    pub fn set_major(&mut self, major: bool) {
+        self.major = major
+    }
+
+    #[inline]
+    /// Enable/disable the major grid lines. Consumes self.
+    pub fn major(mut self, major: bool) -> Self {
+        self.set_major(major);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable the minor grid lines.
+    pub fn set_minor(&mut self, minor: bool) {
+        self.minor = minor
+    }
+
+    #[inline]
+    /// Enable/disable the minor grid lines. Consumes self.
+    pub fn minor(mut self, minor: bool) -> Self {
+        self.set_minor(minor);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the major grid lines.
+    pub fn set_major_style(&mut self, major_style: ShapeStyle) {
+        self.major_style = major_style
+    }
+
+    #[inline]
+    /// Set the style of the major grid lines. Consumes self.
+    pub fn major_style(mut self, major_style: ShapeStyle) -> Self {
+        self.set_major_style(major_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the minor grid lines.
+    pub fn set_minor_style(&mut self, minor_style: ShapeStyle) {
+        self.minor_style = minor_style
+    }
+
+    #[inline]
+    /// Set the style of the minor grid lines. Consumes self.
+    pub fn minor_style(mut self, minor_style: ShapeStyle) -> Self {
+        self.set_minor_style(minor_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the maximum number of major grid lines, and labels, on the axis.
+    pub fn set_major_lines(&mut self, major_lines: usize) {
+        self.major_lines = major_lines
+    }
+
+    #[inline]
+    /// Set the maximum number of major grid lines, and labels, on the axis. Consumes self.
+    pub fn major_lines(mut self, major_lines: usize) -> Self {
+        self.set_major_lines(major_lines);
+
+        self
+    }
+
+    #[inline]
+    /// Set the maximum number of minor grid lines between each major grid line.
+    pub fn set_minor_lines(&mut self, minor_lines: usize) {
+        self.minor_lines = minor_lines
+    }
+
+    #[inline]
+    /// Set the maximum number of minor grid lines between each major grid line. Consumes self.
+    pub fn minor_lines(mut self, minor_lines: usize) -> Self {
+        self.set_minor_lines(minor_lines);
+
+        self
+    }
+
+    #[inline]
+    /// Get the maximum number of major grid lines, and labels, on the axis.
+    pub fn get_major_lines(&self) -> usize {
+        self.major_lines
+    }
+}
+
+/// Draw the grid lines of both axes onto a chart. Minor lines are drawn first so the major lines
+/// sit on top of them.
+pub(crate) fn draw_grid<DB, X, Y>(
+    chart: &mut ChartContext<DB, Cartesian2d<X, Y>>,
+    x_grid: &GridConfig,
+    y_grid: &GridConfig,
+) where
+    DB: DrawingBackend,
+    X: Ranged<ValueType = f32> + Clone,
+    Y: Ranged<ValueType = f32> + Clone,
+{
+    let Range {
+        start: x_start,
+        end: x_end,
+    } = chart.x_range();
+    let Range {
+        start: y_start,
+        end: y_end,
+    } = chart.y_range();
+
+    let (x_spec, y_spec) = {
+        let spec = chart.as_coord_spec();
+
+        (spec.x_spec().clone(), spec.y_spec().clone())
+    };
+
+    let x_lines = |points: Vec<f32>, style: ShapeStyle| {
+        points
+            .into_iter()
+            .map(move |x| PathElement::new(vec![(x, y_start), (x, y_end)], style))
+    };
+
+    let y_lines = |points: Vec<f32>, style: ShapeStyle| {
+        points
+            .into_iter()
+            .map(move |y| PathElement::new(vec![(x_start, y), (x_end, y)], style))
+    };
+
+    if x_grid.minor {
+        let points = minor_points(&x_spec, x_grid);
+
+        chart
+            .draw_series(x_lines(points, x_grid.minor_style))
+            .unwrap();
+    }
+
+    if y_grid.minor {
+        let points = minor_points(&y_spec, y_grid);
+
+        chart
+            .draw_series(y_lines(points, y_grid.minor_style))
+            .unwrap();
+    }
+
+    if x_grid.major {
+        let points = x_spec.key_points(BoldPoints(x_grid.major_lines));
+
+        chart
+            .draw_series(x_lines(points, x_grid.major_style))
+            .unwrap();
+    }
+
+    if y_grid.major {
+        let points = y_spec.key_points(BoldPoints(y_grid.major_lines));
+
+        chart
+            .draw_series(y_lines(points, y_grid.major_style))
+            .unwrap();
+    }
+}
+
+/// Key points of the minor grid lines of an axis.
+fn minor_points<R: Ranged>(spec: &R, grid: &GridConfig) -> Vec<R::ValueType> {
+    spec.key_points(LightPoints::new(
+        grid.major_lines,
+        grid.major_lines * grid.minor_lines,
+    ))
+}
@@ -0,0 +1,89 @@
+//! Helpers for charts that react to the pointer hovering over them.
+
+use egui::Ui;
+use plotters::{
+    coord::Shift,
+    element::{Rectangle, Text},
+    prelude::DrawingArea,
+    style::{full_palette::GREY_300, Color, TextStyle, WHITE},
+};
+
+use crate::{EguiBackend, Transform};
+
+const TOOLTIP_PADDING: i32 = 4;
+const TOOLTIP_OPACITY: f64 = 0.9;
+
+/// Position of the pointer relative to the Ui's max rect, if it is hovering over it.
+pub(crate) fn hover_pos(ui: &Ui) -> Option<(f32, f32)> {
+    let rect = ui.max_rect();
+
+    ui.ctx()
+        .pointer_hover_pos()
+        .filter(|pos| rect.contains(*pos) && ui.ui_contains_pointer())
+        .map(|pos| (pos.x - rect.min.x, pos.y - rect.min.y))
+}
+
+/// Undo the scale and offset the chart's transform applies, turning a position relative to the
+/// Ui into a pixel of the drawing area.
+pub(crate) fn untransform(
+    area: &DrawingArea<EguiBackend, Shift>,
+    transform: &Transform,
+    pos: (f32, f32),
+) -> (i32, i32) {
+    let (x, y) = pos;
+    let (width, height) = area.dim_in_pixel();
+    let (center_x, center_y) = (width as f32 / 2.0, height as f32 / 2.0);
+    let scale = transform.scale as f32;
+
+    let x = (x - transform.x as f32 - center_x) / scale + center_x;
+    let y = (y - transform.y as f32 - center_y) / scale + center_y;
+
+    (x as i32, y as i32)
+}
+
+/// Draw a box of text lines with its top left corner at the given pixel, moved left if needed
+/// to keep it inside the drawing area.
+pub(crate) fn draw_tooltip(
+    area: &DrawingArea<EguiBackend, Shift>,
+    pos: (i32, i32),
+    lines: Vec<String>,
+    text_style: &TextStyle,
+) {
+    let font_size = text_style.font.get_size() as i32;
+    let line_height = font_size + 2;
+
+    // Monospace characters are roughly 3/5 as wide as they are tall
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count() as i32)
+        .max()
+        .unwrap_or(0)
+        * font_size
+        * 3
+        / 5
+        + TOOLTIP_PADDING * 2;
+    let height = lines.len() as i32 * line_height + TOOLTIP_PADDING * 2;
+
+    let (x, y) = pos;
+    let (area_width, _) = area.dim_in_pixel();
+
+    let x = x.min(area_width as i32 - width).max(0);
+
+    area.draw(&Rectangle::new(
+        [(x, y), (x + width, y + height)],
+        WHITE.mix(TOOLTIP_OPACITY).filled(),
+    ))
+    .unwrap();
+    area.draw(&Rectangle::new([(x, y), (x + width, y + height)], GREY_300))
+        .unwrap();
+
+    for (index, line) in lines.into_iter().enumerate() {
+        let position = (
+            x + TOOLTIP_PADDING,
+            y + TOOLTIP_PADDING + index as i32 * line_height,
+        );
+
+        area.draw(&Text::new(line, position, text_style.clone()))
+            .unwrap();
+    }
+}
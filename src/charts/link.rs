@@ -0,0 +1,86 @@
+//! Hover and selection highlights shared between linked charts.
+
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Debug, Default)]
+/// Keys hovered and selected in a link group.
+pub(crate) struct Link {
+    /// Key hovered in one of the charts, along with the member hovering it.
+    hovered: Option<(usize, f32)>,
+    selected: Option<f32>,
+    /// Number of charts that have joined, used to tell them apart.
+    members: usize,
+}
+
+/// Group of charts sharing hover and selection highlights, brushing a key across all of them.
+///
+/// ## Usage
+/// **Ensure the `timechart` feature is enabled to use this type.**
+///
+/// Charts join a group with `.set_link_group()`. The key is the time of the points, so hovering
+/// over a point of one chart in the group highlights the points shown at the same time in every
+/// other chart, and clicking it selects that time for all of them. Time charts highlight the point
+/// nearest the key, while timelines draw a line across at it.
+///
+/// Cloning a group returns another handle to the same group.
+#[derive(Clone, Default)]
+pub struct LinkGroup {
+    link: Rc<RefCell<Link>>,
+}
+
+impl LinkGroup {
+    #[inline]
+    /// Create a new, empty link group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Join the group, returning the member the chart is known as.
+    pub(crate) fn join(&self) -> usize {
+        let mut link = self.link.borrow_mut();
+
+        link.members += 1;
+
+        link.members
+    }
+
+    /// Set the key hovered by a member, or clear it with None if that member was hovering it.
+    pub(crate) fn hover(&self, member: usize, key: Option<f32>) {
+        let mut link = self.link.borrow_mut();
+
+        match key {
+            Some(key) => link.hovered = Some((member, key)),
+            None => {
+                if link.hovered.is_some_and(|(hovering, _)| hovering == member) {
+                    link.hovered = None;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    /// Get the key hovered in any chart of the group.
+    pub fn get_hovered(&self) -> Option<f32> {
+        self.link.borrow().hovered.map(|(_, key)| key)
+    }
+
+    #[inline]
+    /// Select a key in every chart of the group, or clear the selection with None.
+    pub fn set_selected(&mut self, key: Option<f32>) {
+        self.link.borrow_mut().selected = key
+    }
+
+    #[inline]
+    /// Select a key in every chart of the group. Consumes self.
+    pub fn selected(mut self, key: Option<f32>) -> Self {
+        self.set_selected(key);
+
+        self
+    }
+
+    #[inline]
+    /// Get the key selected in the group.
+    pub fn get_selected(&self) -> Option<f32> {
+        self.link.borrow().selected
+    }
+}
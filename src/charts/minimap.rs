@@ -0,0 +1,191 @@
+//! Overview strip showing the full extent of a chart's data, with a draggable window marking the
+//! part currently in view.
+
+use std::ops::Range;
+
+use egui::{CursorIcon, Id, Sense, Ui};
+use plotters::{
+    prelude::{ChartBuilder, IntoDrawingArea, PathElement, Rectangle},
+    series::LineSeries,
+    style::{full_palette::GREY_700, Color, ShapeStyle, BLACK, WHITE},
+};
+
+use crate::{charts::decimate::decimate, EguiBackend};
+
+/// Default height of the strip, in pixels.
+pub(crate) const DEFAULT_MINIMAP_HEIGHT: f32 = 48.0;
+/// Space between the chart and the strip, in pixels.
+pub(crate) const MINIMAP_SPACING: f32 = 4.0;
+/// Distance from the edges of the window, in pixels, that grabs the edge instead of the window.
+const HANDLE_WIDTH: f32 = 6.0;
+const WINDOW_OPACITY: f64 = 0.2;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Part of the window being dragged.
+enum Grab {
+    Window,
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Minimap {
+    /// Id of the chart the strip belongs to.
+    id: Id,
+    grab: Option<Grab>,
+    /// X values marked with a line across the strip.
+    markers: Vec<f32>,
+}
+
+impl Minimap {
+    #[inline]
+    /// Create a strip for the chart with the given id.
+    pub(crate) fn new(id: Id) -> Self {
+        Self {
+            id,
+            grab: None,
+            markers: Vec::new(),
+        }
+    }
+
+    #[inline]
+    /// Set the X values marked with a line across the strip.
+    pub(crate) fn set_markers(&mut self, markers: Vec<f32>) {
+        self.markers = markers
+    }
+
+    /// Draw the strip to the Ui, with the window over the X range in view. Returns the new X
+    /// range of the view if the user moved or resized the window. Points are only decimated if
+    /// they are monotonic.
+    pub(crate) fn draw(
+        &mut self,
+        ui: &mut Ui,
+        points: &[(f32, f32)],
+        monotonic: bool,
+        extent: (Range<f32>, Range<f32>),
+        view: Range<f32>,
+        line_style: ShapeStyle,
+    ) -> Option<Range<f32>> {
+        let rect = ui.max_rect();
+        let (x_extent, y_extent) = extent;
+        let span = x_extent.end - x_extent.start;
+
+        if span.is_nan() || span <= 0.0 || rect.width() <= 0.0 {
+            return None;
+        }
+
+        let to_pixel = |x: f32| rect.left() + (x - x_extent.start) / span * rect.width();
+        let to_data = |px: f32| x_extent.start + (px - rect.left()) / rect.width() * span;
+
+        let response = ui.interact(rect, self.id.with("minimap"), Sense::drag());
+
+        let (start, end) = (to_pixel(view.start), to_pixel(view.end));
+        let grab_at = |px: f32| match px {
+            px if (px - start).abs() <= HANDLE_WIDTH => Some(Grab::Start),
+            px if (px - end).abs() <= HANDLE_WIDTH => Some(Grab::End),
+            px if px > start && px < end => Some(Grab::Window),
+            _ => None,
+        };
+
+        let hovered = response.hover_pos().and_then(|pos| grab_at(pos.x));
+
+        if matches!(hovered, Some(Grab::Start | Grab::End))
+            || matches!(self.grab, Some(Grab::Start | Grab::End))
+        {
+            ui.ctx().set_cursor_icon(CursorIcon::ResizeHorizontal);
+        }
+
+        let mut new_view = None;
+
+        if response.drag_started() {
+            let pos = response.interact_pointer_pos();
+
+            self.grab = pos.map(|pos| match grab_at(pos.x) {
+                Some(grab) => grab,
+                None => {
+                    // Jump the window to where the strip was grabbed
+                    let center = to_data(pos.x);
+                    let half = (view.end - view.start) / 2.0;
+
+                    new_view = Some((center - half)..(center + half));
+
+                    Grab::Window
+                }
+            });
+        }
+
+        if response.dragged() {
+            let delta = response.drag_delta().x / rect.width() * span;
+            let view = new_view.clone().unwrap_or(view.clone());
+            // Keep the window at least a handle wide so it can still be grabbed
+            let min_width = HANDLE_WIDTH * 2.0 / rect.width() * span;
+
+            new_view = match self.grab {
+                Some(Grab::Window) if delta != 0.0 => {
+                    Some((view.start + delta)..(view.end + delta))
+                }
+                Some(Grab::Start) if delta != 0.0 => {
+                    Some((view.start + delta).min(view.end - min_width)..view.end)
+                }
+                Some(Grab::End) if delta != 0.0 => {
+                    Some(view.start..(view.end + delta).max(view.start + min_width))
+                }
+                _ => new_view,
+            };
+        }
+
+        if response.drag_stopped() {
+            self.grab = None;
+        }
+
+        let view = new_view.clone().unwrap_or(view);
+
+        let root = EguiBackend::new(ui).into_drawing_area();
+
+        root.fill(&WHITE).unwrap();
+
+        let mut chart = ChartBuilder::on(&root)
+            .build_cartesian_2d(x_extent.clone(), y_extent.clone())
+            .unwrap();
+
+        // Long recordings are drawn one column of points per pixel
+        let columns = rect.width() as usize;
+        let line: Vec<(f32, f32)> = match monotonic && points.len() > columns * 2 {
+            true => decimate(points, x_extent.clone(), columns)
+                .line()
+                .into_iter()
+                .map(|index| points[index])
+                .collect(),
+            false => points.to_vec(),
+        };
+
+        chart
+            .draw_series(LineSeries::new(line, line_style.stroke_width(1)))
+            .unwrap();
+
+        let markers = self.markers.iter().map(|x| {
+            PathElement::new(
+                vec![(*x, y_extent.start), (*x, y_extent.end)],
+                BLACK.stroke_width(1),
+            )
+        });
+
+        chart.draw_series(markers).unwrap();
+
+        let window = [
+            (view.start.max(x_extent.start), y_extent.start),
+            (view.end.min(x_extent.end), y_extent.end),
+        ];
+
+        chart
+            .draw_series([
+                Rectangle::new(window, GREY_700.mix(WINDOW_OPACITY).filled()),
+                Rectangle::new(window, GREY_700.stroke_width(1)),
+            ])
+            .unwrap();
+
+        root.present().unwrap();
+
+        new_view
+    }
+}
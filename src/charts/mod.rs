@@ -1,11 +1,70 @@
 //! Various type of premade charts.
 
+#[cfg(feature = "timechart")]
+mod axis;
+#[cfg(feature = "timechart")]
+mod axisbreak;
+mod bar;
+#[cfg(feature = "timechart")]
+mod clipping;
+mod comparison;
+mod contour;
+#[cfg(feature = "timechart")]
+mod decimate;
+#[cfg(feature = "timechart")]
+mod gauge;
+mod grid;
+mod hover;
+#[cfg(feature = "timechart")]
+mod link;
+#[cfg(feature = "timechart")]
+mod minimap;
+#[cfg(feature = "timechart")]
+mod playback;
+mod quiver;
+#[cfg(feature = "timechart")]
+mod sparkline;
+#[cfg(feature = "timechart")]
+mod table;
+mod textfit;
 #[cfg(feature = "timechart")]
 mod timedata;
 #[cfg(feature = "timechart")]
+mod timeindex;
+#[cfg(feature = "timechart")]
+mod timeline;
+#[cfg(feature = "timechart")]
+mod timemap;
+mod units;
+mod waterfall;
+#[cfg(feature = "timechart")]
 mod xytime;
 
+#[cfg(feature = "timechart")]
+pub use axis::{AutoscaleMode, XAxisPosition, YAxisPosition};
+#[cfg(feature = "timechart")]
+pub(crate) use axisbreak::AxisBreak;
+pub use bar::{BarChart, BarLayout, SeriesEvent};
+pub use comparison::{Comparison, ComparisonMode};
+pub use contour::Contour;
+#[cfg(feature = "timechart")]
+pub use gauge::Gauge;
+pub use grid::{GridConfig, DEFAULT_MAJOR_LINES, DEFAULT_MINOR_LINES};
+#[cfg(feature = "timechart")]
+pub use link::LinkGroup;
+#[cfg(feature = "timechart")]
+pub use playback::{PlaybackError, PlaybackGroup};
+pub use quiver::Quiver;
+#[cfg(feature = "timechart")]
+pub use sparkline::Sparkline;
+#[cfg(feature = "timechart")]
+pub use table::DataTableMode;
 #[cfg(feature = "timechart")]
 pub use timedata::TimeData;
 #[cfg(feature = "timechart")]
-pub use xytime::XyTimeData;
+pub use timeline::Timeline;
+#[cfg(feature = "timechart")]
+pub use timemap::TimeMapping;
+pub use waterfall::Waterfall;
+#[cfg(feature = "timechart")]
+pub use xytime::{TimeChartState, XyTimeData};
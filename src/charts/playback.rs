@@ -0,0 +1,559 @@
+//! Playback state shared between animatable charts.
+
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    rc::{Rc, Weak},
+};
+
+use instant::Instant;
+
+const MIN_DELTA: f32 = 0.000_010;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Reason playback couldn't be started.
+pub enum PlaybackError {
+    /// There is nothing to animate, as every point is at the same time or there are no points.
+    /// The chart is shown statically instead.
+    ZeroDuration,
+}
+
+impl Display for PlaybackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ZeroDuration => write!(f, "playback has zero duration"),
+        }
+    }
+}
+
+impl Error for PlaybackError {}
+
+#[derive(Debug, Copy, Clone, Default)]
+/// Where a chart sits on the playback clock it uses.
+struct Timing {
+    /// Time of the chart's first point, used to align it with the other members of a group.
+    start: f32,
+    /// Seconds of playback it takes to play every point of the chart.
+    duration: f32,
+    /// Seconds of playback before the chart starts, or skipped at its start if negative.
+    offset: f32,
+}
+
+/// Timing of a chart on the playback clock it uses, shared with the clock so a playback group
+/// can align its members.
+#[derive(Clone, Default)]
+pub(crate) struct Member {
+    timing: Rc<Cell<Timing>>,
+}
+
+impl Member {
+    #[inline]
+    pub(crate) fn new(start: f32, duration: f32) -> Self {
+        let member = Self::default();
+
+        member.set_extent(start, duration);
+
+        member
+    }
+
+    #[inline]
+    /// Set the time of the chart's first point and the seconds it takes to play every point.
+    pub(crate) fn set_extent(&self, start: f32, duration: f32) {
+        let timing = self.timing.get();
+
+        self.timing.set(Timing {
+            start,
+            duration,
+            ..timing
+        })
+    }
+
+    #[inline]
+    pub(crate) fn offset(&self) -> f32 {
+        self.timing.get().offset
+    }
+
+    #[inline]
+    pub(crate) fn set_offset(&self, offset: f32) {
+        let timing = self.timing.get();
+
+        self.timing.set(Timing { offset, ..timing })
+    }
+
+    #[inline]
+    /// Seconds of playback the chart takes to finish, including its offset.
+    pub(crate) fn span(&self) -> f32 {
+        let timing = self.timing.get();
+
+        (timing.offset + timing.duration).max(0.0)
+    }
+
+    #[inline]
+    /// Seconds the chart has played, given the seconds played by its clock.
+    pub(crate) fn elapsed(&self, elapsed: f32) -> f32 {
+        (elapsed - self.offset()).max(MIN_DELTA)
+    }
+
+    #[inline]
+    /// Seconds the clock has to have played for the chart to have played the given seconds.
+    pub(crate) fn clock_time(&self, time: f32) -> f32 {
+        time + self.offset()
+    }
+
+    #[inline]
+    /// Copy of the timing no longer shared with the clock, for leaving a playback group.
+    pub(crate) fn detach(&self) -> Self {
+        Self {
+            timing: Rc::new(Cell::new(self.timing.get())),
+        }
+    }
+}
+
+/// Playback clock of an animatable chart. Times are in seconds since the start of the
+/// animation.
+///
+/// The seconds played are accumulated whenever the clock is paused, seeked, or changes speed,
+/// so only the time since the last of those is scaled by the current speed.
+pub(crate) struct Playback {
+    /// Seconds played up to when playback last resumed, or in total while paused.
+    played: f32,
+    /// When playback last resumed. None while paused or stopped.
+    resumed: Option<Instant>,
+    /// Set while playback is underway, even if paused.
+    started: bool,
+    speed: f32,
+    /// Length of the animation in seconds. Playback stops once it is reached.
+    duration: f32,
+    /// Longest animation of the charts using the clock without a member, such as sparklines,
+    /// which the clock is never fitted shorter than.
+    min_duration: f32,
+    /// Set once playback has stopped by reaching the end, so every chart sharing the clock
+    /// still gets to show the final frame.
+    finished: bool,
+    /// Timing of every time chart using the clock, whether its own or a playback group's. Charts
+    /// that have since left or been dropped are pruned when the clock is fitted or aligned.
+    members: Vec<Weak<Cell<Timing>>>,
+}
+
+impl Playback {
+    #[inline]
+    pub(crate) fn new(duration: f32) -> Self {
+        Self {
+            played: 0.0,
+            resumed: None,
+            started: false,
+            speed: 1.0,
+            duration,
+            min_duration: duration,
+            finished: false,
+            members: Vec::new(),
+        }
+    }
+
+    #[inline]
+    /// Start playback from the beginning. Does nothing without a duration to play.
+    pub(crate) fn start(&mut self) {
+        if !self.has_duration() {
+            return;
+        }
+
+        self.played = 0.0;
+        self.resumed = Some(Instant::now());
+        self.started = true;
+        self.finished = false;
+    }
+
+    #[inline]
+    /// Start playback from the beginning, or return why it can't be.
+    pub(crate) fn try_start(&mut self) -> Result<(), PlaybackError> {
+        if !self.has_duration() {
+            return Err(PlaybackError::ZeroDuration);
+        }
+
+        self.start();
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn stop(&mut self) {
+        self.played = 0.0;
+        self.resumed = None;
+        self.started = false;
+        self.finished = false;
+    }
+
+    pub(crate) fn toggle(&mut self) {
+        match (self.started, self.resumed) {
+            (true, Some(_)) => {
+                self.played = self.position();
+                self.resumed = None;
+            }
+            (true, None) => self.resumed = Some(Instant::now()),
+            (false, _) => self.start(),
+        }
+    }
+
+    /// Seconds played so far, without stopping playback at the end.
+    fn position(&self) -> f32 {
+        match (self.started, self.resumed) {
+            (true, Some(resumed)) => {
+                self.played + self.speed * Instant::now().duration_since(resumed).as_secs_f32()
+            }
+            (true, None) => self.played,
+            (false, _) => match self.finished {
+                true => self.duration,
+                false => 0.0,
+            },
+        }
+    }
+
+    /// Seek to a time, continuing to play from it if playing. Playback is started paused at the
+    /// time if it wasn't underway.
+    pub(crate) fn set_time(&mut self, time: f32) {
+        self.played = time.max(0.0);
+        self.finished = false;
+
+        match self.started && self.resumed.is_some() {
+            true => self.resumed = Some(Instant::now()),
+            false => {
+                self.started = true;
+                self.resumed = None;
+            }
+        }
+    }
+
+    #[inline]
+    /// Seek forward, or backward if negative, from the time played so far.
+    pub(crate) fn seek_relative(&mut self, delta: f32) {
+        let time = (self.position() + delta).min(self.duration);
+
+        self.set_time(time)
+    }
+
+    pub(crate) fn set_speed(&mut self, speed: f32) {
+        // Bank the seconds played at the old speed before changing it
+        if self.resumed.is_some() {
+            self.played = self.position();
+            self.resumed = Some(Instant::now());
+        }
+
+        self.speed = speed;
+    }
+
+    #[inline]
+    pub(crate) fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    #[inline]
+    pub(crate) fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    #[inline]
+    /// Extend the clock to fit a chart using it without a member, which it stays long enough
+    /// for from then on.
+    pub(crate) fn extend_duration(&mut self, duration: f32) {
+        self.min_duration = self.min_duration.max(duration);
+        self.duration = self.duration.max(duration);
+    }
+
+    /// Add a chart to the members of the clock, fitting the clock to it.
+    pub(crate) fn join(&mut self, member: &Member) {
+        self.members.push(Rc::downgrade(&member.timing));
+
+        self.fit_duration();
+    }
+
+    /// Every member still using the clock, pruning the ones that have left or been dropped.
+    fn live_members(&mut self) -> Vec<Member> {
+        self.members.retain(|member| member.strong_count() > 0);
+
+        self.members
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|timing| Member { timing })
+            .collect()
+    }
+
+    /// Fit the clock to the longest of its members, including their offsets, so it shrinks as
+    /// well as grows when their timing changes.
+    pub(crate) fn fit_duration(&mut self) {
+        let longest = self
+            .live_members()
+            .iter()
+            .map(Member::span)
+            .fold(self.min_duration, f32::max);
+
+        self.duration = longest;
+    }
+
+    /// Offset every member by how much later its first point is than the earliest first point
+    /// of any member, fitting the clock to them.
+    fn align_members(&mut self) {
+        let members = self.live_members();
+
+        let earliest = members
+            .iter()
+            .map(|member| member.timing.get().start)
+            .fold(f32::INFINITY, f32::min);
+
+        for member in members {
+            member.set_offset(member.timing.get().start - earliest);
+        }
+
+        self.fit_duration();
+    }
+
+    #[inline]
+    /// Return true if there is anything to play. Clocks without a duration stay inactive, so
+    /// their charts are shown statically.
+    fn has_duration(&self) -> bool {
+        self.duration > 0.0
+    }
+
+    #[inline]
+    /// Return true if playback is underway, paused, or has finished by reaching the end.
+    pub(crate) fn is_active(&self) -> bool {
+        (self.started || self.finished) && self.has_duration()
+    }
+
+    #[inline]
+    pub(crate) fn is_playing(&self) -> bool {
+        self.started && self.resumed.is_some() && self.has_duration()
+    }
+
+    /// Return the seconds played so far, or None if playback is disabled or there is nothing to
+    /// play. Stops playback once the end of the animation is reached.
+    pub(crate) fn elapsed(&mut self) -> Option<f32> {
+        let elapsed = self.peek_elapsed()?;
+
+        if self.started && elapsed >= self.duration {
+            self.stop();
+            self.finished = true;
+        }
+
+        Some(elapsed)
+    }
+
+    /// Return the seconds played so far like `elapsed`, without stopping playback at the end.
+    pub(crate) fn peek_elapsed(&self) -> Option<f32> {
+        if !self.has_duration() {
+            return None;
+        }
+
+        if !self.started {
+            return self.finished.then_some(self.duration);
+        }
+
+        // Ensure deltas are over 10us, otherwise they can cause overflows
+        // in the plotters library
+        Some((MIN_DELTA + self.position()).min(self.duration))
+    }
+}
+
+/// Group of animatable charts played back in lock-step.
+///
+/// ## Usage
+/// **Ensure the `timechart` feature is enabled to use this type.**
+///
+/// Charts join a group with `.set_playback_group()`, after which they all share
+/// a single playback clock. Starting, pausing, seeking, or changing the speed of the
+/// group or of any chart in it applies to every member at once. Playback stops once
+/// the longest member has finished animating.
+///
+/// Time charts can be delayed behind the rest of the group with `.set_playback_offset()`, or
+/// offset by when their recordings started with `.align_starts()`.
+///
+/// Cloning a group returns another handle to the same group.
+#[derive(Clone)]
+pub struct PlaybackGroup {
+    playback: Rc<RefCell<Playback>>,
+}
+
+impl Default for PlaybackGroup {
+    fn default() -> Self {
+        Self {
+            playback: Rc::new(RefCell::new(Playback::new(0.0))),
+        }
+    }
+}
+
+impl PlaybackGroup {
+    #[inline]
+    /// Create a new, empty playback group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Clock shared by every member of the group.
+    pub(crate) fn playback(&self) -> &Rc<RefCell<Playback>> {
+        &self.playback
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds since the start of playback.
+    pub fn set_time(&mut self, time: f32) {
+        self.playback.borrow_mut().set_time(time)
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds since the start of playback.
+    /// Consumes self.
+    pub fn time(mut self, time: f32) -> Self {
+        self.set_time(time);
+
+        self
+    }
+
+    #[inline]
+    /// Move playback forward, or backward if negative, by some seconds from the current time.
+    pub fn seek_relative(&mut self, delta: f32) {
+        self.playback.borrow_mut().seek_relative(delta)
+    }
+
+    #[inline]
+    /// Offset every time chart in the group by how much later its first point is than the
+    /// earliest first point of any of them, so recordings started at different times animate on
+    /// a common timeline. Offsets are in seconds of playback, so the gaps between the starts are
+    /// played at normal speed whatever the time mapping of the charts. Replaces any offsets set
+    /// with `.set_playback_offset()`, and only applies to the charts that have already joined.
+    pub fn align_starts(&mut self) {
+        self.playback.borrow_mut().align_members()
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half.
+    pub fn set_playback_speed(&mut self, speed: f32) {
+        self.playback.borrow_mut().set_speed(speed)
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Consumes self.
+    pub fn playback_speed(mut self, speed: f32) -> Self {
+        self.set_playback_speed(speed);
+
+        self
+    }
+
+    #[inline]
+    /// Start/enable playback of the group. Does nothing if the group has nothing to animate.
+    pub fn start_playback(&mut self) {
+        self.playback.borrow_mut().start()
+    }
+
+    #[inline]
+    /// Start/enable playback of the group, returning [`PlaybackError::ZeroDuration`] if the
+    /// group has nothing to animate.
+    pub fn try_start_playback(&mut self) -> Result<(), PlaybackError> {
+        self.playback.borrow_mut().try_start()
+    }
+
+    #[inline]
+    /// Stop/disable playback of the group.
+    pub fn stop_playback(&mut self) {
+        self.playback.borrow_mut().stop()
+    }
+
+    #[inline]
+    /// Toggle playback of the group.
+    pub fn toggle_playback(&mut self) {
+        self.playback.borrow_mut().toggle()
+    }
+
+    #[inline]
+    /// Return true if playback is currently enabled & underway.
+    pub fn is_playing(&self) -> bool {
+        self.playback.borrow().is_playing()
+    }
+
+    #[inline]
+    /// Return the seconds played so far when playback is enabled.
+    pub fn current_time(&mut self) -> f32 {
+        self.playback.borrow_mut().elapsed().unwrap_or(0.0)
+    }
+
+    #[inline]
+    /// Return the length of the longest member's animation in seconds.
+    pub fn duration(&self) -> f32 {
+        self.playback.borrow().duration()
+    }
+
+    #[inline]
+    /// Return the speed the group is animated at.
+    pub fn get_playback_speed(&self) -> f32 {
+        self.playback.borrow().speed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_fits_the_longest_member() {
+        let mut playback = Playback::new(0.0);
+        let short = Member::new(0.0, 2.0);
+        let long = Member::new(0.0, 5.0);
+
+        playback.join(&short);
+        playback.join(&long);
+        assert_eq!(playback.duration(), 5.0);
+
+        // Shrinks as well as grows with the members, as when their time mapping changes
+        long.set_extent(0.0, 1.0);
+        playback.fit_duration();
+        assert_eq!(playback.duration(), 2.0);
+
+        short.set_offset(4.0);
+        playback.fit_duration();
+        assert_eq!(playback.duration(), 6.0);
+
+        drop(short);
+        playback.fit_duration();
+        assert_eq!(playback.duration(), 1.0);
+    }
+
+    #[test]
+    fn clock_stays_long_enough_for_charts_without_members() {
+        let mut playback = Playback::new(0.0);
+        let member = Member::new(0.0, 5.0);
+
+        playback.join(&member);
+        playback.extend_duration(3.0);
+
+        member.set_extent(0.0, 1.0);
+        playback.fit_duration();
+        assert_eq!(playback.duration(), 3.0);
+    }
+
+    #[test]
+    fn aligning_offsets_members_by_their_start() {
+        let mut playback = Playback::new(0.0);
+        let members = [
+            Member::new(12.0, 1.0),
+            Member::new(10.0, 5.0),
+            Member::new(15.0, 2.0),
+        ];
+
+        for member in &members {
+            playback.join(member);
+        }
+
+        members[0].set_offset(-1.0);
+        playback.align_members();
+
+        let offsets: Vec<f32> = members.iter().map(Member::offset).collect();
+
+        assert_eq!(offsets, [2.0, 0.0, 5.0]);
+        assert_eq!(playback.duration(), 7.0);
+
+        // Members play from their offset on
+        assert_eq!(members[2].elapsed(6.0), 1.0);
+        assert_eq!(members[2].clock_time(1.0), 6.0);
+        assert_eq!(members[2].elapsed(1.0), MIN_DELTA);
+    }
+}
@@ -0,0 +1,459 @@
+//! Vector field chart drawing an arrow for every vector.
+
+use std::sync::Arc;
+
+use egui::Ui;
+use plotters::{
+    element::{PathElement, Polygon},
+    prelude::{ChartBuilder, LabelAreaPosition},
+    style::{full_palette::GREY_700, Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK},
+};
+use plotters_backend::{DrawingBackend, DrawingErrorKind, FontFamily, FontStyle};
+
+use crate::{
+    charts::textfit::TextFit,
+    colormap::{Colormap, VIRIDIS},
+    locale::locale,
+    Chart,
+};
+
+const DEFAULT_MARGIN: i32 = 25;
+const DEFAULT_LABEL_AREA: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+/// Length of the longest arrow when scaled automatically, relative to the spacing of the arrows.
+const AUTO_SCALE_LENGTH: f32 = 0.9;
+/// Length of the arrow heads in pixels.
+const HEAD_LENGTH: f32 = 6.0;
+/// Largest portion of an arrow taken up by its head.
+const MAX_HEAD_RATIO: f32 = 0.5;
+/// Padding around the arrows, relative to the range they cover.
+const RANGE_PADDING: f32 = 0.05;
+
+/// Arrow of a vector, as its tail, tip, and magnitude.
+type Arrow = ((f32, f32), (f32, f32), f32);
+
+#[derive(Clone)]
+struct QuiverConfig {
+    /// Vectors to be plotted, as their X, Y, U, and V components.
+    vectors: Arc<[(f32, f32, f32, f32)]>,
+    /// Factor the vectors are scaled by. Scaled to fit the spacing of the arrows if None.
+    scale: Option<f32>,
+    /// Draw every arrow at the same length, only showing the magnitude through color.
+    normalize: bool,
+    /// Colormap the arrows are colored with, by magnitude.
+    colormap: Arc<dyn Colormap>,
+    /// Style of the arrows, colored with the colormap if None.
+    arrow_style: Option<ShapeStyle>,
+    /// Style of the axes.
+    axes_style: ShapeStyle,
+    /// Style of the text
+    text_color: RGBAColor,
+    /// Unit of the X axis.
+    x_unit: Arc<str>,
+    /// Unit of the Y axis.
+    y_unit: Arc<str>,
+    /// Caption of the chart. Hidden if None.
+    caption: Option<Arc<str>>,
+    /// Caption and axis descriptions shortened to fit the chart.
+    text_fit: TextFit,
+}
+
+impl QuiverConfig {
+    /// Every arrow as its tail, tip, and magnitude, after scaling.
+    fn arrows(&self) -> Vec<Arrow> {
+        let magnitudes: Vec<f32> = self
+            .vectors
+            .iter()
+            .map(|(_, _, u, v)| u.hypot(*v))
+            .collect();
+
+        let scale = self.scale.unwrap_or_else(|| {
+            let (mut min_x, mut min_y, mut max_x, mut max_y) =
+                (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+
+            for (x, y, _, _) in self.vectors.iter() {
+                min_x = min_x.min(*x);
+                min_y = min_y.min(*y);
+                max_x = max_x.max(*x);
+                max_y = max_y.max(*y);
+            }
+
+            // Treat the arrows as if they were evenly spread over the area they cover
+            let area = (max_x - min_x).max(f32::EPSILON) * (max_y - min_y).max(f32::EPSILON);
+            let spacing = (area / self.vectors.len().max(1) as f32).sqrt();
+
+            let longest = match self.normalize {
+                true => 1.0,
+                false => magnitudes.iter().copied().fold(0.0, f32::max),
+            };
+
+            match longest > 0.0 {
+                true => spacing * AUTO_SCALE_LENGTH / longest,
+                false => 1.0,
+            }
+        });
+
+        self.vectors
+            .iter()
+            .zip(magnitudes)
+            .map(|((x, y, u, v), magnitude)| {
+                let (u, v) = match (self.normalize, magnitude > 0.0) {
+                    (true, true) => (u / magnitude, v / magnitude),
+                    (true, false) => (0.0, 0.0),
+                    (false, _) => (*u, *v),
+                };
+
+                ((*x, *y), (x + u * scale, y + v * scale), magnitude)
+            })
+            .collect()
+    }
+}
+
+/// Vector field chart.
+///
+/// ## Usage
+/// Creating the chart only requires the vectors and a caption.
+///
+///  * `vectors`: A slice of tuples, arranged so that the first two floats are the X and Y
+///    position of the vector and the last two are its U and V components.
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// By default the arrows are scaled so the longest one roughly fits between neighbouring arrows,
+/// which can be overridden with `.set_arrow_scale()`. Arrows are colored by their magnitude
+/// using a [`Colormap`], viridis by default, and can be drawn at a uniform length with
+/// `.set_normalize()` so only their color shows the magnitude.
+pub struct Quiver {
+    chart: Chart<QuiverConfig>,
+}
+
+impl Quiver {
+    /// Create a new Quiver chart. See [Usage](#usage).
+    pub fn new(vectors: &[(f32, f32, f32, f32)], caption: &str) -> Self {
+        let config = QuiverConfig {
+            vectors: vectors.into(),
+            scale: None,
+            normalize: false,
+            colormap: Arc::new(VIRIDIS),
+            arrow_style: None,
+            axes_style: GREY_700.into(),
+            text_color: BLACK.to_rgba(),
+            x_unit: "".into(),
+            y_unit: "".into(),
+            caption: Some(caption.into()),
+            text_fit: TextFit::default(),
+        };
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, _t, data| {
+            data.text_fit.clear();
+
+            let arrows = data.arrows();
+
+            // Nothing to show without vectors
+            if arrows.is_empty() {
+                return;
+            }
+
+            let (mut min_x, mut min_y, mut max_x, mut max_y) =
+                (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+            let (mut min_magnitude, mut max_magnitude) = (f32::MAX, f32::MIN);
+
+            for ((tail_x, tail_y), (tip_x, tip_y), magnitude) in &arrows {
+                min_x = min_x.min(*tail_x).min(*tip_x);
+                min_y = min_y.min(*tail_y).min(*tip_y);
+                max_x = max_x.max(*tail_x).max(*tip_x);
+                max_y = max_y.max(*tail_y).max(*tip_y);
+                min_magnitude = min_magnitude.min(*magnitude);
+                max_magnitude = max_magnitude.max(*magnitude);
+            }
+
+            let pad = |min: f32, max: f32| {
+                let padding = match max > min {
+                    true => (max - min) * RANGE_PADDING,
+                    false => 1.0,
+                };
+
+                (min - padding)..(max + padding)
+            };
+
+            let font_desc = FontDesc::new(
+                FontFamily::Monospace,
+                CAPTION_SIZE as f64,
+                FontStyle::Normal,
+            );
+
+            let text_style = TextStyle::from(font_desc).color(&data.text_color);
+
+            let mut builder = ChartBuilder::on(area);
+
+            builder
+                .margin(DEFAULT_MARGIN)
+                .x_label_area_size(DEFAULT_LABEL_AREA)
+                .y_label_area_size(DEFAULT_LABEL_AREA);
+
+            if let Some(caption) = &data.caption {
+                let margins = (DEFAULT_MARGIN, DEFAULT_MARGIN);
+                let caption = data.text_fit.caption(area, margins, caption, &text_style);
+
+                builder.caption(caption, text_style.clone());
+            }
+
+            let mut chart = match builder.build_cartesian_2d(pad(min_x, max_x), pad(min_y, max_y)) {
+                Ok(chart) => chart,
+                // Area too small to be drawn
+                Err(_) => return,
+            };
+
+            let plot = chart.plotting_area();
+            let x_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Bottom,
+                DEFAULT_LABEL_AREA,
+                &data.x_unit,
+                &text_style,
+            );
+            let y_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Left,
+                DEFAULT_LABEL_AREA,
+                &data.y_unit,
+                &text_style,
+            );
+
+            let locale = locale();
+            let tick_label = |value: &f32| locale.format_tick(*value as f64);
+
+            chart
+                .configure_mesh()
+                .disable_mesh()
+                .x_label_formatter(&tick_label)
+                .y_label_formatter(&tick_label)
+                .label_style(text_style.clone())
+                .axis_style(data.axes_style)
+                .x_desc(x_desc)
+                .y_desc(y_desc)
+                .draw()
+                .unwrap();
+
+            for (tail, tip, magnitude) in arrows {
+                let style = data.arrow_style.unwrap_or_else(|| {
+                    let position = match max_magnitude > min_magnitude {
+                        true => (magnitude - min_magnitude) / (max_magnitude - min_magnitude),
+                        false => 1.0,
+                    };
+
+                    data.colormap.color(position).stroke_width(1)
+                });
+
+                // Arrows are drawn in pixels so the heads aren't skewed by the axes
+                let (tail_x, tail_y) = chart.backend_coord(&tail);
+                let (tip_x, tip_y) = chart.backend_coord(&tip);
+
+                let (dx, dy) = ((tip_x - tail_x) as f32, (tip_y - tail_y) as f32);
+                let length = dx.hypot(dy);
+
+                if length < 1.0 {
+                    continue;
+                }
+
+                let head = HEAD_LENGTH.min(length * MAX_HEAD_RATIO);
+                let (ux, uy) = (dx / length, dy / length);
+
+                let base = (tip_x as f32 - ux * head, tip_y as f32 - uy * head);
+                let half_width = head / 2.0;
+
+                let head_points = vec![
+                    (tip_x, tip_y),
+                    (
+                        (base.0 - uy * half_width) as i32,
+                        (base.1 + ux * half_width) as i32,
+                    ),
+                    (
+                        (base.0 + uy * half_width) as i32,
+                        (base.1 - ux * half_width) as i32,
+                    ),
+                ];
+
+                area.draw(&PathElement::new(
+                    vec![(tail_x, tail_y), (base.0 as i32, base.1 as i32)],
+                    style,
+                ))
+                .unwrap();
+                area.draw(&Polygon::new(head_points, style.filled()))
+                    .unwrap();
+            }
+        }));
+
+        Self { chart }
+    }
+
+    #[inline]
+    /// Replace the vectors plotted.
+    pub fn set_vectors(&mut self, vectors: &[(f32, f32, f32, f32)]) {
+        self.chart.get_data_mut().vectors = vectors.into()
+    }
+
+    #[inline]
+    /// Set the factor the vectors are scaled by, or scale them to fit the spacing of the arrows
+    /// with None.
+    pub fn set_arrow_scale(&mut self, scale: Option<f32>) {
+        self.chart.get_data_mut().scale = scale
+    }
+
+    #[inline]
+    /// Set the factor the vectors are scaled by. Consumes self.
+    pub fn arrow_scale(mut self, scale: Option<f32>) -> Self {
+        self.set_arrow_scale(scale);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable drawing every arrow at the same length.
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.chart.get_data_mut().normalize = normalize
+    }
+
+    #[inline]
+    /// Enable/disable drawing every arrow at the same length. Consumes self.
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.set_normalize(normalize);
+
+        self
+    }
+
+    /// Set the colormap the arrows are colored with.
+    pub fn set_colormap<C>(&mut self, colormap: C)
+    where
+        C: Colormap + 'static,
+    {
+        self.chart.get_data_mut().colormap = Arc::new(colormap)
+    }
+
+    #[inline]
+    /// Set the colormap the arrows are colored with. Consumes self.
+    pub fn colormap<C>(mut self, colormap: C) -> Self
+    where
+        C: Colormap + 'static,
+    {
+        self.set_colormap(colormap);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the arrows, or color them with the colormap with None.
+    pub fn set_arrow_style(&mut self, arrow_style: Option<ShapeStyle>) {
+        self.chart.get_data_mut().arrow_style = arrow_style
+    }
+
+    #[inline]
+    /// Set the style of the arrows, or color them with the colormap with None. Consumes self.
+    pub fn arrow_style(mut self, arrow_style: Option<ShapeStyle>) -> Self {
+        self.set_arrow_style(arrow_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the axes.
+    pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
+        self.chart.get_data_mut().axes_style = axes_style
+    }
+
+    #[inline]
+    /// Set the style of the axes. Consumes self.
+    pub fn axes_style(mut self, axes_style: ShapeStyle) -> Self {
+        self.set_axes_style(axes_style);
+
+        self
+    }
+
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the units of the X and Y axes.
+    pub fn set_units(&mut self, x_unit: &str, y_unit: &str) {
+        let config = self.chart.get_data_mut();
+
+        config.x_unit = x_unit.into();
+        config.y_unit = y_unit.into();
+    }
+
+    #[inline]
+    /// Set the units of the X and Y axes. Consumes self.
+    pub fn units(mut self, x_unit: &str, y_unit: &str) -> Self {
+        self.set_units(x_unit, y_unit);
+
+        self
+    }
+
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening the caption and axis descriptions too long for
+    /// the chart with an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.get_data_mut().text_fit.set_enabled(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_data().text_fit.is_enabled()
+    }
+
+    #[inline]
+    /// Draw the chart to a Ui.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        self.chart.draw(ui);
+
+        let transform = self.chart.get_transform();
+
+        self.chart.get_data().text_fit.show_full_text(ui, transform);
+    }
+
+    /// Draw the chart to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.chart.draw_headless(backend)
+    }
+}
@@ -0,0 +1,525 @@
+//! Minimal animatable line chart for embedding in tables and status rows.
+
+use std::{cell::RefCell, cmp::Ordering, ops::Range, rc::Rc, sync::Arc};
+
+use egui::{vec2, Ui};
+use plotters::{
+    prelude::{ChartBuilder, Circle},
+    series::LineSeries,
+    style::{
+        full_palette::{BLUE_700, RED_700, RED_900},
+        Color, ShapeStyle,
+    },
+};
+use plotters_backend::{DrawingBackend, DrawingErrorKind};
+
+use crate::{
+    charts::{
+        playback::{Playback, PlaybackError, PlaybackGroup},
+        timeindex::time_index,
+    },
+    Chart,
+};
+
+const DEFAULT_HEIGHT: f32 = 20.0;
+const MARGIN: i32 = 3;
+const MARKER_SIZE: i32 = 2;
+
+#[derive(Clone)]
+struct SparklineConfig {
+    /// Every point, including the ones not yet plotted. A slice of time, value pairs.
+    all_points: Arc<[(f32, f32)]>,
+    /// Number of points plotted, counted from the start of `all_points`.
+    visible: usize,
+    /// Range of the times and values of every point.
+    range: (Range<f32>, Range<f32>),
+    /// Style of the plotted line.
+    line_style: ShapeStyle,
+    /// Style of the minimum value marker.
+    min_style: ShapeStyle,
+    /// Style of the maximum value marker.
+    max_style: ShapeStyle,
+    /// Style of the last value marker.
+    last_style: ShapeStyle,
+    /// Mark the minimum plotted value.
+    min_marker: bool,
+    /// Mark the maximum plotted value.
+    max_marker: bool,
+    /// Mark the last plotted value.
+    last_marker: bool,
+}
+
+impl SparklineConfig {
+    #[inline]
+    /// Points plotted so far, shared with every other frame of the playback.
+    fn points(&self) -> &[(f32, f32)] {
+        &self.all_points[..self.visible]
+    }
+}
+
+/// Animatable sparkline.
+///
+/// ## Usage
+/// **Ensure the `timechart` feature is enabled to use this type.**
+///
+/// A sparkline is a tiny line chart with no axes or caption, drawn in a strip of fixed
+/// height spanning the available width. Perfect for table cells and status rows.
+///
+///  * `points`: A slice of tuples, arranged so that the first float is the time and the second
+///    is the value.
+///
+/// Markers for the minimum, maximum, and last values can be enabled with the `.set_` functions
+/// included, and the sparkline can be animated just like the other time charts with
+/// `.toggle_playback()`.
+pub struct Sparkline {
+    playback: Rc<RefCell<Playback>>,
+    height: f32,
+    /// Time of every point, in order.
+    times: Arc<[f32]>,
+    chart: Chart<SparklineConfig>,
+}
+
+impl Sparkline {
+    /// Create a new Sparkline. See [Usage](#usage).
+    pub fn new(points: &[(f32, f32)]) -> Self {
+        let mut points = points.to_vec();
+
+        // Sort by the time of the point
+        points.sort_by(|a, b| {
+            let (a, _) = a;
+            let (b, _) = b;
+
+            a.partial_cmp(b).unwrap_or(Ordering::Equal)
+        });
+
+        let mut min_time: f32 = f32::MAX;
+        let mut min_value: f32 = f32::MAX;
+        let mut max_time: f32 = f32::MIN;
+        let mut max_value: f32 = f32::MIN;
+
+        for (time, value) in &points {
+            min_time = min_time.min(*time);
+            min_value = min_value.min(*value);
+            max_time = max_time.max(*time);
+            max_value = max_value.max(*value);
+        }
+
+        // Without any points there's nothing to span, so span nothing at zero instead
+        if points.is_empty() {
+            (min_time, max_time, min_value, max_value) = (0.0, 0.0, 0.0, 0.0);
+        }
+
+        let times = points.iter().map(|(time, _)| *time).collect();
+        let points: Arc<[(f32, f32)]> = points.into();
+
+        let line_style = ShapeStyle {
+            color: RED_900.to_rgba(),
+            filled: false,
+            stroke_width: 1,
+        };
+
+        let config = SparklineConfig {
+            all_points: points.clone(),
+            visible: points.len(),
+            range: (
+                pad_range(min_time..max_time),
+                pad_range(min_value..max_value),
+            ),
+            line_style,
+            min_style: BLUE_700.filled(),
+            max_style: RED_700.filled(),
+            last_style: line_style.filled(),
+            min_marker: false,
+            max_marker: false,
+            last_marker: false,
+        };
+
+        let playback = Rc::new(RefCell::new(Playback::new(max_time - min_time)));
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, _t, data| {
+            let (x_range, y_range) = data.range.clone();
+
+            let mut chart = ChartBuilder::on(area)
+                .margin(MARGIN)
+                .build_cartesian_2d(x_range, y_range)
+                .unwrap();
+
+            chart
+                .draw_series(LineSeries::new(
+                    data.points().iter().copied(),
+                    data.line_style,
+                ))
+                .unwrap();
+
+            let compare = |a: &&(f32, f32), b: &&(f32, f32)| {
+                let (_, a) = a;
+                let (_, b) = b;
+
+                a.partial_cmp(b).unwrap_or(Ordering::Equal)
+            };
+
+            let markers = [
+                (
+                    data.min_marker,
+                    data.points().iter().min_by(compare),
+                    data.min_style,
+                ),
+                (
+                    data.max_marker,
+                    data.points().iter().max_by(compare),
+                    data.max_style,
+                ),
+                (data.last_marker, data.points().last(), data.last_style),
+            ];
+
+            for (enabled, point, style) in markers {
+                if let (true, Some(point)) = (enabled, point) {
+                    chart
+                        .draw_series([Circle::new(*point, MARKER_SIZE, style)])
+                        .unwrap();
+                }
+            }
+        }));
+
+        Self {
+            playback,
+            height: DEFAULT_HEIGHT,
+            times,
+            chart,
+        }
+    }
+
+    #[inline]
+    /// Set the height of the sparkline's strip in points.
+    pub fn set_height(&mut self, height: f32) {
+        self.height = height
+    }
+
+    #[inline]
+    /// Set the height of the sparkline's strip in points. Consumes self.
+    pub fn height(mut self, height: f32) -> Self {
+        self.set_height(height);
+
+        self
+    }
+
+    /// Set the style of the plotted line.
+    pub fn set_line_style(&mut self, line_style: ShapeStyle) {
+        self.chart.get_data_mut().line_style = line_style;
+    }
+
+    #[inline]
+    /// Set the style of the plotted line. Consumes self.
+    pub fn line_style(mut self, line_style: ShapeStyle) -> Self {
+        self.set_line_style(line_style);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide a marker on the minimum plotted value.
+    pub fn set_min_marker(&mut self, min_marker: bool) {
+        self.chart.get_data_mut().min_marker = min_marker
+    }
+
+    #[inline]
+    /// Show/hide a marker on the minimum plotted value. Consumes self.
+    pub fn min_marker(mut self, min_marker: bool) -> Self {
+        self.set_min_marker(min_marker);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide a marker on the maximum plotted value.
+    pub fn set_max_marker(&mut self, max_marker: bool) {
+        self.chart.get_data_mut().max_marker = max_marker
+    }
+
+    #[inline]
+    /// Show/hide a marker on the maximum plotted value. Consumes self.
+    pub fn max_marker(mut self, max_marker: bool) -> Self {
+        self.set_max_marker(max_marker);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide a marker on the last plotted value.
+    pub fn set_last_marker(&mut self, last_marker: bool) {
+        self.chart.get_data_mut().last_marker = last_marker
+    }
+
+    #[inline]
+    /// Show/hide a marker on the last plotted value. Consumes self.
+    pub fn last_marker(mut self, last_marker: bool) -> Self {
+        self.set_last_marker(last_marker);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the minimum, maximum, and last value markers.
+    pub fn set_marker_styles(
+        &mut self,
+        min_style: ShapeStyle,
+        max_style: ShapeStyle,
+        last_style: ShapeStyle,
+    ) {
+        let config = self.chart.get_data_mut();
+
+        config.min_style = min_style;
+        config.max_style = max_style;
+        config.last_style = last_style;
+    }
+
+    #[inline]
+    /// Set the style of the minimum, maximum, and last value markers. Consumes self.
+    pub fn marker_styles(
+        mut self,
+        min_style: ShapeStyle,
+        max_style: ShapeStyle,
+        last_style: ShapeStyle,
+    ) -> Self {
+        self.set_marker_styles(min_style, max_style, last_style);
+
+        self
+    }
+
+    /// Draw the sparkline to a Ui, taking up the available width and the sparkline's height.
+    /// Will also proceed to animate the sparkline if playback is currently enabled.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        if self.playback.borrow().is_active() {
+            let time = self.current_time();
+
+            self.seek_points(time);
+        }
+
+        self.draw_strip(ui);
+    }
+
+    /// Draw the sparkline to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui. The backend's size is used as is, in place of the sparkline's height.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        if self.playback.borrow().is_active() {
+            let time = self.current_time();
+
+            self.seek_points(time);
+        }
+
+        self.chart.draw_headless(backend)
+    }
+
+    /// Draw the sparkline to a Ui as it would appear at the given time, bypassing the
+    /// sparkline's own playback clock.
+    pub fn draw_at_time(&mut self, ui: &mut Ui, time: f32) {
+        self.seek_points(time);
+
+        self.draw_strip(ui);
+    }
+
+    fn draw_strip(&mut self, ui: &mut Ui) {
+        let size = vec2(ui.available_width(), self.height);
+
+        ui.allocate_ui(size, |ui| self.chart.draw(ui));
+    }
+
+    /// Update the plotted points to those shown at the given time.
+    fn seek_points(&mut self, time: f32) {
+        let visible = match self.times.is_empty() {
+            true => 0,
+            false => time_index(&self.times, time) + 1,
+        };
+
+        self.chart.get_data_mut().visible = visible;
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds.
+    pub fn set_time(&mut self, time: f32) {
+        self.playback.borrow_mut().set_time(time)
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds. Consumes self.
+    pub fn time(mut self, time: f32) -> Self {
+        self.set_time(time);
+
+        self
+    }
+
+    #[inline]
+    /// Move playback forward, or backward if negative, by some seconds from the current time.
+    /// Starts playback paused if it wasn't underway.
+    pub fn seek_relative(&mut self, delta: f32) {
+        self.playback.borrow_mut().seek_relative(delta)
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half.
+    pub fn set_playback_speed(&mut self, speed: f32) {
+        self.playback.borrow_mut().set_speed(speed)
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Consumes self.
+    pub fn playback_speed(mut self, speed: f32) -> Self {
+        self.set_playback_speed(speed);
+
+        self
+    }
+
+    #[inline]
+    /// Start/enable playback of the sparkline. Does nothing if the sparkline has nothing to
+    /// animate.
+    pub fn start_playback(&mut self) {
+        self.playback.borrow_mut().start()
+    }
+
+    #[inline]
+    /// Start/enable playback of the sparkline, returning [`PlaybackError::ZeroDuration`] if the
+    /// sparkline has nothing to animate, such as when every point is at the same time.
+    pub fn try_start_playback(&mut self) -> Result<(), PlaybackError> {
+        self.playback.borrow_mut().try_start()
+    }
+
+    #[inline]
+    /// Stop/disable playback of the sparkline.
+    pub fn stop_playback(&mut self) {
+        self.playback.borrow_mut().stop()
+    }
+
+    #[inline]
+    /// Toggle playback of the sparkline.
+    pub fn toggle_playback(&mut self) {
+        self.playback.borrow_mut().toggle()
+    }
+
+    #[inline]
+    /// Return true if playback is currently enabled & underway.
+    pub fn is_playing(&self) -> bool {
+        self.playback.borrow().is_playing()
+    }
+
+    #[inline]
+    /// Return the time the sparkline starts at when playback is enabled.
+    pub fn start_time(&self) -> f32 {
+        self.times.first().copied().unwrap_or_default()
+    }
+
+    /// Return the current time to be animated when playback is enabled.
+    pub fn current_time(&mut self) -> f32 {
+        let time_start = self.start_time();
+        let time_end = self.end_time();
+
+        match self.playback.borrow_mut().elapsed() {
+            Some(elapsed) => (elapsed + time_start).min(time_end),
+            None => time_start,
+        }
+    }
+
+    #[inline]
+    /// Return the time the sparkline finishes animating at when playback is enabled.
+    pub fn end_time(&self) -> f32 {
+        self.times.last().copied().unwrap_or_default()
+    }
+
+    #[inline]
+    /// Return the speed the sparkline is animated at.
+    pub fn get_playback_speed(&self) -> f32 {
+        self.playback.borrow().speed()
+    }
+
+    /// Join a playback group, sharing its playback clock with every other member.
+    pub fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        let playback = group.playback().clone();
+
+        playback
+            .borrow_mut()
+            .extend_duration(self.end_time() - self.start_time());
+
+        self.playback = playback;
+    }
+
+    #[inline]
+    /// Join a playback group, sharing its playback clock with every other member. Consumes self.
+    pub fn playback_group(mut self, group: &PlaybackGroup) -> Self {
+        self.set_playback_group(group);
+
+        self
+    }
+
+    /// Leave the current playback group, if any, and go back to an independent playback clock.
+    pub fn leave_playback_group(&mut self) {
+        let duration = self.end_time() - self.start_time();
+
+        self.playback = Rc::new(RefCell::new(Playback::new(duration)));
+    }
+}
+
+/// Widen empty ranges so flat lines can still be drawn.
+fn pad_range(range: Range<f32>) -> Range<f32> {
+    match range.end > range.start {
+        true => range,
+        false => (range.start - 1.0)..(range.end + 1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeks_to_the_points_shown() {
+        let mut sparkline = Sparkline::new(&[(2.0, 5.0), (0.0, 1.0), (1.0, 3.0)]);
+
+        for (time, visible) in [(-1.0, 1), (0.5, 1), (1.0, 2), (2.0, 3), (9.0, 3)] {
+            sparkline.seek_points(time);
+
+            assert_eq!(sparkline.chart.get_data().visible, visible, "time {time}");
+        }
+    }
+
+    #[test]
+    fn empty_sparkline_seeks_nothing() {
+        let mut sparkline = Sparkline::new(&[]);
+
+        sparkline.seek_points(1.0);
+
+        assert_eq!(sparkline.chart.get_data().visible, 0);
+        assert_eq!((sparkline.start_time(), sparkline.end_time()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn sparklines_sharing_a_ui_keep_their_own_ids() {
+        let mut sparklines: Vec<Sparkline> = (0..3)
+            .map(|index| Sparkline::new(&[(0.0, 0.0), (1.0, index as f32)]))
+            .collect();
+
+        let ctx = egui::Context::default();
+
+        ctx.options_mut(|options| options.warn_on_id_clash = true);
+
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                egui::Grid::new("sparklines").show(ui, |ui| {
+                    for sparkline in &mut sparklines {
+                        sparkline.draw(ui);
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+
+        // egui paints a warning over widgets whose ids clash
+        let warnings = output.shapes.iter().any(|shape| match &shape.shape {
+            egui::Shape::Text(text) => text.galley.job.text.contains(" ID "),
+            _ => false,
+        });
+
+        assert!(!warnings);
+    }
+}
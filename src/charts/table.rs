@@ -0,0 +1,104 @@
+//! Scrollable table of a time chart's points, shown beside or instead of the plot. Every value is
+//! an egui label, so the data can be read by screen readers as well as inspected by eye.
+
+use egui::{Label, RichText, ScrollArea, SelectableLabel, TextStyle, Ui};
+
+use crate::locale::locale;
+
+/// Default width of the table when shown beside the plot, in pixels.
+pub(crate) const DEFAULT_TABLE_WIDTH: f32 = 240.0;
+/// Space between the plot and the table, in pixels.
+pub(crate) const TABLE_SPACING: f32 = 4.0;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Where the data table of a time chart is shown.
+pub enum DataTableMode {
+    #[default]
+    /// Only show the plot.
+    Hidden,
+    /// Show the table to the right of the plot.
+    Beside,
+    /// Show the table in place of the plot.
+    Instead,
+}
+
+/// Draw the points as a table with a row of time, X, and Y per point. Rows past the `shown`
+/// points plotted so far are dimmed, while the last one plotted is emphasized and, if `follow`
+/// is set, kept in view. The row at the `selected` index is highlighted.
+///
+/// Returns the times of the rows hovered and clicked, if any. The table's scroll state is keyed
+/// by the id of the Ui, so every table needs a Ui of its own.
+pub(crate) fn draw_table(
+    ui: &mut Ui,
+    header: [&str; 3],
+    points: &[(f32, f32)],
+    times: &[f32],
+    shown: usize,
+    selected: Option<usize>,
+    follow: bool,
+) -> (Option<f32>, Option<f32>) {
+    let locale = locale();
+    let spacing = ui.spacing().item_spacing;
+    let row_height = ui.text_style_height(&TextStyle::Body);
+    let column = ((ui.available_width() - 2.0 * spacing.x) / 3.0).max(0.0);
+
+    ui.horizontal(|ui| {
+        for text in header {
+            ui.add_sized(
+                [column, row_height],
+                Label::new(RichText::new(text).strong()),
+            );
+        }
+    });
+
+    let mut area = ScrollArea::vertical()
+        .id_source("egui_plotter_data_table")
+        .auto_shrink([false; 2]);
+
+    if follow {
+        let playhead = shown.saturating_sub(1) as f32 * (row_height + spacing.y);
+
+        area = area.vertical_scroll_offset((playhead - ui.available_height() / 2.0).max(0.0));
+    }
+
+    let mut hovered = None;
+    let mut clicked = None;
+
+    area.show_rows(ui, row_height, points.len(), |ui, rows| {
+        for index in rows {
+            let (x, y) = points[index];
+            let time = times[index];
+
+            let cells = [time, x, y].map(|value| {
+                let text = RichText::new(locale.format_number(value));
+
+                match index + 1 {
+                    row if row == shown => text.strong(),
+                    row if row > shown => text.weak(),
+                    _ => text,
+                }
+            });
+
+            let responses = ui
+                .horizontal(|ui| {
+                    cells.map(|text| {
+                        let cell = SelectableLabel::new(selected == Some(index), text);
+
+                        ui.add_sized([column, row_height], cell)
+                    })
+                })
+                .inner;
+
+            if responses.iter().any(|response| response.hovered()) {
+                hovered = Some(time);
+            }
+
+            if responses.iter().any(|response| response.clicked()) {
+                clicked = Some(time);
+            }
+        }
+    });
+
+    (hovered, clicked)
+}
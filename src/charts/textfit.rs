@@ -0,0 +1,447 @@
+//! Shortening of captions, axis descriptions, and legend entries too long for the chart, with
+//! their full text revealed when hovered.
+
+use std::{borrow::Cow, cell::RefCell};
+
+use egui::{Pos2, Rect, Ui, Vec2};
+use plotters::{
+    coord::{CoordTranslate, Shift},
+    element::MultiLineText,
+    prelude::{DrawingArea, LabelAreaPosition},
+    style::TextStyle,
+};
+use plotters_backend::DrawingBackend;
+
+use crate::{compat::show_tooltip_text, ScreenTransform, Transform};
+
+const ELLIPSIS: char = '…';
+/// Widest the legend's entries can be before they are shortened, relative to the width of the
+/// plotting area.
+const LEGEND_WIDTH: f32 = 0.4;
+/// Space plotters leaves around the legend's entries, and for the legend's markers.
+const LEGEND_MARGIN: i32 = 10;
+const LEGEND_MARKER_AREA: i32 = 30;
+/// Distance plotters places the legend from the corner of the plot.
+const LEGEND_OFFSET: i32 = 5;
+/// Line height of the legend's entries, relative to the font size.
+const LEGEND_LINE_HEIGHT: f64 = 1.25;
+
+#[derive(Debug, Clone)]
+/// Texts of a chart shortened with an ellipsis to fit, remembered along with where they were
+/// drawn so their full text can be shown when hovered.
+pub(crate) struct TextFit {
+    enabled: bool,
+    /// Rects of the texts shortened the last frame, in pixels of the drawing area, along with
+    /// their full text.
+    shortened: RefCell<Vec<(Rect, String)>>,
+}
+
+impl Default for TextFit {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shortened: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl TextFit {
+    #[inline]
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled
+    }
+
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    #[inline]
+    /// Forget the texts shortened the last frame, before drawing the next one.
+    pub(crate) fn clear(&self) {
+        self.shortened.borrow_mut().clear()
+    }
+
+    /// Shorten a text drawn to an area to fit within `max_width` pixels, revealing it when `rect`
+    /// is hovered.
+    fn fit<'a, DB, CT>(
+        &self,
+        area: &DrawingArea<DB, CT>,
+        text: &'a str,
+        style: &TextStyle,
+        max_width: i32,
+        rect: Rect,
+    ) -> Cow<'a, str>
+    where
+        DB: DrawingBackend,
+        CT: CoordTranslate,
+    {
+        if !self.enabled {
+            return Cow::Borrowed(text);
+        }
+
+        match shorten(area, text, style, max_width) {
+            Some(short) => {
+                self.shortened.borrow_mut().push((rect, text.to_string()));
+
+                Cow::Owned(short)
+            }
+            None => Cow::Borrowed(text),
+        }
+    }
+
+    /// Shorten the caption of a chart to the width of its drawing area, less the `margin` on
+    /// each side. `top` is the margin above the caption.
+    pub(crate) fn caption<'a, DB>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        (margin, top): (i32, i32),
+        caption: &'a str,
+        style: &TextStyle,
+    ) -> Cow<'a, str>
+    where
+        DB: DrawingBackend,
+    {
+        let (width, _) = area.dim_in_pixel();
+        let (left, base_y) = area.get_base_pixel();
+
+        let max_width = width as i32 - margin * 2;
+        let rect = Rect::from_min_size(
+            Pos2::new((left + margin) as f32, (base_y + top) as f32),
+            Vec2::new(max_width as f32, style.font.get_size() as f32),
+        );
+
+        self.fit(area, caption, style, max_width, rect)
+    }
+
+    /// Shorten the description of an axis to the length of the plotting area along it. The
+    /// description is revealed when the label area of the axis, `label_area` pixels wide, is
+    /// hovered.
+    pub(crate) fn axis_desc<'a, DB, CT>(
+        &self,
+        plot: &DrawingArea<DB, CT>,
+        position: LabelAreaPosition,
+        label_area: i32,
+        desc: &'a str,
+        style: &TextStyle,
+    ) -> Cow<'a, str>
+    where
+        DB: DrawingBackend,
+        CT: CoordTranslate,
+    {
+        let (x, y) = plot.get_pixel_range();
+
+        let (max_width, (left, right), (top, bottom)) = match position {
+            LabelAreaPosition::Top => (
+                x.end - x.start,
+                (x.start, x.end),
+                (y.start - label_area, y.start),
+            ),
+            LabelAreaPosition::Bottom => (
+                x.end - x.start,
+                (x.start, x.end),
+                (y.end, y.end + label_area),
+            ),
+            LabelAreaPosition::Left => (
+                y.end - y.start,
+                (x.start - label_area, x.start),
+                (y.start, y.end),
+            ),
+            LabelAreaPosition::Right => (
+                y.end - y.start,
+                (x.end, x.end + label_area),
+                (y.start, y.end),
+            ),
+        };
+
+        let rect = Rect::from_min_max(
+            Pos2::new(left as f32, top as f32),
+            Pos2::new(right as f32, bottom as f32),
+        );
+
+        self.fit(plot, desc, style, max_width, rect)
+    }
+
+    /// Shorten the entries of a legend drawn in the upper right of the plotting area to a part
+    /// of its width.
+    pub(crate) fn legend<'a, DB, CT>(
+        &self,
+        area: &DrawingArea<DB, CT>,
+        labels: &[&'a str],
+        style: &TextStyle,
+    ) -> Vec<Cow<'a, str>>
+    where
+        DB: DrawingBackend,
+        CT: CoordTranslate,
+    {
+        if !self.enabled {
+            return labels.iter().map(|label| Cow::Borrowed(*label)).collect();
+        }
+
+        let (width, _) = area.dim_in_pixel();
+        let max_width = (width as f32 * LEGEND_WIDTH) as i32;
+
+        let shortened: Vec<Option<String>> = labels
+            .iter()
+            .map(|label| shorten(area, label, style, max_width))
+            .collect();
+
+        let fitted: Vec<Cow<str>> = labels
+            .iter()
+            .zip(&shortened)
+            .map(|(label, short)| match short {
+                Some(short) => Cow::Owned(short.clone()),
+                None => Cow::Borrowed(*label),
+            })
+            .collect();
+
+        let entries = legend_entries(area, &fitted, style);
+        let mut revealed = self.shortened.borrow_mut();
+
+        for ((entry, label), short) in entries.into_iter().zip(labels).zip(&shortened) {
+            if short.is_some() {
+                revealed.push((entry, label.to_string()));
+            }
+        }
+
+        fitted
+    }
+
+    /// Show the full text of the shortened text under the pointer, if any, for a chart drawn to
+    /// the Ui with the given transform.
+    pub(crate) fn show_full_text(&self, ui: &Ui, transform: Transform) {
+        let transform = ScreenTransform::new(ui.max_rect(), transform);
+
+        let Some(pointer) = ui.input(|input| input.pointer.hover_pos()) else {
+            return;
+        };
+
+        let shortened = self.shortened.borrow();
+
+        let hovered = shortened
+            .iter()
+            .find(|(rect, _)| transform.rect_to_screen(*rect).contains(pointer));
+
+        if let Some((_, text)) = hovered {
+            show_tooltip_text(ui, "egui_plotter_shortened_text", text.clone());
+        }
+    }
+}
+
+/// Shorten a text drawn to an area with an ellipsis to fit within `max_width` pixels. Returns
+/// None if it already fits, or if the text can't be measured.
+fn shorten<DB, CT>(
+    area: &DrawingArea<DB, CT>,
+    text: &str,
+    style: &TextStyle,
+    max_width: i32,
+) -> Option<String>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate,
+{
+    // Measured by the backend, as egui lays out text wider than plotters estimates
+    let width = |text: &str| {
+        area.estimate_text_size(text, style)
+            .ok()
+            .map(|(width, _)| width as i32)
+    };
+
+    if width(text)? <= max_width {
+        return None;
+    }
+
+    // Keep the longest start of the text that fits along with the ellipsis
+    let ends: Vec<usize> = text.char_indices().map(|(index, _)| index).collect();
+    let fitting = ends.partition_point(|end| {
+        width(&format!("{}{ELLIPSIS}", &text[..*end])).is_some_and(|width| width <= max_width)
+    });
+
+    let end = fitting.checked_sub(1).map_or(0, |index| ends[index]);
+
+    Some(format!("{}{ELLIPSIS}", text[..end].trim_end()))
+}
+
+/// Rects of the entries of a legend, laid out as plotters lays out series labels in the upper
+/// right of the plotting area.
+pub(crate) fn legend_entries<DB, CT, S>(
+    area: &DrawingArea<DB, CT>,
+    labels: &[S],
+    style: &TextStyle,
+) -> Vec<Rect>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate,
+    S: AsRef<str>,
+{
+    let mut text = MultiLineText::<_, &str>::new((0, 0), style);
+
+    for label in labels {
+        text.push_line(label.as_ref());
+    }
+
+    let Ok((text_width, _)) = text.estimate_dimension() else {
+        return Vec::new();
+    };
+
+    let width = text_width + LEGEND_MARKER_AREA + LEGEND_MARGIN * 2;
+    let line_height = (style.font.get_size() * LEGEND_LINE_HEIGHT) as f32;
+
+    let (area_width, _) = area.dim_in_pixel();
+    let (base_x, base_y) = area.get_base_pixel();
+    let left = base_x + area_width as i32 - width - LEGEND_OFFSET;
+
+    text.relocate((0, base_y + LEGEND_OFFSET + LEGEND_MARGIN));
+
+    let Ok(lines) = text.compute_line_layout() else {
+        return Vec::new();
+    };
+
+    // Entries span the whole legend, centered on their markers
+    lines
+        .into_iter()
+        .map(|((_, top), (_, bottom))| {
+            Rect::from_center_size(
+                Pos2::new(
+                    left as f32 + width as f32 / 2.0,
+                    (top + bottom) as f32 / 2.0,
+                ),
+                Vec2::new(width as f32, line_height),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::prelude::IntoDrawingArea;
+    use plotters_backend::{BackendColor, BackendCoord, BackendTextStyle, DrawingErrorKind};
+
+    use super::*;
+
+    /// Backend measuring every character, the ellipsis included, as 10 pixels wide.
+    struct FixedWidth;
+
+    impl DrawingBackend for FixedWidth {
+        type ErrorType = std::io::Error;
+
+        fn get_size(&self) -> (u32, u32) {
+            (200, 100)
+        }
+
+        fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            Ok(())
+        }
+
+        fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            Ok(())
+        }
+
+        fn draw_pixel(
+            &mut self,
+            _point: BackendCoord,
+            _color: BackendColor,
+        ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+            Ok(())
+        }
+
+        fn estimate_text_size<TStyle: BackendTextStyle>(
+            &self,
+            text: &str,
+            _style: &TStyle,
+        ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+            Ok((text.chars().count() as u32 * 10, 10))
+        }
+    }
+
+    fn style() -> TextStyle<'static> {
+        ("sans-serif", 10.0).into()
+    }
+
+    #[test]
+    fn texts_that_fit_are_left_alone() {
+        let area = FixedWidth.into_drawing_area();
+
+        assert_eq!(shorten(&area, "fits", &style(), 40), None);
+        assert_eq!(shorten(&area, "", &style(), 0), None);
+    }
+
+    #[test]
+    fn long_texts_keep_the_longest_start_that_fits() {
+        let area = FixedWidth.into_drawing_area();
+
+        assert_eq!(
+            shorten(&area, "abcdefghij", &style(), 60).as_deref(),
+            Some("abcde…")
+        );
+
+        // Characters are kept whole, however many bytes they take
+        assert_eq!(
+            shorten(&area, "ééééééé", &style(), 45).as_deref(),
+            Some("ééé…")
+        );
+    }
+
+    #[test]
+    fn spaces_before_the_ellipsis_are_trimmed() {
+        let area = FixedWidth.into_drawing_area();
+
+        assert_eq!(
+            shorten(&area, "ab  cdefgh", &style(), 50).as_deref(),
+            Some("ab…")
+        );
+    }
+
+    #[test]
+    fn texts_too_long_for_any_start_are_only_an_ellipsis() {
+        let area = FixedWidth.into_drawing_area();
+
+        assert_eq!(shorten(&area, "abc", &style(), 5).as_deref(), Some("…"));
+    }
+
+    #[test]
+    fn shortened_captions_are_remembered_for_hovering() {
+        let area = FixedWidth.into_drawing_area();
+        let mut fit = TextFit::default();
+
+        let caption = "a caption far too long for the chart";
+
+        assert_eq!(
+            fit.caption(&area, (10, 5), caption, &style()),
+            "a caption far too…"
+        );
+        assert_eq!(fit.caption(&area, (10, 5), "short", &style()), "short");
+
+        let shortened = fit.shortened.borrow().clone();
+
+        assert_eq!(
+            shortened,
+            [(
+                Rect::from_min_size(Pos2::new(10.0, 5.0), Vec2::new(180.0, 10.0)),
+                caption.to_string()
+            )]
+        );
+
+        fit.clear();
+        fit.set_enabled(false);
+
+        assert_eq!(fit.caption(&area, (10, 5), caption, &style()), caption);
+        assert!(fit.shortened.borrow().is_empty());
+    }
+
+    #[test]
+    fn only_long_legend_entries_are_shortened() {
+        let area = FixedWidth.into_drawing_area();
+        let fit = TextFit::default();
+
+        // Entries can be 80 pixels wide, 40% of the area
+        let fitted = fit.legend(&area, &["short", "a long series name"], &style());
+
+        assert_eq!(fitted, ["short", "a long…"]);
+
+        let shortened = fit.shortened.borrow();
+
+        assert_eq!(shortened.len(), 1);
+        assert_eq!(shortened[0].1, "a long series name");
+    }
+}
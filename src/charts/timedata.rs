@@ -1,9 +1,22 @@
 //! Animatable chart with data on the Y and time on the X axis
 
-use egui::Ui;
-use plotters::style::{RGBAColor, ShapeStyle};
+use std::{io, ops::Range, path::Path};
 
-use crate::charts::XyTimeData;
+use egui::{Rect, Ui};
+use plotters::style::{RGBAColor, ShapeStyle};
+use plotters_backend::{DrawingBackend, DrawingErrorKind};
+
+#[cfg(feature = "adaptive")]
+use crate::AdaptiveQuality;
+use crate::{
+    chart::{BackgroundCallback, OverlayCallback},
+    charts::{
+        AutoscaleMode, DataTableMode, GridConfig, LinkGroup, PlaybackError, PlaybackGroup,
+        TimeChartState, TimeMapping, XAxisPosition, XyTimeData, YAxisPosition,
+    },
+    colormap::Colormap,
+    RenderQuality,
+};
 
 /// Animatable chart with time on the X axis and data on the Y axis.
 ///
@@ -14,7 +27,7 @@ use crate::charts::XyTimeData;
 /// 3 of which are just strings.
 ///
 ///  * `points`: A slice of tuples, arranged so that the first float is the time
-///  and the second is the data.
+///    and the second is the data.
 ///  * `unit`: String describing the data on the Y axis.
 ///  * `caption`: String to be shown as the caption of the chart.
 ///
@@ -29,7 +42,7 @@ impl TimeData {
     /// Create a new TimeData chart. See [Usage](#usage).
     pub fn new(points: &[(f32, f32)], unit: &str, caption: &str) -> Self {
         let points: Vec<(f32, f32, f32)> = points
-            .into_iter()
+            .iter()
             .map(|(data, time)| (*data, *time, *time))
             .collect();
 
@@ -38,6 +51,17 @@ impl TimeData {
         Self { chart }
     }
 
+    /// Replace the points with a new dataset, keeping the styling, view, bookmarks, and playback
+    /// settings of the chart. Empty datasets are ignored, as the chart needs at least one point.
+    pub fn set_points(&mut self, points: &[(f32, f32)]) {
+        let points: Vec<(f32, f32, f32)> = points
+            .iter()
+            .map(|(data, time)| (*data, *time, *time))
+            .collect();
+
+        self.chart.set_points(&points)
+    }
+
     /// Set the time to resume playback at. Time is in seconds.
     #[inline]
     pub fn set_time(&mut self, time: f32) {
@@ -52,6 +76,13 @@ impl TimeData {
         self
     }
 
+    /// Move playback forward, or backward if negative, by some seconds from the current time.
+    /// Starts playback paused if it wasn't underway.
+    #[inline]
+    pub fn seek_relative(&mut self, delta: f32) {
+        self.chart.seek_relative(delta)
+    }
+
     /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half.
     #[inline]
     pub fn set_playback_speed(&mut self, speed: f32) {
@@ -81,13 +112,13 @@ impl TimeData {
     }
 
     #[inline]
-    /// Set the style of the grid.
+    /// Set the style of the major grid lines on both axes.
     pub fn set_grid_style(&mut self, grid_style: ShapeStyle) {
         self.chart.set_grid_style(grid_style)
     }
 
     #[inline]
-    /// Set the style of the grid. Consumes self.
+    /// Set the style of the major grid lines on both axes. Consumes self.
     pub fn grid_style(mut self, grid_style: ShapeStyle) -> Self {
         self.set_grid_style(grid_style);
 
@@ -95,19 +126,47 @@ impl TimeData {
     }
 
     #[inline]
-    /// Set the style of the subgrid.
+    /// Set the style of the minor grid lines on both axes.
     pub fn set_subgrid_style(&mut self, subgrid_style: ShapeStyle) {
         self.chart.set_subgrid_style(subgrid_style)
     }
 
     #[inline]
-    /// Set the style of the subgrid. Consumes self.
+    /// Set the style of the minor grid lines on both axes. Consumes self.
     pub fn subgrid_style(mut self, subgrid_style: ShapeStyle) -> Self {
         self.set_subgrid_style(subgrid_style);
 
         self
     }
 
+    #[inline]
+    /// Configure the grid lines of the time axis.
+    pub fn set_x_grid(&mut self, x_grid: GridConfig) {
+        self.chart.set_x_grid(x_grid)
+    }
+
+    #[inline]
+    /// Configure the grid lines of the time axis. Consumes self.
+    pub fn x_grid(mut self, x_grid: GridConfig) -> Self {
+        self.set_x_grid(x_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Configure the grid lines of the data axis.
+    pub fn set_y_grid(&mut self, y_grid: GridConfig) {
+        self.chart.set_y_grid(y_grid)
+    }
+
+    #[inline]
+    /// Configure the grid lines of the data axis. Consumes self.
+    pub fn y_grid(mut self, y_grid: GridConfig) -> Self {
+        self.set_y_grid(y_grid);
+
+        self
+    }
+
     #[inline]
     /// Set the style of the axes.
     pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
@@ -162,6 +221,537 @@ impl TimeData {
         self
     }
 
+    #[inline]
+    /// Enable/disable the playback trail. When enabled the points yet to be played are shown as
+    /// a faint ghost, a playhead marks the current point, and the chart is kept at the range of
+    /// the full dataset during playback.
+    pub fn set_trail(&mut self, trail: bool) {
+        self.chart.set_trail(trail)
+    }
+
+    #[inline]
+    /// Enable/disable the playback trail. Consumes self.
+    pub fn trail(mut self, trail: bool) -> Self {
+        self.set_trail(trail);
+
+        self
+    }
+
+    #[inline]
+    /// Set how the ranges of the axes follow playback. See [`AutoscaleMode`].
+    pub fn set_autoscale_mode(&mut self, autoscale: AutoscaleMode) {
+        self.chart.set_autoscale_mode(autoscale)
+    }
+
+    #[inline]
+    /// Set how the ranges of the axes follow playback. Consumes self.
+    pub fn autoscale_mode(mut self, autoscale: AutoscaleMode) -> Self {
+        self.set_autoscale_mode(autoscale);
+
+        self
+    }
+
+    #[inline]
+    /// Get how the ranges of the axes follow playback.
+    pub fn get_autoscale_mode(&self) -> AutoscaleMode {
+        self.chart.get_autoscale_mode()
+    }
+
+    #[inline]
+    /// Set the style of the ghost trail. Defaults to a faint version of the line style.
+    pub fn set_ghost_style(&mut self, ghost_style: ShapeStyle) {
+        self.chart.set_ghost_style(ghost_style)
+    }
+
+    #[inline]
+    /// Set the style of the ghost trail. Consumes self.
+    pub fn ghost_style(mut self, ghost_style: ShapeStyle) -> Self {
+        self.set_ghost_style(ghost_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the playhead. Defaults to a filled version of the line style.
+    pub fn set_playhead_style(&mut self, playhead_style: ShapeStyle) {
+        self.chart.set_playhead_style(playhead_style)
+    }
+
+    #[inline]
+    /// Set the style of the playhead. Consumes self.
+    pub fn playhead_style(mut self, playhead_style: ShapeStyle) -> Self {
+        self.set_playhead_style(playhead_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.set_caption(caption)
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening the caption and axis descriptions too long for the chart with
+    /// an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.set_text_truncation(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_text_truncation()
+    }
+
+    #[inline]
+    /// Set the margins around the chart in pixels. The X margin is applied to the left and
+    /// right, and the Y margin to the top and bottom.
+    pub fn set_margins(&mut self, x_margin: i32, y_margin: i32) {
+        self.chart.set_margins(x_margin, y_margin)
+    }
+
+    #[inline]
+    /// Set the margins around the chart in pixels. Consumes self.
+    pub fn margins(mut self, x_margin: i32, y_margin: i32) -> Self {
+        self.set_margins(x_margin, y_margin);
+
+        self
+    }
+
+    #[inline]
+    /// Set the size of the label areas in pixels. The X label area is the one under the X axis,
+    /// and the Y label area the one beside the Y axis.
+    pub fn set_label_area_size(&mut self, x_label_area: i32, y_label_area: i32) {
+        self.chart.set_label_area_size(x_label_area, y_label_area)
+    }
+
+    #[inline]
+    /// Set the size of the label areas in pixels. Consumes self.
+    pub fn label_area_size(mut self, x_label_area: i32, y_label_area: i32) -> Self {
+        self.set_label_area_size(x_label_area, y_label_area);
+
+        self
+    }
+
+    #[inline]
+    /// Set the side of the chart the time axis and its labels are placed on, or hide it.
+    pub fn set_x_axis(&mut self, x_axis: XAxisPosition) {
+        self.chart.set_x_axis(x_axis)
+    }
+
+    #[inline]
+    /// Set the side of the chart the time axis and its labels are placed on, or hide it. Consumes
+    /// self.
+    pub fn x_axis(mut self, x_axis: XAxisPosition) -> Self {
+        self.set_x_axis(x_axis);
+
+        self
+    }
+
+    #[inline]
+    /// Set the side of the chart the data axis and its labels are placed on, or hide it.
+    pub fn set_y_axis(&mut self, y_axis: YAxisPosition) {
+        self.chart.set_y_axis(y_axis)
+    }
+
+    #[inline]
+    /// Set the side of the chart the data axis and its labels are placed on, or hide it. Consumes
+    /// self.
+    pub fn y_axis(mut self, y_axis: YAxisPosition) -> Self {
+        self.set_y_axis(y_axis);
+
+        self
+    }
+
+    #[inline]
+    /// Set the size of the tick marks in pixels.
+    pub fn set_tick_size(&mut self, tick_size: i32) {
+        self.chart.set_tick_size(tick_size)
+    }
+
+    #[inline]
+    /// Set the size of the tick marks in pixels. Consumes self.
+    pub fn tick_size(mut self, tick_size: i32) -> Self {
+        self.set_tick_size(tick_size);
+
+        self
+    }
+
+    #[inline]
+    /// Set the SI units of the X and Y axes, such as "s", "B", or "V". Tick labels of an axis
+    /// with a unit are scaled with an SI prefix to fit its range, such as ms, kB, or MV. Axes
+    /// with None are left unscaled.
+    pub fn set_si_units(&mut self, x_si_unit: Option<&str>, y_si_unit: Option<&str>) {
+        self.chart.set_si_units(x_si_unit, y_si_unit)
+    }
+
+    #[inline]
+    /// Set the SI units of the X and Y axes. Consumes self.
+    pub fn si_units(mut self, x_si_unit: Option<&str>, y_si_unit: Option<&str>) -> Self {
+        self.set_si_units(x_si_unit, y_si_unit);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide an overview strip below the chart. The strip shows every point, with a window
+    /// over the part of the chart in view that can be dragged to pan the chart, or resized by
+    /// its edges to zoom it.
+    pub fn set_minimap(&mut self, minimap: bool) {
+        self.chart.set_minimap(minimap)
+    }
+
+    #[inline]
+    /// Show/hide an overview strip below the chart. Consumes self.
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        self.set_minimap(minimap);
+
+        self
+    }
+
+    #[inline]
+    /// Set the height of the overview strip, in pixels.
+    pub fn set_minimap_height(&mut self, minimap_height: f32) {
+        self.chart.set_minimap_height(minimap_height)
+    }
+
+    #[inline]
+    /// Set the height of the overview strip, in pixels. Consumes self.
+    pub fn minimap_height(mut self, minimap_height: f32) -> Self {
+        self.set_minimap_height(minimap_height);
+
+        self
+    }
+
+    #[inline]
+    /// Set where a table of the points is shown, with a row of time, X, and Y per point. See
+    /// [`DataTableMode`].
+    pub fn set_data_table(&mut self, data_table: DataTableMode) {
+        self.chart.set_data_table(data_table)
+    }
+
+    #[inline]
+    /// Set where a table of the points is shown. Consumes self.
+    pub fn data_table(mut self, data_table: DataTableMode) -> Self {
+        self.set_data_table(data_table);
+
+        self
+    }
+
+    #[inline]
+    /// Get where the table of the points is shown.
+    pub fn get_data_table(&self) -> DataTableMode {
+        self.chart.get_data_table()
+    }
+
+    #[inline]
+    /// Set the width of the table when shown beside the chart, in pixels. Negative and
+    /// non-finite widths are ignored.
+    pub fn set_data_table_width(&mut self, data_table_width: f32) {
+        self.chart.set_data_table_width(data_table_width)
+    }
+
+    #[inline]
+    /// Set the width of the table when shown beside the chart, in pixels. Consumes self.
+    pub fn data_table_width(mut self, data_table_width: f32) -> Self {
+        self.set_data_table_width(data_table_width);
+
+        self
+    }
+
+    #[inline]
+    /// Limit how far the chart can be zoomed, as the smallest and largest span of X and Y values
+    /// that can be shown. Use 0.0 and `f32::INFINITY` to leave a limit open.
+    pub fn set_zoom_limits(
+        &mut self,
+        min_span_x: f32,
+        max_span_x: f32,
+        min_span_y: f32,
+        max_span_y: f32,
+    ) {
+        self.chart
+            .set_zoom_limits(min_span_x, max_span_x, min_span_y, max_span_y)
+    }
+
+    #[inline]
+    /// Limit how far the chart can be zoomed. Consumes self.
+    pub fn zoom_limits(
+        mut self,
+        min_span_x: f32,
+        max_span_x: f32,
+        min_span_y: f32,
+        max_span_y: f32,
+    ) -> Self {
+        self.set_zoom_limits(min_span_x, max_span_x, min_span_y, max_span_y);
+
+        self
+    }
+
+    #[inline]
+    /// Set how carefully the chart is drawn. See [`RenderQuality`].
+    pub fn set_render_quality(&mut self, quality: RenderQuality) {
+        self.chart.set_render_quality(quality)
+    }
+
+    #[inline]
+    /// Set how carefully the chart is drawn. Consumes self.
+    pub fn render_quality(mut self, quality: RenderQuality) -> Self {
+        self.set_render_quality(quality);
+
+        self
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Set the controller lowering the chart's quality while it's too slow to draw, during
+    /// playback as well as while it's moved. See [`AdaptiveQuality`].
+    pub fn set_adaptive_quality(&mut self, adaptive: Option<AdaptiveQuality>) {
+        self.chart.set_adaptive_quality(adaptive)
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Set the controller lowering the chart's quality while it's too slow to draw. Consumes
+    /// self.
+    pub fn adaptive_quality(mut self, adaptive: Option<AdaptiveQuality>) -> Self {
+        self.set_adaptive_quality(adaptive);
+
+        self
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Get the controller lowering the chart's quality, if any, along with how long the chart
+    /// takes to draw.
+    pub fn get_adaptive_quality(&self) -> Option<&AdaptiveQuality> {
+        self.chart.get_adaptive_quality()
+    }
+
+    #[inline]
+    /// Remove the zoom limits.
+    pub fn clear_zoom_limits(&mut self) {
+        self.chart.clear_zoom_limits()
+    }
+
+    #[inline]
+    /// Limit how far the chart can be dragged, keeping the center of the view within the given
+    /// X and Y ranges.
+    pub fn set_pan_bounds(&mut self, x_range: Range<f32>, y_range: Range<f32>) {
+        self.chart.set_pan_bounds(x_range, y_range)
+    }
+
+    #[inline]
+    /// Limit how far the chart can be dragged. Consumes self.
+    pub fn pan_bounds(mut self, x_range: Range<f32>, y_range: Range<f32>) -> Self {
+        self.set_pan_bounds(x_range, y_range);
+
+        self
+    }
+
+    #[inline]
+    /// Remove the pan bounds.
+    pub fn clear_pan_bounds(&mut self) {
+        self.chart.clear_pan_bounds()
+    }
+
+    #[inline]
+    /// Show/hide badges at the edges of the plot counting the points clipped out of view past
+    /// them. Clicking a badge fits the view to include those points.
+    pub fn set_clip_indicators(&mut self, clip_indicators: bool) {
+        self.chart.set_clip_indicators(clip_indicators)
+    }
+
+    #[inline]
+    /// Show/hide badges counting the points clipped out of view. Consumes self.
+    pub fn clip_indicators(mut self, clip_indicators: bool) -> Self {
+        self.set_clip_indicators(clip_indicators);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable fitting an axis to the data in view when its labels are double-clicked.
+    pub fn set_axis_autoscale(&mut self, axis_autoscale: bool) {
+        self.chart.set_axis_autoscale(axis_autoscale)
+    }
+
+    #[inline]
+    /// Enable/disable fitting an axis to the data in view when its labels are double-clicked.
+    /// Consumes self.
+    pub fn axis_autoscale(mut self, axis_autoscale: bool) -> Self {
+        self.set_axis_autoscale(axis_autoscale);
+
+        self
+    }
+
+    #[inline]
+    /// Set the callback drawing on top of the chart after it is drawn. The callback is given a
+    /// painter clipped to the chart and a [`ScreenTransform`](crate::ScreenTransform) between
+    /// time/value coordinates and the screen.
+    pub fn set_on_overlay(&mut self, overlay_cb: OverlayCallback) {
+        self.chart.set_on_overlay(overlay_cb)
+    }
+
+    #[inline]
+    /// Set the callback drawing on top of the chart after it is drawn. Consumes self.
+    pub fn on_overlay(mut self, overlay_cb: OverlayCallback) -> Self {
+        self.set_on_overlay(overlay_cb);
+
+        self
+    }
+
+    #[inline]
+    /// Set the callback drawing beneath the chart before its axes and points are drawn. The
+    /// callback is given a painter clipped to the chart, the rect of the plot between the axes
+    /// on screen, and a [`ScreenTransform`](crate::ScreenTransform) between time/value
+    /// coordinates and the screen.
+    pub fn set_on_background(&mut self, background_cb: BackgroundCallback) {
+        self.chart.set_on_background(background_cb)
+    }
+
+    #[inline]
+    /// Set the callback drawing beneath the chart before it is drawn. Consumes self.
+    pub fn on_background(mut self, background_cb: BackgroundCallback) -> Self {
+        self.set_on_background(background_cb);
+
+        self
+    }
+
+    #[inline]
+    /// Zoom the X axis to fit the plotted points within the Y range in view.
+    pub fn zoom_to_fit_x(&mut self) {
+        self.chart.zoom_to_fit_x()
+    }
+
+    #[inline]
+    /// Zoom the Y axis to fit the plotted points within the X range in view.
+    pub fn zoom_to_fit_y(&mut self) {
+        self.chart.zoom_to_fit_y()
+    }
+
+    #[inline]
+    /// Zoom the X axis to fit the `low` to `high` percentile of the plotted points within the Y
+    /// range in view.
+    pub fn zoom_to_fit_x_percentile(&mut self, low: f32, high: f32) {
+        self.chart.zoom_to_fit_x_percentile(low, high)
+    }
+
+    #[inline]
+    /// Zoom the Y axis to fit the `low` to `high` percentile of the plotted points within the X
+    /// range in view.
+    pub fn zoom_to_fit_y_percentile(&mut self, low: f32, high: f32) {
+        self.chart.zoom_to_fit_y_percentile(low, high)
+    }
+
+    #[inline]
+    /// Undo any zooming to fit, dragging, and zooming, showing the whole range of the data again.
+    pub fn reset_view(&mut self) {
+        self.chart.reset_view()
+    }
+
+    #[inline]
+    /// Capture the view, playback position, parts shown, and bookmarks of the chart. See
+    /// [`TimeChartState`].
+    pub fn state(&self) -> TimeChartState {
+        self.chart.state()
+    }
+
+    #[inline]
+    /// Restore the chart to a captured state. Playback is moved to the time captured, along
+    /// with the rest of the chart's playback group.
+    pub fn restore(&mut self, state: &TimeChartState) {
+        self.chart.restore(state)
+    }
+
+    #[inline]
+    /// Get the rect of the plot between the axes on screen, as laid out the last frame drawn.
+    /// None if the chart hasn't been drawn yet.
+    pub fn get_plot_rect(&self) -> Option<Rect> {
+        self.chart.get_plot_rect()
+    }
+
+    #[inline]
+    /// Color the line along time with a colormap, going from the start of the colormap at the
+    /// first point to its end at the last. Set to None to go back to the line style's color.
+    pub fn set_line_colormap<C>(&mut self, colormap: Option<C>)
+    where
+        C: Colormap + 'static,
+    {
+        self.chart.set_line_colormap(colormap)
+    }
+
+    #[inline]
+    /// Color the line along time with a colormap. Consumes self.
+    pub fn line_colormap<C>(mut self, colormap: Option<C>) -> Self
+    where
+        C: Colormap + 'static,
+    {
+        self.set_line_colormap(colormap);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable decimation. When enabled and there are more points than pixel columns,
+    /// the points in each column are reduced to a single point of the line and, unless disabled
+    /// with `.set_envelope()`, an envelope showing their range.
+    pub fn set_decimation(&mut self, decimation: bool) {
+        self.chart.set_decimation(decimation)
+    }
+
+    #[inline]
+    /// Enable/disable decimation. Consumes self.
+    pub fn decimation(mut self, decimation: bool) -> Self {
+        self.set_decimation(decimation);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide the min/max envelope drawn around decimated lines. Shown by default.
+    pub fn set_envelope(&mut self, envelope: bool) {
+        self.chart.set_envelope(envelope)
+    }
+
+    #[inline]
+    /// Show/hide the min/max envelope drawn around decimated lines. Consumes self.
+    pub fn envelope(mut self, envelope: bool) -> Self {
+        self.set_envelope(envelope);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the min/max envelope. Defaults to a faint fill of the line color.
+    pub fn set_envelope_style(&mut self, envelope_style: ShapeStyle) {
+        self.chart.set_envelope_style(envelope_style)
+    }
+
+    #[inline]
+    /// Set the style of the min/max envelope. Consumes self.
+    pub fn envelope_style(mut self, envelope_style: ShapeStyle) -> Self {
+        self.set_envelope_style(envelope_style);
+
+        self
+    }
+
     #[inline]
     /// Set the ratio between X and Y values, default being 1 x unit to 1 y unit.
     pub fn set_ratio(&mut self, ratio: f32) {
@@ -176,18 +766,120 @@ impl TimeData {
         self
     }
 
+    #[inline]
+    /// Skip a range of values, such as 100.0..10_000.0, drawing the Y axis with a break in their
+    /// place. Not broken if None.
+    pub fn set_y_break(&mut self, y_break: Option<Range<f32>>) {
+        self.chart.set_y_break(y_break)
+    }
+
+    #[inline]
+    /// Skip a range of values, drawing the Y axis with a break in their place. Consumes self.
+    pub fn y_break(mut self, y_break: Option<Range<f32>>) -> Self {
+        self.set_y_break(y_break);
+
+        self
+    }
+
+    #[inline]
+    /// Get the range of values skipped by the Y axis, if any.
+    pub fn get_y_break(&self) -> Option<Range<f32>> {
+        self.chart.get_y_break()
+    }
+
     /// Draw the chart to a Ui. Will also proceed to animate the chart if playback is currently
     /// enabled.
-    pub fn draw<'a>(&mut self, ui: &'a mut Ui) {
+    pub fn draw(&mut self, ui: &mut Ui) {
         self.chart.draw(ui)
     }
 
-    /// Start/enable playback of the chart.
+    #[inline]
+    /// Draw the chart to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.chart.draw_headless(backend)
+    }
+
+    /// Draw the chart to a Ui as it would appear at the given time, bypassing the chart's own
+    /// playback clock. Time is in seconds and is clamped to the start and end times of the chart.
+    #[inline]
+    pub fn draw_at_time(&mut self, ui: &mut Ui, time: f32) {
+        self.chart.draw_at_time(ui, time)
+    }
+
+    #[inline]
+    /// Bookmark a time, marking the point shown at it on the chart and the overview strip.
+    /// Replaces any bookmark with the same name.
+    pub fn add_bookmark(&mut self, name: &str, time: f32) {
+        self.chart.add_bookmark(name, time)
+    }
+
+    #[inline]
+    /// Bookmark a time. Consumes self.
+    pub fn bookmark(mut self, name: &str, time: f32) -> Self {
+        self.add_bookmark(name, time);
+
+        self
+    }
+
+    #[inline]
+    /// Remove the bookmark with the given name, if there is one.
+    pub fn remove_bookmark(&mut self, name: &str) {
+        self.chart.remove_bookmark(name)
+    }
+
+    #[inline]
+    /// Remove every bookmark.
+    pub fn clear_bookmarks(&mut self) {
+        self.chart.clear_bookmarks()
+    }
+
+    #[inline]
+    /// Get the time of the bookmark with the given name.
+    pub fn get_bookmark(&self, name: &str) -> Option<f32> {
+        self.chart.get_bookmark(name)
+    }
+
+    #[inline]
+    /// Move playback to the time of the bookmark with the given name, pausing there if playback
+    /// isn't underway. Returns false if there's no such bookmark.
+    pub fn jump_to_bookmark(&mut self, name: &str) -> bool {
+        self.chart.jump_to_bookmark(name)
+    }
+
+    /// Return the currently plotted points in view as CSV, with a header row made from the time
+    /// and the units.
+    #[inline]
+    pub fn visible_data_csv(&self) -> String {
+        self.chart.visible_data_csv()
+    }
+
+    /// Write the points to a CSV file at `path`, with a header row made from the time and the
+    /// units. If a time range is provided only the points within it are written.
+    #[inline]
+    pub fn export_csv<P>(&self, path: P, range: Option<Range<f32>>) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.chart.export_csv(path, range)
+    }
+
+    /// Start/enable playback of the chart. Does nothing if the chart has nothing to animate.
     #[inline]
     pub fn start_playback(&mut self) {
         self.chart.start_playback()
     }
 
+    /// Start/enable playback of the chart, returning [`PlaybackError::ZeroDuration`] if the
+    /// chart has nothing to animate, such as when every point is at the same time.
+    #[inline]
+    pub fn try_start_playback(&mut self) -> Result<(), PlaybackError> {
+        self.chart.try_start_playback()
+    }
+
     /// Stop/disable playback of the chart.
     #[inline]
     pub fn stop_playback(&mut self) {
@@ -228,4 +920,93 @@ impl TimeData {
     pub fn get_playback_speed(&self) -> f32 {
         self.chart.get_playback_speed()
     }
+
+    /// Join a playback group, sharing its playback clock with every other member.
+    #[inline]
+    pub fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        self.chart.set_playback_group(group)
+    }
+
+    /// Join a playback group, sharing its playback clock with every other member. Consumes self.
+    #[inline]
+    pub fn playback_group(mut self, group: &PlaybackGroup) -> Self {
+        self.set_playback_group(group);
+
+        self
+    }
+
+    /// Leave the current playback group, if any, and go back to an independent playback clock.
+    #[inline]
+    pub fn leave_playback_group(&mut self) {
+        self.chart.leave_playback_group()
+    }
+
+    /// Set the seconds of playback before the chart starts animating, delaying it behind the
+    /// other members of its playback group. Negative offsets skip the start of the chart instead.
+    #[inline]
+    pub fn set_playback_offset(&mut self, offset: f32) {
+        self.chart.set_playback_offset(offset)
+    }
+
+    /// Set the seconds of playback before the chart starts animating. Consumes self.
+    #[inline]
+    pub fn playback_offset(mut self, offset: f32) -> Self {
+        self.set_playback_offset(offset);
+
+        self
+    }
+
+    /// Get the seconds of playback before the chart starts animating.
+    #[inline]
+    pub fn get_playback_offset(&self) -> f32 {
+        self.chart.get_playback_offset()
+    }
+
+    #[inline]
+    /// Join a link group, highlighting the points at the times hovered and selected in any chart
+    /// of the group.
+    pub fn set_link_group(&mut self, group: &LinkGroup) {
+        self.chart.set_link_group(group)
+    }
+
+    #[inline]
+    /// Join a link group. Consumes self.
+    pub fn link_group(mut self, group: &LinkGroup) -> Self {
+        self.set_link_group(group);
+
+        self
+    }
+
+    #[inline]
+    /// Leave the current link group, if any.
+    pub fn leave_link_group(&mut self) {
+        self.chart.leave_link_group()
+    }
+
+    #[inline]
+    /// Set how the time of the data is compressed during playback. See [`TimeMapping`].
+    pub fn set_time_mapping(&mut self, mapping: TimeMapping) {
+        self.chart.set_time_mapping(mapping)
+    }
+
+    #[inline]
+    /// Set how the time of the data is compressed during playback. Consumes self.
+    pub fn time_mapping(mut self, mapping: TimeMapping) -> Self {
+        self.set_time_mapping(mapping);
+
+        self
+    }
+
+    #[inline]
+    /// Return how the time of the data is compressed during playback.
+    pub fn get_time_mapping(&self) -> TimeMapping {
+        self.chart.get_time_mapping()
+    }
+
+    #[inline]
+    /// Get the `XyTimeData` chart the chart is drawn with, such as for binding a
+    /// [`Gauge`](crate::charts::Gauge) to it.
+    pub fn as_xy(&self) -> &XyTimeData {
+        &self.chart
+    }
 }
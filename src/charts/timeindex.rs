@@ -0,0 +1,216 @@
+//! Lookups into time-ordered points, fast enough to run every frame of a playback through
+//! hundreds of thousands of points.
+
+use std::{cell::Cell, ops::Range};
+
+/// Number of points between the checkpoints of [`Extents`]. Lookups scan at most this many
+/// points past a checkpoint, while the checkpoints take a fraction of the memory of the points.
+const CHECKPOINT_INTERVAL: usize = 64;
+
+#[derive(Debug, Copy, Clone)]
+/// Smallest and largest X and Y values of some points.
+struct Bounds {
+    min_x: f32,
+    max_x: f32,
+    min_y: f32,
+    max_y: f32,
+}
+
+impl Bounds {
+    /// Bounds of no points, which any point extends.
+    const EMPTY: Self = Self {
+        min_x: f32::MAX,
+        max_x: f32::MIN,
+        min_y: f32::MAX,
+        max_y: f32::MIN,
+    };
+
+    #[inline]
+    fn extend(&mut self, (x, y): (f32, f32)) {
+        self.min_x = self.min_x.min(x);
+        self.max_x = self.max_x.max(x);
+        self.min_y = self.min_y.min(y);
+        self.max_y = self.max_y.max(y);
+    }
+
+    #[inline]
+    fn ranges(&self) -> (Range<f32>, Range<f32>) {
+        (self.min_x..self.max_x, self.min_y..self.max_y)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// X and Y ranges of every prefix of some points, as shown at each step of a playback.
+///
+/// Rather than storing the ranges at every point, they are checkpointed every
+/// [`CHECKPOINT_INTERVAL`] points and the points since the last checkpoint are scanned on lookup.
+pub(crate) struct Extents {
+    /// Bounds of the points up to and including every `CHECKPOINT_INTERVAL`th one.
+    checkpoints: Vec<Bounds>,
+    /// Ranges of every point. None if there are no points.
+    total: Option<(Range<f32>, Range<f32>)>,
+}
+
+impl Extents {
+    /// Checkpoint the ranges of some points.
+    pub(crate) fn new(points: &[(f32, f32)]) -> Self {
+        let mut checkpoints = Vec::with_capacity(points.len() / CHECKPOINT_INTERVAL + 1);
+        let mut bounds = Bounds::EMPTY;
+
+        for (index, point) in points.iter().enumerate() {
+            bounds.extend(*point);
+
+            if index % CHECKPOINT_INTERVAL == 0 {
+                checkpoints.push(bounds);
+            }
+        }
+
+        let total = (!points.is_empty()).then(|| bounds.ranges());
+
+        Self { checkpoints, total }
+    }
+
+    /// X and Y ranges of the points up to and including the one at `index`. `points` must be the
+    /// points the extents were created from.
+    pub(crate) fn prefix(&self, points: &[(f32, f32)], index: usize) -> (Range<f32>, Range<f32>) {
+        let checkpoint = index / CHECKPOINT_INTERVAL;
+
+        let Some(mut bounds) = self.checkpoints.get(checkpoint).copied() else {
+            return Bounds::EMPTY.ranges();
+        };
+
+        let start = checkpoint * CHECKPOINT_INTERVAL + 1;
+        let end = (index + 1).min(points.len());
+
+        for point in points.get(start..end).unwrap_or_default() {
+            bounds.extend(*point);
+        }
+
+        bounds.ranges()
+    }
+
+    #[inline]
+    /// X and Y ranges of every point. None if there are no points.
+    pub(crate) fn total(&self) -> Option<(Range<f32>, Range<f32>)> {
+        self.total.clone()
+    }
+}
+
+#[derive(Debug, Default)]
+/// Remembers the last index looked up with [`time_index`], so the lookups of a playback, which
+/// only moves a little each frame, rarely have to search.
+pub(crate) struct TimeIndexCache {
+    last: Cell<usize>,
+}
+
+impl TimeIndexCache {
+    /// Index of the last point shown at the given time, the same as [`time_index`].
+    pub(crate) fn lookup(&self, times: &[f32], time: f32) -> usize {
+        let last = self.last.get();
+
+        // Try the cached index and the one after it before searching
+        let index = [last, last + 1]
+            .into_iter()
+            .find(|&index| is_time_index(times, time, index))
+            .unwrap_or_else(|| time_index(times, time));
+
+        self.last.set(index);
+
+        index
+    }
+}
+
+/// Return true if `index` is where [`time_index`] would find the given time.
+fn is_time_index(times: &[f32], time: f32, index: usize) -> bool {
+    let Some(&at) = times.get(index) else {
+        return false;
+    };
+
+    let shown = index == 0 || at <= time;
+    let next_shown = times.get(index + 1).is_some_and(|next| *next <= time);
+
+    shown && !next_shown
+}
+
+/// Index of the last point shown at the given time, the last whose time isn't past it. Points
+/// sharing a time are all shown together. The first point is shown from the start on.
+pub(crate) fn time_index(times: &[f32], time: f32) -> usize {
+    times.partition_point(|at| *at <= time).saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Index found by scanning every time, for checking the searches against.
+    fn scanned_index(times: &[f32], time: f32) -> usize {
+        times.iter().rposition(|at| *at <= time).unwrap_or_default()
+    }
+
+    #[test]
+    fn time_index_finds_the_last_point_shown() {
+        let times = [0.0, 1.0, 2.0, 3.0];
+
+        assert_eq!(time_index(&times, -1.0), 0);
+        assert_eq!(time_index(&times, 0.0), 0);
+        assert_eq!(time_index(&times, 0.5), 0);
+        assert_eq!(time_index(&times, 1.0), 1);
+        assert_eq!(time_index(&times, 2.9), 2);
+        assert_eq!(time_index(&times, 3.0), 3);
+        assert_eq!(time_index(&times, 10.0), 3);
+        assert_eq!(time_index(&[], 1.0), 0);
+    }
+
+    #[test]
+    fn cache_agrees_with_search_on_duplicate_times() {
+        let times = [0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 3.0];
+        let cache = TimeIndexCache::default();
+
+        // Step forward as a playback would, then jump back and forth
+        let steps = (0..=40).map(|step| step as f32 * 0.1 - 0.5);
+        let jumps = [3.0, 1.0, 0.0, 2.0, 1.0, 1.0, -1.0, 2.5];
+
+        for time in steps.chain(jumps) {
+            let searched = time_index(&times, time);
+
+            assert_eq!(searched, scanned_index(&times, time), "time {time}");
+            assert_eq!(cache.lookup(&times, time), searched, "time {time}");
+        }
+
+        assert_eq!(time_index(&times, 1.0), 3);
+        assert_eq!(time_index(&times, 2.0), 5);
+    }
+
+    #[test]
+    fn prefix_matches_a_scan_of_the_points() {
+        let points: Vec<(f32, f32)> = (0..300)
+            .map(|index| {
+                let index = index as f32;
+
+                ((index * 0.37).sin() * index, (index * 1.3).cos() * 50.0)
+            })
+            .collect();
+
+        let extents = Extents::new(&points);
+
+        for index in [0, 1, 63, 64, 65, 127, 128, 200, 299] {
+            let mut bounds = Bounds::EMPTY;
+
+            for point in &points[..=index] {
+                bounds.extend(*point);
+            }
+
+            assert_eq!(
+                extents.prefix(&points, index),
+                bounds.ranges(),
+                "index {index}"
+            );
+        }
+
+        assert_eq!(
+            extents.total(),
+            Some(extents.prefix(&points, points.len() - 1))
+        );
+        assert_eq!(Extents::new(&[]).total(), None);
+    }
+}
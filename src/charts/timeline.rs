@@ -0,0 +1,923 @@
+//! Gantt-style timeline chart showing labeled intervals on rows.
+
+use std::{
+    cell::{Cell, RefCell},
+    ops::Range,
+    rc::Rc,
+    sync::Arc,
+};
+
+use egui::Ui;
+use plotters::{
+    coord::ReverseCoordTranslate,
+    element::{PathElement, Rectangle, Text},
+    prelude::{ChartBuilder, LabelAreaPosition},
+    style::{
+        full_palette::{GREY_700, RED_900},
+        Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK,
+    },
+};
+use plotters_backend::{
+    text_anchor::{HPos, Pos, VPos},
+    DrawingBackend, DrawingErrorKind, FontFamily, FontStyle,
+};
+
+use crate::{
+    charts::{
+        grid::{draw_grid, GridConfig},
+        hover::{draw_tooltip, hover_pos, untransform},
+        link::LinkGroup,
+        playback::{Playback, PlaybackError, PlaybackGroup},
+        textfit::TextFit,
+        units::si_axis,
+    },
+    locale::locale,
+    palette::{default_palette, Palette},
+    Chart,
+};
+
+const DEFAULT_MARGIN: i32 = 25;
+const DEFAULT_X_LABEL_AREA: i32 = 25;
+const DEFAULT_ROW_LABEL_AREA: i32 = 80;
+const CAPTION_SIZE: i32 = 10;
+/// Height of every span, relative to the space between rows.
+const SPAN_HEIGHT: f32 = 0.7;
+/// Padding between a span's label and its edges, in pixels.
+const LABEL_PADDING: i32 = 3;
+/// Smallest width of the visible time window, relative to the whole timeline.
+const MIN_VIEW_WIDTH: f32 = 0.001;
+const DEFAULT_ZOOM_SCALE: f32 = 0.002;
+/// Opacity of the line across the time hovered in a linked chart.
+const LINK_HOVER_OPACITY: f64 = 0.5;
+
+#[derive(Clone)]
+struct Span {
+    /// Index of the row the span is on.
+    row: usize,
+    /// Label shown on the span.
+    label: Arc<str>,
+    /// Start and end time of the span.
+    range: Range<f32>,
+    /// Color of the span. Taken from the palette by row if None.
+    color: Option<RGBAColor>,
+}
+
+#[derive(Clone)]
+struct TimelineConfig {
+    /// Names of the rows, from top to bottom.
+    rows: Arc<[Arc<str>]>,
+    /// Every span on the timeline.
+    spans: Arc<[Span]>,
+    /// Time window shown.
+    view: Range<f32>,
+    /// Time the playhead is at. Hidden if None.
+    playhead: Option<f32>,
+    /// Style of the playhead.
+    playhead_style: ShapeStyle,
+    /// Grid lines of the time axis.
+    x_grid: GridConfig,
+    /// Style of the axes.
+    axes_style: ShapeStyle,
+    /// Style of the text
+    text_color: RGBAColor,
+    /// Unit of the time axis.
+    x_unit: Arc<str>,
+    /// SI unit of the time axis, its labels scaled with a prefix to fit the view. Unscaled if
+    /// None.
+    x_si_unit: Option<Arc<str>>,
+    /// Caption of the chart. Hidden if None.
+    caption: Option<Arc<str>>,
+    /// Caption and axis description shortened to fit the chart.
+    text_fit: TextFit,
+    /// Palette spans without a color are colored from. Uses the default palette if None.
+    palette: Option<Palette>,
+    /// Width of the area the row names are shown in.
+    row_label_area: i32,
+    /// Show the details of the hovered span.
+    hover: bool,
+    /// Position of the pointer relative to the chart, if it is hovering over it.
+    pointer: Option<(f32, f32)>,
+    /// Times hovered and selected in the link group.
+    link_hovered: Option<f32>,
+    link_selected: Option<f32>,
+    /// Time under the pointer as of the last draw, shared with the link group.
+    pointer_time: Cell<Option<f32>>,
+}
+
+impl TimelineConfig {
+    /// Y coordinate of the center of a row. Rows are listed from the top.
+    fn row_center(&self, row: usize) -> f32 {
+        (self.rows.len() - 1 - row) as f32
+    }
+}
+
+/// Animatable Gantt-style timeline.
+///
+/// ## Usage
+/// **Ensure the `timechart` feature is enabled to use this type.**
+///
+/// Creating the chart only requires the spans to be shown and a caption.
+///
+///  * `spans`: A slice of tuples, arranged so that the first element is the name of the row
+///    the span is on, the second is the span's label, and the third is its start and end time.
+///    Rows are listed from the top in the order they first appear, and their spans are colored
+///    from the chart's [`Palette`](crate::palette::Palette).
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// Dragging the timeline pans it along the time axis, and scrolling zooms in and out around the
+/// pointer. Hovering over a span shows its start, end, and duration. Playback moves a playhead
+/// across the timeline, and like the other time charts the timeline can join a [`PlaybackGroup`]
+/// to keep its playhead in sync with them.
+pub struct Timeline {
+    playback: Rc<RefCell<Playback>>,
+    /// Start and end time of every span together.
+    range: Range<f32>,
+    zoom_scale: f32,
+    /// Link group joined, along with the member the timeline is known as in it.
+    link: Option<(LinkGroup, usize)>,
+    chart: Chart<TimelineConfig>,
+}
+
+impl Timeline {
+    /// Create a new Timeline. See [Usage](#usage).
+    pub fn new(spans: &[(&str, &str, Range<f32>)], caption: &str) -> Self {
+        let playhead_style = ShapeStyle {
+            color: RED_900.to_rgba(),
+            filled: false,
+            stroke_width: 2,
+        };
+
+        let config = TimelineConfig {
+            rows: Arc::new([]),
+            spans: Arc::new([]),
+            view: 0.0..1.0,
+            playhead: None,
+            playhead_style,
+            x_grid: GridConfig::default().minor(false),
+            axes_style: GREY_700.into(),
+            text_color: BLACK.to_rgba(),
+            x_unit: "".into(),
+            x_si_unit: None,
+            caption: Some(caption.into()),
+            text_fit: TextFit::default(),
+            palette: None,
+            row_label_area: DEFAULT_ROW_LABEL_AREA,
+            hover: true,
+            pointer: None,
+            link_hovered: None,
+            link_selected: None,
+            pointer_time: Cell::new(None),
+        };
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, transform, data| {
+            data.text_fit.clear();
+
+            let row_count = data.rows.len();
+
+            data.pointer_time.set(None);
+
+            // Nothing to show without rows
+            if row_count == 0 {
+                return;
+            }
+
+            let font_desc = FontDesc::new(
+                FontFamily::Monospace,
+                CAPTION_SIZE as f64,
+                FontStyle::Normal,
+            );
+
+            let text_style = TextStyle::from(font_desc).color(&data.text_color);
+
+            let mut builder = ChartBuilder::on(area);
+
+            builder
+                .margin(DEFAULT_MARGIN)
+                .x_label_area_size(DEFAULT_X_LABEL_AREA)
+                .y_label_area_size(data.row_label_area);
+
+            if let Some(caption) = &data.caption {
+                let margins = (DEFAULT_MARGIN, DEFAULT_MARGIN);
+                let caption = data.text_fit.caption(area, margins, caption, &text_style);
+
+                builder.caption(caption, text_style.clone());
+            }
+
+            let y_range = -0.5..(row_count as f32 - 0.5);
+
+            let mut chart = match builder.build_cartesian_2d(data.view.clone(), y_range) {
+                Ok(chart) => chart,
+                // Area too small to be drawn
+                Err(_) => return,
+            };
+
+            draw_grid(&mut chart, &data.x_grid, &GridConfig::disabled());
+
+            // Only label the row centers
+            let row_label = |y: &f32| {
+                let center = y.round();
+
+                match (y - center).abs() < f32::EPSILON && center >= 0.0 {
+                    true => row_count
+                        .checked_sub(1 + center as usize)
+                        .and_then(|row| data.rows.get(row))
+                        .map_or_else(String::new, |row| row.to_string()),
+                    false => String::new(),
+                }
+            };
+
+            let locale = locale();
+            let (x_scale, x_desc) = si_axis(&data.view, &data.x_unit, data.x_si_unit.as_deref());
+            let x_desc = data.text_fit.axis_desc(
+                chart.plotting_area(),
+                LabelAreaPosition::Bottom,
+                DEFAULT_X_LABEL_AREA,
+                &x_desc,
+                &text_style,
+            );
+            let x_label = |x: &f32| {
+                x_scale.map_or_else(|| locale.format_tick(*x as f64), |scale| scale.format(*x))
+            };
+
+            let mut mesh = chart.configure_mesh();
+
+            mesh.disable_mesh()
+                .x_labels(data.x_grid.get_major_lines())
+                .y_labels(row_count)
+                .y_label_formatter(&row_label)
+                .label_style(text_style.clone())
+                .axis_style(data.axes_style)
+                .x_desc(x_desc)
+                .x_label_formatter(&x_label);
+
+            mesh.draw().unwrap();
+
+            let view = data.view.clone();
+
+            // Spans are cut to the time window, as nothing outside of it is clipped
+            let visible = |span: &Span| {
+                let start = span.range.start.max(view.start);
+                let end = span.range.end.min(view.end);
+
+                (start < end).then_some(start..end)
+            };
+
+            let palette = data.palette.clone().unwrap_or_else(default_palette);
+
+            let rectangles = data.spans.iter().filter_map(|span| {
+                let range = visible(span)?;
+                let center = data.row_center(span.row);
+
+                Some(Rectangle::new(
+                    [
+                        (range.start, center - SPAN_HEIGHT / 2.0),
+                        (range.end, center + SPAN_HEIGHT / 2.0),
+                    ],
+                    span.color
+                        .unwrap_or_else(|| palette.color(span.row))
+                        .filled(),
+                ))
+            });
+
+            chart.draw_series(rectangles).unwrap();
+
+            // Label the spans wide enough to fit their label
+            let label_style = text_style.pos(Pos::new(HPos::Left, VPos::Center));
+
+            for span in data.spans.iter() {
+                let Some(range) = visible(span) else {
+                    continue;
+                };
+
+                let center = data.row_center(span.row);
+
+                let (left, y) = chart.backend_coord(&(range.start, center));
+                let (right, _) = chart.backend_coord(&(range.end, center));
+
+                let label_width = span.label.chars().count() as i32 * CAPTION_SIZE * 3 / 5;
+
+                if right - left >= label_width + LABEL_PADDING * 2 {
+                    area.draw(&Text::new(
+                        span.label.to_string(),
+                        (left + LABEL_PADDING, y),
+                        label_style.clone(),
+                    ))
+                    .unwrap();
+                }
+            }
+
+            if let Some(time) = data.playhead.filter(|time| view.contains(time)) {
+                let playhead = PathElement::new(
+                    vec![(time, -0.5), (time, row_count as f32 - 0.5)],
+                    data.playhead_style,
+                );
+
+                chart.draw_series([playhead]).unwrap();
+            }
+
+            // Lines across the times hovered and selected in linked charts
+            let linked = [
+                (data.link_hovered, data.text_color.mix(LINK_HOVER_OPACITY)),
+                (data.link_selected, data.text_color),
+            ];
+
+            for (time, color) in linked {
+                if let Some(time) = time.filter(|time| view.contains(time)) {
+                    let line =
+                        PathElement::new(vec![(time, -0.5), (time, row_count as f32 - 0.5)], color);
+
+                    chart.draw_series([line]).unwrap();
+                }
+            }
+
+            // Find the time and the span under the pointer, undoing the chart's transform
+            let pointer = data.pointer.and_then(|pos| {
+                chart
+                    .as_coord_spec()
+                    .reverse_translate(untransform(area, transform, pos))
+            });
+
+            data.pointer_time.set(pointer.map(|(time, _)| time));
+
+            let hovered = pointer.filter(|_| data.hover).and_then(|(time, y)| {
+                data.spans.iter().rev().find(|span| {
+                    let center = data.row_center(span.row);
+
+                    span.range.contains(&time) && (y - center).abs() <= SPAN_HEIGHT / 2.0
+                })
+            });
+
+            let Some(span) = hovered else {
+                return;
+            };
+
+            let lines = vec![
+                format!("{} ({})", span.label, data.rows[span.row]),
+                format!("Start: {}", locale.format_number(span.range.start)),
+                format!("End: {}", locale.format_number(span.range.end)),
+                format!(
+                    "Duration: {}",
+                    locale.format_number(span.range.end - span.range.start)
+                ),
+            ];
+
+            let center = data.row_center(span.row);
+            let time = span.range.start.max(view.start);
+            let pos = chart.backend_coord(&(time, center - SPAN_HEIGHT / 2.0));
+
+            draw_tooltip(area, pos, lines, &text_style);
+        }));
+
+        let mut timeline = Self {
+            playback: Rc::new(RefCell::new(Playback::new(0.0))),
+            range: 0.0..0.0,
+            zoom_scale: DEFAULT_ZOOM_SCALE,
+            link: None,
+            chart,
+        };
+
+        for (row, label, range) in spans {
+            timeline.push_span(row, label, range.clone(), None);
+        }
+
+        timeline.reset_view();
+
+        timeline
+    }
+
+    #[inline]
+    /// Add a span to a row, adding the row below the others if it doesn't exist yet.
+    pub fn add_span<T>(&mut self, row: &str, label: &str, range: Range<f32>, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.push_span(row, label, range, Some(color.into()))
+    }
+
+    #[inline]
+    /// Add a span to a row, colored by the row's position in the chart's palette.
+    pub fn add_palette_span(&mut self, row: &str, label: &str, range: Range<f32>) {
+        self.push_span(row, label, range, None)
+    }
+
+    #[inline]
+    /// Add a span to a row, colored by the row's position in the chart's palette. Consumes self.
+    pub fn palette_span(mut self, row: &str, label: &str, range: Range<f32>) -> Self {
+        self.add_palette_span(row, label, range);
+
+        self
+    }
+
+    fn push_span(&mut self, row: &str, label: &str, range: Range<f32>, color: Option<RGBAColor>) {
+        let first = self.chart.get_data().spans.is_empty();
+        let config = self.chart.get_data_mut();
+
+        let row = match config.rows.iter().position(|name| **name == *row) {
+            Some(index) => index,
+            None => {
+                let mut rows = config.rows.to_vec();
+                rows.push(row.into());
+
+                config.rows = rows.into();
+
+                config.rows.len() - 1
+            }
+        };
+
+        self.range = match first {
+            true => range.clone(),
+            false => self.range.start.min(range.start)..self.range.end.max(range.end),
+        };
+
+        let mut spans = config.spans.to_vec();
+        spans.push(Span {
+            row,
+            label: label.into(),
+            range,
+            color,
+        });
+
+        config.spans = spans.into();
+
+        self.playback
+            .borrow_mut()
+            .extend_duration(self.range.end - self.range.start);
+    }
+
+    #[inline]
+    /// Add a span to a row. Consumes self.
+    pub fn span<T>(mut self, row: &str, label: &str, range: Range<f32>, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.add_span(row, label, range, color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the palette spans without a color are colored from. Set to None to use the default
+    /// palette.
+    pub fn set_palette(&mut self, palette: Option<Palette>) {
+        self.chart.get_data_mut().palette = palette
+    }
+
+    #[inline]
+    /// Set the palette spans without a color are colored from. Consumes self.
+    pub fn palette(mut self, palette: Option<Palette>) -> Self {
+        self.set_palette(palette);
+
+        self
+    }
+
+    #[inline]
+    /// Set the time window shown.
+    pub fn set_view(&mut self, view: Range<f32>) {
+        self.chart.get_data_mut().view = view
+    }
+
+    #[inline]
+    /// Set the time window shown. Consumes self.
+    pub fn view(mut self, view: Range<f32>) -> Self {
+        self.set_view(view);
+
+        self
+    }
+
+    #[inline]
+    /// Return the time window shown.
+    pub fn get_view(&self) -> Range<f32> {
+        self.chart.get_data().view.clone()
+    }
+
+    /// Zoom out to show every span.
+    pub fn reset_view(&mut self) {
+        let Range { start, end } = self.range;
+
+        // Widen empty timelines so they can still be drawn
+        let view = match end > start {
+            true => start..end,
+            false => (start - 1.0)..(end + 1.0),
+        };
+
+        self.set_view(view);
+    }
+
+    #[inline]
+    /// Set how quickly scrolling zooms in/out.
+    pub fn set_zoom_scale(&mut self, zoom_scale: f32) {
+        self.zoom_scale = zoom_scale
+    }
+
+    #[inline]
+    /// Set how quickly scrolling zooms in/out. Consumes self.
+    pub fn zoom_scale(mut self, zoom_scale: f32) -> Self {
+        self.set_zoom_scale(zoom_scale);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable the details shown when hovering over a span.
+    pub fn set_hover(&mut self, hover: bool) {
+        self.chart.get_data_mut().hover = hover
+    }
+
+    #[inline]
+    /// Enable/disable the details shown when hovering over a span. Consumes self.
+    pub fn hover(mut self, hover: bool) -> Self {
+        self.set_hover(hover);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the playhead.
+    pub fn set_playhead_style(&mut self, playhead_style: ShapeStyle) {
+        self.chart.get_data_mut().playhead_style = playhead_style
+    }
+
+    #[inline]
+    /// Set the style of the playhead. Consumes self.
+    pub fn playhead_style(mut self, playhead_style: ShapeStyle) -> Self {
+        self.set_playhead_style(playhead_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the grid lines of the time axis.
+    pub fn set_x_grid(&mut self, x_grid: GridConfig) {
+        self.chart.get_data_mut().x_grid = x_grid
+    }
+
+    #[inline]
+    /// Set the grid lines of the time axis. Consumes self.
+    pub fn x_grid(mut self, x_grid: GridConfig) -> Self {
+        self.set_x_grid(x_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the axes.
+    pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
+        self.chart.get_data_mut().axes_style = axes_style
+    }
+
+    #[inline]
+    /// Set the style of the axes. Consumes self.
+    pub fn axes_style(mut self, axes_style: ShapeStyle) -> Self {
+        self.set_axes_style(axes_style);
+
+        self
+    }
+
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the unit of the time axis.
+    pub fn set_x_unit(&mut self, x_unit: &str) {
+        self.chart.get_data_mut().x_unit = x_unit.into()
+    }
+
+    #[inline]
+    /// Set the unit of the time axis. Consumes self.
+    pub fn x_unit(mut self, x_unit: &str) -> Self {
+        self.set_x_unit(x_unit);
+
+        self
+    }
+
+    #[inline]
+    /// Set the SI unit of the time axis, such as "s". Tick labels are scaled with an SI prefix
+    /// to fit the view, such as ms or µs, and the prefixed unit is added to the axis
+    /// description. Set to None to leave the labels unscaled.
+    pub fn set_x_si_unit(&mut self, x_si_unit: Option<&str>) {
+        self.chart.get_data_mut().x_si_unit = x_si_unit.map(|unit| unit.into())
+    }
+
+    #[inline]
+    /// Set the SI unit of the time axis. Consumes self.
+    pub fn x_si_unit(mut self, x_si_unit: Option<&str>) -> Self {
+        self.set_x_si_unit(x_si_unit);
+
+        self
+    }
+
+    #[inline]
+    /// Set the width of the area the row names are shown in.
+    pub fn set_row_label_area(&mut self, row_label_area: i32) {
+        self.chart.get_data_mut().row_label_area = row_label_area
+    }
+
+    #[inline]
+    /// Set the width of the area the row names are shown in. Consumes self.
+    pub fn row_label_area(mut self, row_label_area: i32) -> Self {
+        self.set_row_label_area(row_label_area);
+
+        self
+    }
+
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening the caption and axis description too long for
+    /// the chart with an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.get_data_mut().text_fit.set_enabled(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_data().text_fit.is_enabled()
+    }
+
+    /// Draw the timeline to a Ui. Will also move the playhead if playback is currently enabled.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        let active = self.playback.borrow().is_active();
+        let playhead = active.then(|| self.current_time());
+
+        self.draw_with_playhead(ui, playhead);
+    }
+
+    /// Draw the timeline to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui. No event is highlighted, as there is no pointer.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let active = self.playback.borrow().is_active();
+        let playhead = active.then(|| self.current_time());
+
+        let config = self.chart.get_data_mut();
+
+        config.pointer = None;
+        config.playhead = playhead;
+
+        self.chart.draw_headless(backend)
+    }
+
+    /// Draw the timeline to a Ui with the playhead at the given time, bypassing the timeline's
+    /// own playback clock.
+    pub fn draw_at_time(&mut self, ui: &mut Ui, time: f32) {
+        self.draw_with_playhead(ui, Some(time));
+    }
+
+    fn draw_with_playhead(&mut self, ui: &mut Ui, playhead: Option<f32>) {
+        let pointer = hover_pos(ui);
+
+        if let Some((x, _)) = pointer {
+            self.pan_zoom(ui, x);
+        }
+
+        let config = self.chart.get_data_mut();
+
+        config.pointer = pointer;
+        config.playhead = playhead;
+
+        if let Some((group, _)) = &self.link {
+            config.link_hovered = group.get_hovered();
+            config.link_selected = group.get_selected();
+        }
+
+        self.chart.draw(ui);
+
+        let transform = self.chart.get_transform();
+
+        self.chart.get_data().text_fit.show_full_text(ui, transform);
+
+        if let Some((group, member)) = &mut self.link {
+            let time = self.chart.get_data().pointer_time.get();
+
+            group.hover(*member, time);
+
+            if time.is_some() && ui.input(|input| input.pointer.primary_clicked()) {
+                group.set_selected(time);
+            }
+        }
+    }
+
+    /// Pan the time window by dragging, and zoom it around the pointer by scrolling.
+    fn pan_zoom(&mut self, ui: &Ui, pointer_x: f32) {
+        let config = self.chart.get_data();
+
+        let plot_width =
+            ui.max_rect().width() - (DEFAULT_MARGIN * 2 + config.row_label_area) as f32;
+
+        if plot_width <= 0.0 {
+            return;
+        }
+
+        let Range { start, end } = config.view;
+        let width = end - start;
+        let seconds_per_pixel = width / plot_width;
+
+        let (drag, scroll) = ui.input(|input| {
+            let drag = match input.pointer.primary_down() {
+                true => input.pointer.delta().x,
+                false => 0.0,
+            };
+
+            (drag, input.smooth_scroll_delta.y)
+        });
+
+        let shift = -drag * seconds_per_pixel;
+
+        // Zoom around the time under the pointer
+        let min_width = (self.range.end - self.range.start).abs() * MIN_VIEW_WIDTH;
+        let anchor = start
+            + (pointer_x - (DEFAULT_MARGIN + config.row_label_area) as f32) * seconds_per_pixel;
+        let zoom = (-scroll * self.zoom_scale).exp();
+        let new_width = (width * zoom).max(min_width.max(f32::EPSILON));
+        let ratio = new_width / width;
+
+        let start = anchor - (anchor - start) * ratio + shift;
+        let end = start + new_width;
+
+        if start != config.view.start || end != config.view.end {
+            self.set_view(start..end);
+        }
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds.
+    pub fn set_time(&mut self, time: f32) {
+        self.playback.borrow_mut().set_time(time)
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds. Consumes self.
+    pub fn time(mut self, time: f32) -> Self {
+        self.set_time(time);
+
+        self
+    }
+
+    #[inline]
+    /// Move playback forward, or backward if negative, by some seconds from the current time.
+    /// Starts playback paused if it wasn't underway.
+    pub fn seek_relative(&mut self, delta: f32) {
+        self.playback.borrow_mut().seek_relative(delta)
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half.
+    pub fn set_playback_speed(&mut self, speed: f32) {
+        self.playback.borrow_mut().set_speed(speed)
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Consumes self.
+    pub fn playback_speed(mut self, speed: f32) -> Self {
+        self.set_playback_speed(speed);
+
+        self
+    }
+
+    #[inline]
+    /// Start/enable playback of the timeline. Does nothing if the timeline has nothing to animate.
+    pub fn start_playback(&mut self) {
+        self.playback.borrow_mut().start()
+    }
+
+    #[inline]
+    /// Start/enable playback of the timeline, returning [`PlaybackError::ZeroDuration`] if the
+    /// timeline has nothing to animate, such as when every point is at the same time.
+    pub fn try_start_playback(&mut self) -> Result<(), PlaybackError> {
+        self.playback.borrow_mut().try_start()
+    }
+
+    #[inline]
+    /// Stop/disable playback of the timeline.
+    pub fn stop_playback(&mut self) {
+        self.playback.borrow_mut().stop()
+    }
+
+    #[inline]
+    /// Toggle playback of the timeline.
+    pub fn toggle_playback(&mut self) {
+        self.playback.borrow_mut().toggle()
+    }
+
+    #[inline]
+    /// Return true if playback is currently enabled & underway.
+    pub fn is_playing(&self) -> bool {
+        self.playback.borrow().is_playing()
+    }
+
+    #[inline]
+    /// Return the time the timeline starts at when playback is enabled.
+    pub fn start_time(&self) -> f32 {
+        self.range.start
+    }
+
+    /// Return the current time of the playhead when playback is enabled.
+    pub fn current_time(&mut self) -> f32 {
+        let time_start = self.start_time();
+        let time_end = self.end_time();
+
+        match self.playback.borrow_mut().elapsed() {
+            Some(elapsed) => (elapsed + time_start).min(time_end),
+            None => time_start,
+        }
+    }
+
+    #[inline]
+    /// Return the time the timeline finishes animating at when playback is enabled.
+    pub fn end_time(&self) -> f32 {
+        self.range.end
+    }
+
+    #[inline]
+    /// Return the speed the timeline is animated at.
+    pub fn get_playback_speed(&self) -> f32 {
+        self.playback.borrow().speed()
+    }
+
+    /// Join a playback group, sharing its playback clock with every other member.
+    pub fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        let playback = group.playback().clone();
+
+        playback
+            .borrow_mut()
+            .extend_duration(self.end_time() - self.start_time());
+
+        self.playback = playback;
+    }
+
+    #[inline]
+    /// Join a playback group, sharing its playback clock with every other member. Consumes self.
+    pub fn playback_group(mut self, group: &PlaybackGroup) -> Self {
+        self.set_playback_group(group);
+
+        self
+    }
+
+    /// Leave the current playback group, if any, and go back to an independent playback clock.
+    pub fn leave_playback_group(&mut self) {
+        let duration = self.end_time() - self.start_time();
+
+        self.playback = Rc::new(RefCell::new(Playback::new(duration)));
+    }
+
+    /// Join a link group, drawing lines across the times hovered and selected in any chart of the
+    /// group.
+    pub fn set_link_group(&mut self, group: &LinkGroup) {
+        self.leave_link_group();
+
+        self.link = Some((group.clone(), group.join()));
+    }
+
+    #[inline]
+    /// Join a link group. Consumes self.
+    pub fn link_group(mut self, group: &LinkGroup) -> Self {
+        self.set_link_group(group);
+
+        self
+    }
+
+    /// Leave the current link group, if any.
+    pub fn leave_link_group(&mut self) {
+        if let Some((group, member)) = self.link.take() {
+            group.hover(member, None);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Mappings between playback time and the time of the data being played back.
+
+use std::sync::Arc;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+/// How the time of the data is compressed during playback.
+///
+/// ## Usage
+/// By default data is played back in real time, scaled only by the playback speed. Recordings
+/// with long idle stretches between short bursts of activity can instead compress the gaps
+/// between points, so playback doesn't spend minutes on flat lines:
+///  * `Linear` - Play the data back as is.
+///  * `SkipGaps` - Shorten every gap between points longer than `max_gap` down to `max_gap`.
+///  * `Symlog` - Play gaps up to `linear_width` as is, and compress longer gaps logarithmically.
+///
+/// Compression only changes how fast the data is played through; the chart and its times stay
+/// the same.
+pub enum TimeMapping {
+    #[default]
+    Linear,
+    SkipGaps {
+        max_gap: f32,
+    },
+    Symlog {
+        linear_width: f32,
+    },
+}
+
+impl TimeMapping {
+    /// Length of a gap between points once played back.
+    fn compress(&self, gap: f32) -> f32 {
+        match *self {
+            Self::Linear => gap,
+            Self::SkipGaps { max_gap } => gap.min(max_gap.max(0.0)),
+            Self::Symlog { linear_width } => match linear_width > 0.0 && gap > linear_width {
+                true => linear_width * (1.0 + (gap / linear_width).ln()),
+                false => gap,
+            },
+        }
+    }
+}
+
+/// Playback times of every point, used to convert between playback and data time. Times are
+/// offsets from the first point.
+#[derive(Clone)]
+pub(crate) struct TimeMap {
+    mapping: TimeMapping,
+    data: Arc<[f32]>,
+    playback: Arc<[f32]>,
+}
+
+impl TimeMap {
+    pub(crate) fn new(times: &[f32], mapping: TimeMapping) -> Self {
+        let first = times.first().copied().unwrap_or(0.0);
+
+        let data: Arc<[f32]> = times.iter().map(|time| time - first).collect();
+
+        let mut elapsed = 0.0;
+        let playback = data
+            .iter()
+            .enumerate()
+            .map(|(index, time)| {
+                if let Some(previous) = index.checked_sub(1).map(|index| data[index]) {
+                    elapsed += mapping.compress(time - previous);
+                }
+
+                elapsed
+            })
+            .collect();
+
+        Self {
+            mapping,
+            data,
+            playback,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn mapping(&self) -> TimeMapping {
+        self.mapping
+    }
+
+    #[inline]
+    /// Seconds it takes to play back every point.
+    pub(crate) fn duration(&self) -> f32 {
+        self.playback.last().copied().unwrap_or(0.0)
+    }
+
+    #[inline]
+    /// Data time shown after the given seconds of playback.
+    pub(crate) fn to_data(&self, elapsed: f32) -> f32 {
+        match self.mapping {
+            TimeMapping::Linear => elapsed,
+            _ => interpolate(&self.playback, &self.data, elapsed),
+        }
+    }
+
+    #[inline]
+    /// Seconds of playback it takes to reach the given data time.
+    pub(crate) fn to_playback(&self, time: f32) -> f32 {
+        match self.mapping {
+            TimeMapping::Linear => time,
+            _ => interpolate(&self.data, &self.playback, time),
+        }
+    }
+}
+
+/// Map a value from one sorted set of times to another, interpolating linearly between them.
+fn interpolate(from: &[f32], to: &[f32], value: f32) -> f32 {
+    let index = from.partition_point(|time| *time <= value);
+
+    match (index.checked_sub(1), from.get(index)) {
+        (None, _) => to.first().copied().unwrap_or(0.0),
+        (Some(last), None) => to[last],
+        (Some(previous), Some(next)) => {
+            let fraction = match *next > from[previous] {
+                true => (value - from[previous]) / (next - from[previous]),
+                false => 0.0,
+            };
+
+            to[previous] + (to[index] - to[previous]) * fraction
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMES: [f32; 5] = [10.0, 11.0, 12.0, 112.0, 113.0];
+
+    #[test]
+    fn compressed_times_round_trip() {
+        for mapping in [
+            TimeMapping::Linear,
+            TimeMapping::SkipGaps { max_gap: 2.0 },
+            TimeMapping::Symlog { linear_width: 1.0 },
+        ] {
+            let map = TimeMap::new(&TIMES, mapping);
+
+            for time in [0.0, 0.5, 2.0, 50.0, 102.0, 103.0] {
+                let round_trip = map.to_data(map.to_playback(time));
+
+                assert!((round_trip - time).abs() < 1e-3, "{mapping:?} at {time}");
+            }
+        }
+    }
+
+    #[test]
+    fn gaps_are_compressed() {
+        assert_eq!(TimeMap::new(&TIMES, TimeMapping::Linear).duration(), 103.0);
+        assert_eq!(
+            TimeMap::new(&TIMES, TimeMapping::SkipGaps { max_gap: 2.0 }).duration(),
+            5.0
+        );
+
+        let symlog = TimeMap::new(&TIMES, TimeMapping::Symlog { linear_width: 1.0 });
+
+        assert!((symlog.duration() - (4.0 + 100f32.ln())).abs() < 1e-4);
+
+        // Times past the last point stay at it
+        assert_eq!(symlog.to_data(symlog.duration() + 1.0), 103.0);
+    }
+}
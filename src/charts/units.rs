@@ -0,0 +1,155 @@
+//! Tick labels scaled with SI prefixes to fit the range of an axis.
+
+use std::ops::Range;
+
+use crate::locale::{locale, Locale};
+
+/// SI prefixes by power of ten, from yocto to yotta.
+const PREFIXES: [(i32, &str); 17] = [
+    (-24, "y"),
+    (-21, "z"),
+    (-18, "a"),
+    (-15, "f"),
+    (-12, "p"),
+    (-9, "n"),
+    (-6, "µ"),
+    (-3, "m"),
+    (0, ""),
+    (3, "k"),
+    (6, "M"),
+    (9, "G"),
+    (12, "T"),
+    (15, "P"),
+    (18, "E"),
+    (21, "Z"),
+    (24, "Y"),
+];
+
+/// Most decimals shown in a label.
+const MAX_DECIMALS: i32 = 6;
+
+#[derive(Debug, Copy, Clone)]
+/// Prefix an axis is labeled with, and the precision its labels need.
+pub(crate) struct SiScale {
+    factor: f64,
+    prefix: &'static str,
+    decimals: usize,
+    locale: Locale,
+}
+
+impl SiScale {
+    /// Pick the prefix keeping the largest value of the range between 1 and 1000, and enough
+    /// decimals to tell apart labels a tenth of the range apart.
+    pub(crate) fn new(range: &Range<f32>) -> Self {
+        let magnitude = range.start.abs().max(range.end.abs()) as f64;
+
+        let exponent = match magnitude > 0.0 && magnitude.is_finite() {
+            true => (magnitude.log10() / 3.0).floor() as i32 * 3,
+            false => 0,
+        };
+
+        let (exponent, prefix) = PREFIXES
+            .iter()
+            .copied()
+            .min_by_key(|(prefix_exponent, _)| (prefix_exponent - exponent).abs())
+            .unwrap_or((0, ""));
+
+        let factor = 10f64.powi(exponent);
+        let step = (range.end - range.start).abs() as f64 / factor / 10.0;
+
+        let decimals = match step > 0.0 && step.is_finite() {
+            true => (-step.log10().floor() as i32).clamp(0, MAX_DECIMALS) as usize,
+            false => 0,
+        };
+
+        Self {
+            factor,
+            prefix,
+            decimals,
+            locale: locale(),
+        }
+    }
+
+    /// Label of a tick, scaled to the prefix.
+    pub(crate) fn format(&self, value: f32) -> String {
+        let scaled = value as f64 / self.factor;
+
+        // Avoid labeling zero, or anything rounding to it, as "-0"
+        let rounded = (scaled * 10f64.powi(self.decimals as i32)).round();
+        let scaled = match rounded == 0.0 {
+            true => 0.0,
+            false => scaled,
+        };
+
+        self.locale.format_fixed(scaled, self.decimals)
+    }
+
+    /// Description of the axis, followed by the prefixed unit.
+    pub(crate) fn describe(&self, desc: &str, symbol: &str) -> String {
+        match desc.is_empty() {
+            true => format!("{}{}", self.prefix, symbol),
+            false => format!("{} ({}{})", desc, self.prefix, symbol),
+        }
+    }
+}
+
+/// Scale and description of an axis, if it has an SI unit.
+pub(crate) fn si_axis(
+    range: &Range<f32>,
+    desc: &str,
+    symbol: Option<&str>,
+) -> (Option<SiScale>, String) {
+    match symbol {
+        Some(symbol) => {
+            let scale = SiScale::new(range);
+
+            (Some(scale), scale.describe(desc, symbol))
+        }
+        None => (None, desc.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_keeps_the_largest_value_below_a_thousand() {
+        assert_eq!(SiScale::new(&(0.0..999.0)).describe("", "V"), "V");
+        assert_eq!(SiScale::new(&(0.0..1500.0)).describe("", "V"), "kV");
+        assert_eq!(
+            SiScale::new(&(-2e6..1.0)).describe("Power", "W"),
+            "Power (MW)"
+        );
+        assert_eq!(SiScale::new(&(0.0..0.002)).describe("", "s"), "ms");
+        assert_eq!(SiScale::new(&(0.0..5e-7)).describe("", "s"), "ns");
+    }
+
+    #[test]
+    fn prefix_is_clamped_to_yotta_and_yocto() {
+        assert_eq!(SiScale::new(&(0.0..1e30)).describe("", "m"), "Ym");
+        assert_eq!(SiScale::new(&(0.0..1e-30)).describe("", "m"), "ym");
+
+        // Ranges without a magnitude aren't prefixed
+        assert_eq!(SiScale::new(&(0.0..0.0)).describe("", "m"), "m");
+        assert_eq!(SiScale::new(&(0.0..f32::INFINITY)).describe("", "m"), "m");
+    }
+
+    #[test]
+    fn labels_are_scaled_with_enough_decimals() {
+        let scale = SiScale::new(&(0.0..2500.0));
+
+        assert_eq!(scale.format(1500.0), "1.5");
+        assert_eq!(SiScale::new(&(0.0..200.0)).format(150.0), "150");
+        assert_eq!(SiScale::new(&(0.0..0.01)).format(0.005), "5.0");
+    }
+
+    #[test]
+    fn zero_is_never_negative() {
+        let scale = SiScale::new(&(-2500.0..2500.0));
+
+        assert_eq!(scale.format(-0.0), "0.0");
+        assert_eq!(scale.format(-10.0), "0.0");
+        assert_eq!(scale.format(-100.0), "-0.1");
+    }
+}
@@ -0,0 +1,544 @@
+//! Waterfall chart showing how a running total is built up from positive and negative steps.
+
+use std::sync::Arc;
+
+use egui::Ui;
+use plotters::{
+    coord::ReverseCoordTranslate,
+    element::{PathElement, Rectangle},
+    prelude::{ChartBuilder, LabelAreaPosition, SeriesLabelPosition},
+    style::{
+        full_palette::{BLUE_600, GREEN_600, GREY_700, RED_600},
+        Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK, WHITE,
+    },
+};
+use plotters_backend::{DrawingBackend, DrawingErrorKind, FontFamily, FontStyle};
+
+use crate::{
+    charts::{
+        grid::{draw_grid, GridConfig},
+        hover::{draw_tooltip, hover_pos, untransform},
+        textfit::TextFit,
+    },
+    locale::locale,
+    Chart,
+};
+
+const DEFAULT_MARGIN: i32 = 25;
+const DEFAULT_LABEL_AREA: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+/// Width of every bar, relative to the space between steps.
+const BAR_WIDTH: f32 = 0.6;
+/// Headroom above and below the bars, relative to the value range.
+const VALUE_PADDING: f32 = 0.05;
+const HOVER_OPACITY: f64 = 0.15;
+
+#[derive(Clone)]
+struct WaterfallConfig {
+    /// Name and change in value of every step.
+    steps: Arc<[(Arc<str>, f32)]>,
+    /// Name of the final total bar. Hidden if None.
+    total: Option<Arc<str>>,
+    /// Color of steps increasing the total.
+    increase_color: RGBAColor,
+    /// Color of steps decreasing the total.
+    decrease_color: RGBAColor,
+    /// Color of the final total bar.
+    total_color: RGBAColor,
+    /// Style of the lines connecting each bar to the next.
+    connector_style: ShapeStyle,
+    /// Grid lines of the value axis.
+    y_grid: GridConfig,
+    /// Style of the axes.
+    axes_style: ShapeStyle,
+    /// Style of the text
+    text_color: RGBAColor,
+    /// Unit of the value axis.
+    y_unit: Arc<str>,
+    /// Caption of the chart. Hidden if None.
+    caption: Option<Arc<str>>,
+    /// Caption and axis description shortened to fit the chart.
+    text_fit: TextFit,
+    /// Show a legend for the increase, decrease, and total colors.
+    legend: bool,
+    /// Show the change and running total of the hovered step.
+    hover: bool,
+    /// Position of the pointer relative to the chart, if it is hovering over it.
+    pointer: Option<(f32, f32)>,
+}
+
+impl WaterfallConfig {
+    /// Every bar as its name, start value, and end value, including the total bar if enabled.
+    fn bars(&self) -> Vec<(Arc<str>, f32, f32)> {
+        let mut running_total = 0.0;
+
+        let mut bars: Vec<_> = self
+            .steps
+            .iter()
+            .map(|(name, delta)| {
+                let start = running_total;
+                running_total += delta;
+
+                (name.clone(), start, running_total)
+            })
+            .collect();
+
+        if let Some(total) = &self.total {
+            bars.push((total.clone(), 0.0, running_total));
+        }
+
+        bars
+    }
+}
+
+/// Waterfall chart.
+///
+/// ## Usage
+/// Creating the chart only requires the steps making up the total and a caption.
+///
+///  * `steps`: A slice of tuples, arranged so that the first element is the name of the step and
+///    the second is how much it changes the running total.
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// Each step is drawn as a floating bar from the running total before it to the running total
+/// after it, colored by whether it increases or decreases the total, and connected to the next
+/// bar by a line. A final bar showing the total is added at the end, which can be renamed or
+/// removed with `.set_total()`. Hovering over a step shows its change and the running total.
+pub struct Waterfall {
+    chart: Chart<WaterfallConfig>,
+}
+
+impl Waterfall {
+    /// Create a new Waterfall chart. See [Usage](#usage).
+    pub fn new(steps: &[(&str, f32)], caption: &str) -> Self {
+        let steps: Arc<[(Arc<str>, f32)]> = steps
+            .iter()
+            .map(|(name, delta)| (Arc::from(*name), *delta))
+            .collect();
+
+        let config = WaterfallConfig {
+            steps,
+            total: Some("Total".into()),
+            increase_color: GREEN_600.to_rgba(),
+            decrease_color: RED_600.to_rgba(),
+            total_color: BLUE_600.to_rgba(),
+            connector_style: GREY_700.into(),
+            y_grid: GridConfig::default(),
+            axes_style: GREY_700.into(),
+            text_color: BLACK.to_rgba(),
+            y_unit: "".into(),
+            caption: Some(caption.into()),
+            text_fit: TextFit::default(),
+            legend: true,
+            hover: true,
+            pointer: None,
+        };
+
+        let chart = Chart::new(config).builder_cb(Box::new(|area, transform, data| {
+            data.text_fit.clear();
+
+            let bars = data.bars();
+            let bar_count = bars.len();
+
+            // Nothing to show without steps
+            if bar_count == 0 {
+                return;
+            }
+
+            let (y_min, y_max) = {
+                let (min, max) =
+                    bars.iter()
+                        .fold((0.0_f32, 0.0_f32), |(min, max), (_, start, end)| {
+                            (min.min(*start).min(*end), max.max(*start).max(*end))
+                        });
+
+                // Leave some headroom, and make sure empty charts can still be drawn
+                let padding = match max > min {
+                    true => (max - min) * VALUE_PADDING,
+                    false => 1.0,
+                };
+
+                (
+                    match min < 0.0 {
+                        true => min - padding,
+                        false => min,
+                    },
+                    max + padding,
+                )
+            };
+
+            let x_range = -0.5..(bar_count as f32 - 0.5);
+
+            let font_desc = FontDesc::new(
+                FontFamily::Monospace,
+                CAPTION_SIZE as f64,
+                FontStyle::Normal,
+            );
+
+            let text_style = TextStyle::from(font_desc).color(&data.text_color);
+
+            let mut builder = ChartBuilder::on(area);
+
+            builder
+                .margin(DEFAULT_MARGIN)
+                .x_label_area_size(DEFAULT_LABEL_AREA)
+                .y_label_area_size(DEFAULT_LABEL_AREA);
+
+            if let Some(caption) = &data.caption {
+                let margins = (DEFAULT_MARGIN, DEFAULT_MARGIN);
+                let caption = data.text_fit.caption(area, margins, caption, &text_style);
+
+                builder.caption(caption, text_style.clone());
+            }
+
+            let mut chart = match builder.build_cartesian_2d(x_range, y_min..y_max) {
+                Ok(chart) => chart,
+                // Area too small to be drawn
+                Err(_) => return,
+            };
+
+            draw_grid(&mut chart, &GridConfig::disabled(), &data.y_grid);
+
+            let plot = chart.plotting_area();
+            let y_desc = data.text_fit.axis_desc(
+                plot,
+                LabelAreaPosition::Left,
+                DEFAULT_LABEL_AREA,
+                &data.y_unit,
+                &text_style,
+            );
+
+            // Only label the bar centers
+            let step_label = |x: &f32| {
+                let index = x.round();
+
+                match (x - index).abs() < f32::EPSILON && index >= 0.0 {
+                    true => bars
+                        .get(index as usize)
+                        .map_or_else(String::new, |(name, _, _)| name.to_string()),
+                    false => String::new(),
+                }
+            };
+            let locale = locale();
+            let value_label = |y: &f32| locale.format_tick(*y as f64);
+
+            chart
+                .configure_mesh()
+                .disable_mesh()
+                .x_labels(bar_count)
+                .x_label_formatter(&step_label)
+                .y_labels(data.y_grid.get_major_lines())
+                .y_label_formatter(&value_label)
+                .label_style(text_style.clone())
+                .axis_style(data.axes_style)
+                .y_desc(y_desc)
+                .draw()
+                .unwrap();
+
+            // Find the bar under the pointer, undoing the chart's transform
+            let hovered = data
+                .pointer
+                .filter(|_| data.hover)
+                .and_then(|pos| {
+                    chart
+                        .as_coord_spec()
+                        .reverse_translate(untransform(area, transform, pos))
+                })
+                .map(|(x, _)| x.round())
+                .filter(|x| *x >= 0.0 && (*x as usize) < bar_count)
+                .map(|x| x as usize);
+
+            if let Some(index) = hovered {
+                let center = index as f32;
+                let highlight = data.text_color.mix(HOVER_OPACITY).filled();
+
+                chart
+                    .draw_series([Rectangle::new(
+                        [(center - 0.5, y_min), (center + 0.5, y_max)],
+                        highlight,
+                    )])
+                    .unwrap();
+            }
+
+            // Connect the end of each bar to the start of the next
+            let connectors = bars.windows(2).enumerate().map(|(index, pair)| {
+                let (_, _, end) = pair[0];
+                let left = index as f32 + BAR_WIDTH / 2.0;
+                let right = (index + 1) as f32 - BAR_WIDTH / 2.0;
+
+                PathElement::new(vec![(left, end), (right, end)], data.connector_style)
+            });
+
+            chart.draw_series(connectors).unwrap();
+
+            let step_count = data.steps.len();
+            let kinds = [
+                ("Increase", data.increase_color),
+                ("Decrease", data.decrease_color),
+                ("Total", data.total_color),
+            ];
+
+            for (kind, (label, color)) in kinds.into_iter().enumerate() {
+                let kind_bars = bars
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, (_, start, end))| {
+                        let bar_kind = match (*index >= step_count, end >= start) {
+                            (true, _) => 2,
+                            (false, true) => 0,
+                            (false, false) => 1,
+                        };
+
+                        bar_kind == kind
+                    })
+                    .map(|(index, (_, start, end))| {
+                        let left = index as f32 - BAR_WIDTH / 2.0;
+
+                        Rectangle::new([(left, *start), (left + BAR_WIDTH, *end)], color.filled())
+                    });
+
+                let series_anno = chart.draw_series(kind_bars).unwrap();
+
+                // Only list the total if it is shown
+                if data.legend && (kind < 2 || data.total.is_some()) {
+                    series_anno.label(label).legend(move |(x, y)| {
+                        Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled())
+                    });
+                }
+            }
+
+            if data.legend {
+                chart
+                    .configure_series_labels()
+                    .position(SeriesLabelPosition::UpperRight)
+                    .label_font(text_style.clone())
+                    .background_style(WHITE.mix(0.8))
+                    .border_style(data.axes_style)
+                    .draw()
+                    .unwrap();
+            }
+
+            let Some(index) = hovered else {
+                return;
+            };
+
+            // Change and running total of the hovered bar, next to its top
+            let (name, start, end) = &bars[index];
+
+            let lines = match index >= step_count {
+                true => vec![
+                    name.to_string(),
+                    format!("Total: {}", locale.format_number(end)),
+                ],
+                false => vec![
+                    name.to_string(),
+                    format!("Change: {}", locale.localize(&format!("{:+}", end - start))),
+                    format!("Running total: {}", locale.format_number(end)),
+                ],
+            };
+
+            let pos = chart.backend_coord(&(index as f32 + BAR_WIDTH / 2.0, start.max(*end)));
+
+            draw_tooltip(area, pos, lines, &text_style);
+        }));
+
+        Self { chart }
+    }
+
+    /// Set the name of the final total bar, or remove it with None.
+    pub fn set_total(&mut self, total: Option<&str>) {
+        self.chart.get_data_mut().total = total.map(|total| total.into())
+    }
+
+    #[inline]
+    /// Set the name of the final total bar, or remove it with None. Consumes self.
+    pub fn total(mut self, total: Option<&str>) -> Self {
+        self.set_total(total);
+
+        self
+    }
+
+    /// Set the colors of increasing steps, decreasing steps, and the final total bar.
+    pub fn set_colors<T>(&mut self, increase: T, decrease: T, total: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        let config = self.chart.get_data_mut();
+
+        config.increase_color = increase.into();
+        config.decrease_color = decrease.into();
+        config.total_color = total.into();
+    }
+
+    #[inline]
+    /// Set the colors of increasing steps, decreasing steps, and the final total bar. Consumes
+    /// self.
+    pub fn colors<T>(mut self, increase: T, decrease: T, total: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_colors(increase, decrease, total);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the lines connecting each bar to the next.
+    pub fn set_connector_style(&mut self, connector_style: ShapeStyle) {
+        self.chart.get_data_mut().connector_style = connector_style
+    }
+
+    #[inline]
+    /// Set the style of the lines connecting each bar to the next. Consumes self.
+    pub fn connector_style(mut self, connector_style: ShapeStyle) -> Self {
+        self.set_connector_style(connector_style);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide the legend.
+    pub fn set_legend(&mut self, legend: bool) {
+        self.chart.get_data_mut().legend = legend
+    }
+
+    #[inline]
+    /// Show/hide the legend. Consumes self.
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.set_legend(legend);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable the change and running total shown when hovering over a step.
+    pub fn set_hover(&mut self, hover: bool) {
+        self.chart.get_data_mut().hover = hover
+    }
+
+    #[inline]
+    /// Enable/disable the change and running total shown when hovering over a step. Consumes
+    /// self.
+    pub fn hover(mut self, hover: bool) -> Self {
+        self.set_hover(hover);
+
+        self
+    }
+
+    #[inline]
+    /// Set the grid lines of the value axis.
+    pub fn set_y_grid(&mut self, y_grid: GridConfig) {
+        self.chart.get_data_mut().y_grid = y_grid
+    }
+
+    #[inline]
+    /// Set the grid lines of the value axis. Consumes self.
+    pub fn y_grid(mut self, y_grid: GridConfig) -> Self {
+        self.set_y_grid(y_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the axes.
+    pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
+        self.chart.get_data_mut().axes_style = axes_style
+    }
+
+    #[inline]
+    /// Set the style of the axes. Consumes self.
+    pub fn axes_style(mut self, axes_style: ShapeStyle) -> Self {
+        self.set_axes_style(axes_style);
+
+        self
+    }
+
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the unit of the value axis.
+    pub fn set_y_unit(&mut self, y_unit: &str) {
+        self.chart.get_data_mut().y_unit = y_unit.into()
+    }
+
+    #[inline]
+    /// Set the unit of the value axis. Consumes self.
+    pub fn y_unit(mut self, y_unit: &str) -> Self {
+        self.set_y_unit(y_unit);
+
+        self
+    }
+
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening the caption and axis description too long for
+    /// the chart with an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.get_data_mut().text_fit.set_enabled(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_data().text_fit.is_enabled()
+    }
+
+    /// Draw the chart to a Ui.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        self.chart.get_data_mut().pointer = hover_pos(ui);
+
+        self.chart.draw(ui);
+
+        let transform = self.chart.get_transform();
+
+        self.chart.get_data().text_fit.show_full_text(ui, transform);
+    }
+
+    /// Draw the chart to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui. No step is highlighted, as there is no pointer.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        self.chart.get_data_mut().pointer = None;
+
+        self.chart.draw_headless(backend)
+    }
+}
@@ -1,40 +1,234 @@
 //! Animatable line chart. Can have X and Y points.
 
-use std::{cmp::Ordering, ops::Range, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::Path,
+    rc::Rc,
+    sync::Arc,
+};
 
-use egui::Ui;
-use instant::Instant;
+use egui::{Color32, Margin, PointerButton, Pos2, Rect, Stroke, Ui};
 use plotters::{
-    prelude::ChartBuilder,
+    prelude::{
+        ChartBuilder, Circle, EmptyElement, LabelAreaPosition, PathElement, Rectangle, Text,
+    },
     series::LineSeries,
     style::{
-        full_palette::{GREY, GREY_700, RED_900},
-        Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK, WHITE,
+        full_palette::RED_900, Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK, WHITE,
     },
 };
-use plotters_backend::{FontFamily, FontStyle};
-
-use crate::{mult_range, Chart, MouseConfig};
+use plotters_backend::{DrawingBackend, DrawingErrorKind, FontFamily, FontStyle};
+
+#[cfg(feature = "adaptive")]
+use crate::AdaptiveQuality;
+use crate::{
+    backend::EguiBackendColor,
+    chart::{BackgroundCallback, OverlayCallback},
+    charts::{
+        axis::{AutoscaleMode, XAxisPosition, YAxisPosition},
+        axisbreak::{draw_break, AxisBreak, BrokenAxis},
+        clipping::{draw_clip_indicators, ClipCounts, ClipEdge},
+        decimate::decimate,
+        grid::{draw_grid, GridConfig},
+        hover::hover_pos,
+        link::LinkGroup,
+        minimap::{Minimap, DEFAULT_MINIMAP_HEIGHT, MINIMAP_SPACING},
+        playback::{Member, Playback, PlaybackError, PlaybackGroup},
+        table::{draw_table, DataTableMode, DEFAULT_TABLE_WIDTH, TABLE_SPACING},
+        textfit::TextFit,
+        timeindex::{time_index, Extents, TimeIndexCache},
+        timemap::{TimeMap, TimeMapping},
+        units::si_axis,
+    },
+    colormap::Colormap,
+    compat::child_ui,
+    locale::{locale, Locale},
+    mult_range, Chart, ChartState, MouseConfig, RenderQuality, ScreenTransform, Transform,
+};
 
-const MIN_DELTA: f32 = 0.000_010;
 const DEFAULT_RATIO: f32 = 1.0;
-const X_MARGIN: i32 = 25;
-const Y_MARGIN: i32 = 25;
-const LABEL_AREA: i32 = 25;
+const DEFAULT_X_MARGIN: i32 = 25;
+const DEFAULT_Y_MARGIN: i32 = 25;
+const DEFAULT_LABEL_AREA: i32 = 25;
+const DEFAULT_TICK_SIZE: i32 = 4;
 const CAPTION_SIZE: i32 = 10;
+const GHOST_OPACITY: f64 = 0.25;
+const PLAYHEAD_SIZE: i32 = 4;
+const BOOKMARK_SIZE: i32 = 4;
+const ENVELOPE_OPACITY: f64 = 0.35;
+/// Span of a fitted range with no width of its own, such as the values of a flat line.
+const FLAT_FIT_SPAN: f32 = 1.0;
+/// Distance, in pixels, from the pointer within which a point is hovered for the link group.
+const LINK_HOVER_RADIUS: f32 = 16.0;
+const LINK_HIGHLIGHT_SIZE: f32 = 5.0;
+
+#[derive(Debug, Copy, Clone)]
+/// Data ranges of the plot and the pixels they were drawn across, used to convert navigation
+/// limits from data units to pixels.
+struct PlotMapping {
+    x: (f32, f32),
+    /// Y range of the plot, folded by the break in the Y axis if any.
+    y: (f32, f32),
+    y_break: Option<AxisBreak>,
+    x_pixels: (i32, i32),
+    y_pixels: (i32, i32),
+    /// Width and height of the drawing area.
+    size: (f32, f32),
+}
+
+impl PlotMapping {
+    /// Rect the plot is drawn across on screen, and the transform from its X/Y values to the
+    /// screen, given the transform from the drawing area's pixels to the screen.
+    fn screen_layout(&self, transform: ScreenTransform) -> (Rect, ScreenTransform) {
+        let plot = Rect::from_min_max(
+            Pos2::new(self.x_pixels.0 as f32, self.y_pixels.0 as f32),
+            Pos2::new(self.x_pixels.1 as f32, self.y_pixels.1 as f32),
+        );
+
+        (
+            transform.rect_to_screen(plot),
+            transform.with_data(self.x.0..self.x.1, self.y.0..self.y.1, self.y_break, plot),
+        )
+    }
+
+    /// Ranges of X and Y values between the edges of the plot, with the drawing area's pixels
+    /// zoomed and dragged by the transform.
+    fn view(&self, transform: &Transform) -> (Range<f32>, Range<f32>) {
+        let (width, height) = self.size;
+        let scale = transform.scale as f32;
+        let (left, right) = (self.x_pixels.0 as f32, self.x_pixels.1 as f32);
+        let (top, bottom) = (self.y_pixels.0 as f32, self.y_pixels.1 as f32);
+
+        // Undo the transform to find which pixels of the plot are at its edges
+        let untransform = |px: f32, offset: i32, size: f32| {
+            (px - offset as f32 - size / 2.0) / scale + size / 2.0
+        };
+        let to_x = |px: f32| self.x.0 + (px - left) / (right - left) * (self.x.1 - self.x.0);
+        // Pixels go down while values go up
+        let to_y = |px: f32| {
+            let folded = self.y.0 + (bottom - px) / (bottom - top) * (self.y.1 - self.y.0);
+
+            self.unfold_y(folded)
+        };
+
+        let x = to_x(untransform(left, transform.x, width))
+            ..to_x(untransform(right, transform.x, width));
+        let y = to_y(untransform(bottom, transform.y, height))
+            ..to_y(untransform(top, transform.y, height));
+
+        (x, y)
+    }
+
+    #[inline]
+    /// Fold a Y value by the break in the Y axis, if any.
+    fn fold_y(&self, y: f32) -> f32 {
+        self.y_break.map_or(y, |y_break| y_break.fold(y))
+    }
+
+    #[inline]
+    /// Unfold a folded Y value back to the value it was folded from.
+    fn unfold_y(&self, y: f32) -> f32 {
+        self.y_break.map_or(y, |y_break| y_break.unfold(y))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Axis fitted to the data by the zoom-to-fit actions.
+enum FitAxis {
+    X,
+    Y,
+}
+
+/// Points sorted by time and split from their times, ready to be plotted.
+struct Dataset {
+    points: Arc<[(f32, f32)]>,
+    times: Arc<[f32]>,
+    extents: Extents,
+    /// The X values never decrease, so the points can be decimated.
+    monotonic: bool,
+}
+
+impl Dataset {
+    fn new(points: &[(f32, f32, f32)]) -> Self {
+        let mut points = points.to_vec();
+
+        // Sort by the time of the point
+        points.sort_by(|a, b| {
+            let (_, _, a) = a;
+            let (_, _, b) = b;
+
+            a.partial_cmp(b).unwrap_or(Ordering::Equal)
+        });
+
+        let times: Vec<f32> = points
+            .iter()
+            .map(|point| {
+                let (_, _, time) = point;
+
+                *time
+            })
+            .collect();
+
+        let points: Vec<(f32, f32)> = points
+            .iter()
+            .map(|point| {
+                let (x, y, _) = point;
+
+                (*x, *y)
+            })
+            .collect();
+
+        let extents = Extents::new(&points);
+
+        let monotonic = points.windows(2).all(|pair| {
+            let (a, _) = pair[0];
+            let (b, _) = pair[1];
+
+            a <= b
+        });
+
+        // Turn the vecs into arcs since they are more or less read-only at this point
+        let points: Arc<[(f32, f32)]> = points.into();
+        let times: Arc<[f32]> = times.into();
+
+        Self {
+            points,
+            times,
+            extents,
+            monotonic,
+        }
+    }
+}
 
 #[derive(Clone)]
 struct XyTimeConfig {
-    /// Points to be plotted. A slice of X, Y f32 pairs.
-    points: Arc<[(f32, f32)]>,
+    /// Every point, including the ones not yet plotted. A slice of X, Y f32 pairs.
+    all_points: Arc<[(f32, f32)]>,
+    /// Number of points plotted, counted from the start of `all_points`.
+    visible: usize,
+    /// Times of every point, including the ones not yet plotted.
+    times: Arc<[f32]>,
     /// Ranges at different time points.
     range: (Range<f32>, Range<f32>),
+    /// How the ranges follow playback.
+    autoscale: AutoscaleMode,
     /// Style of the plotted line.
     line_style: ShapeStyle,
-    /// Style of the grid lines.
-    grid_style: ShapeStyle,
-    /// Style of the small grid lines.
-    subgrid_style: ShapeStyle,
+    /// Show a ghost of the points yet to be played and a playhead during playback.
+    trail: bool,
+    /// Style of the ghost trail, derived from the line style if not set.
+    ghost_style: Option<ShapeStyle>,
+    /// Style of the playhead, derived from the line style if not set.
+    playhead_style: Option<ShapeStyle>,
+    /// Grid lines of the X axis.
+    x_grid: GridConfig,
+    /// Grid lines of the Y axis.
+    y_grid: GridConfig,
     /// Style of the axes.
     axes_style: ShapeStyle,
     /// Style of the text
@@ -45,10 +239,97 @@ struct XyTimeConfig {
     x_unit: Arc<str>,
     /// Unit of the Y axis.
     y_unit: Arc<str>,
+    /// SI unit of the X axis, its labels scaled with a prefix to fit the range. Unscaled if None.
+    x_si_unit: Option<Arc<str>>,
+    /// SI unit of the Y axis, its labels scaled with a prefix to fit the range. Unscaled if None.
+    y_si_unit: Option<Arc<str>>,
     /// Ratio between the X and Y axis units.
     ratio: f32,
-    /// Caption of the chart.
-    caption: Arc<str>,
+    /// Range of Y values skipped by the Y axis, drawn as a break. Not broken if None.
+    y_break: Option<Range<f32>>,
+    /// Caption of the chart. Hidden if None.
+    caption: Option<Arc<str>>,
+    /// Caption and axis descriptions shortened to fit the chart.
+    text_fit: TextFit,
+    /// Margin to the left and right of the chart.
+    x_margin: i32,
+    /// Margin above and below the chart.
+    y_margin: i32,
+    /// Size of the label area under the X axis.
+    x_label_area: i32,
+    /// Size of the label area beside the Y axis.
+    y_label_area: i32,
+    /// Side of the chart the X axis is on.
+    x_axis: XAxisPosition,
+    /// Side of the chart the Y axis is on.
+    y_axis: YAxisPosition,
+    /// Size of the tick marks on both axes.
+    tick_size: i32,
+    /// Decimate the points down to one column per pixel when there are too many to draw.
+    decimation: bool,
+    /// Decimate the points regardless, while the chart's adaptive quality is lowered.
+    degraded: bool,
+    /// Show the range of every decimated column as an envelope around the line.
+    envelope: bool,
+    /// Style of the envelope, derived from the line style if not set.
+    envelope_style: Option<ShapeStyle>,
+    /// True if the X values never decrease over time, required for decimation.
+    monotonic: bool,
+    /// Colormap the line is colored with along time. Uses the line style's color if None.
+    line_colormap: Option<Arc<dyn Colormap>>,
+    /// Named times marked on the chart, sorted by time.
+    bookmarks: Vec<(Arc<str>, f32)>,
+    /// X and Y ranges shown instead of the data's, set by the zoom-to-fit actions. Not adjusted
+    /// to the ratio.
+    fitted_view: Option<(Range<f32>, Range<f32>)>,
+    /// Plot mapping of the last frame drawn. Shared with the background callback, which is
+    /// called before the plot is laid out.
+    mapping: Rc<Cell<Option<PlotMapping>>>,
+}
+
+impl XyTimeConfig {
+    #[inline]
+    /// Points plotted so far, shared with every other frame of the playback.
+    fn points(&self) -> &[(f32, f32)] {
+        &self.all_points[..self.visible]
+    }
+}
+
+#[derive(Clone)]
+/// Points, time mapping, and playback clock of a time chart, shared with the gauges bound to it.
+/// Updated in place whenever the chart replaces any of them, so the gauges follow along.
+pub(crate) struct SharedSeries {
+    pub(crate) playback: Rc<RefCell<Playback>>,
+    pub(crate) member: Member,
+    pub(crate) points: Arc<[(f32, f32)]>,
+    pub(crate) times: Arc<[f32]>,
+    pub(crate) time_map: TimeMap,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Snapshot of the state of a time chart that changes as it is used, like [`ChartState`] is
+/// for other charts, adding where its playback is and which of its parts are shown.
+pub struct TimeChartState {
+    /// View and what the underlying chart shows.
+    pub chart: ChartState,
+    /// Time playback was at, in the same units as the points. None if playback wasn't underway,
+    /// with every point shown.
+    pub time: Option<f32>,
+    /// Playback was running rather than paused.
+    pub playing: bool,
+    /// X and Y ranges the chart was zoomed to fit, if any.
+    pub fitted_view: Option<(Range<f32>, Range<f32>)>,
+    /// The playback trail was shown.
+    pub trail: bool,
+    /// The overview strip was shown.
+    pub minimap: bool,
+    /// The clip indicators were shown.
+    pub clip_indicators: bool,
+    /// Where the table of the points was shown.
+    pub data_table: DataTableMode,
+    /// Names and times of the bookmarks, sorted by time.
+    pub bookmarks: Vec<(String, f32)>,
 }
 
 /// Animatable 2d line chart.
@@ -60,8 +341,8 @@ struct XyTimeConfig {
 /// 3 of which are just strings.
 ///
 ///  * `points`: A slice of tuples, arranged so that the first float is the x position, the second
-///  the y position, and the third is the time the next point is to be shown at(or in the case of
-///  the last point, the time the animation ends).
+///    the y position, and the third is the time the point is shown at (in the case of the last
+///    point, also the time the animation ends). Points sharing a time are shown together.
 ///  * `x_unit`: String describing the data on the X axis.
 ///  * `y_unit`: String describing the data on the Y axis.
 ///  * `caption`: String to be shown as the caption of the chart.
@@ -70,90 +351,53 @@ struct XyTimeConfig {
 /// add to your egui project. You can also animate this chart with `.toggle_playback()`
 /// and adjust various parameters with the many `.set_` functions included.
 pub struct XyTimeData {
-    playback_start: Option<Instant>,
-    pause_start: Option<Instant>,
-    playback_speed: f32,
+    playback: Rc<RefCell<Playback>>,
+    /// Timing of the chart on its playback clock, including its offset.
+    member: Member,
     points: Arc<[(f32, f32)]>,
-    ranges: Arc<[(Range<f32>, Range<f32>)]>,
+    /// X and Y ranges of the points shown at each time.
+    extents: Extents,
     times: Arc<[f32]>,
+    /// Index of the last point shown, remembered between playback frames.
+    time_cache: TimeIndexCache,
+    time_map: TimeMap,
+    /// Points, time mapping, and playback clock shared with bound gauges.
+    shared: Rc<RefCell<SharedSeries>>,
+    /// Smallest and largest X and Y spans that can be zoomed to.
+    zoom_limits: Option<(Range<f32>, Range<f32>)>,
+    /// X and Y ranges the center of the view is kept within.
+    pan_bounds: Option<(Range<f32>, Range<f32>)>,
+    /// Overview strip shown below the chart. Hidden if None.
+    minimap: Option<Minimap>,
+    minimap_height: f32,
+    /// Where the table of the points is shown.
+    data_table: DataTableMode,
+    /// Width of the table when shown beside the plot, in pixels.
+    data_table_width: f32,
+    /// Show badges counting the points clipped past each edge of the plot.
+    clip_indicators: bool,
+    /// Fit an axis to the data in view when its labels are double-clicked.
+    axis_autoscale: bool,
+    /// Callback drawing on top of the chart in data coordinates.
+    overlay_cb: Option<OverlayCallback>,
+    /// Group sharing hover and selection highlights, and the member the chart is known as in it.
+    link: Option<(LinkGroup, usize)>,
     chart: Chart<XyTimeConfig>,
 }
 
 impl XyTimeData {
     /// Create a new XyTimeData chart. See [Usage](#usage).
     pub fn new(points: &[(f32, f32, f32)], x_unit: &str, y_unit: &str, caption: &str) -> Self {
-        let mut points = points.to_vec();
-
-        // Sort by the time of the point
-        points.sort_by(|a, b| {
-            let (_, _, a) = a;
-            let (_, _, b) = b;
-
-            a.partial_cmp(b).unwrap_or(Ordering::Equal)
-        });
-
-        let times: Vec<f32> = points
-            .iter()
-            .map(|point| {
-                let (_, _, time) = point;
-
-                *time
-            })
-            .collect();
-
-        let points: Vec<(f32, f32)> = points
-            .iter()
-            .map(|point| {
-                let (x, y, _) = point;
-
-                (*x, *y)
-            })
-            .collect();
-
-        // Ranges include the X range, Y range, and time in seconds
-        let mut ranges = Vec::<(Range<f32>, Range<f32>)>::with_capacity(points.len());
-
-        let mut min_x: f32 = f32::MAX;
-        let mut min_y: f32 = f32::MAX;
-        let mut max_x: f32 = f32::MIN;
-        let mut max_y: f32 = f32::MIN;
-
-        for point in &points {
-            let (x, y) = *point;
-
-            min_x = min_x.min(x);
-            min_y = min_y.min(y);
-            max_x = max_x.max(x);
-            max_y = max_y.max(y);
-
-            let range_x = min_x..max_x;
-            let range_y = min_y..max_y;
-
-            ranges.push((range_x, range_y));
-        }
-
-        // Turn all the vecs and strings into arcs since they are more or less read-only at
-        // this point
-
-        let points: Arc<[(f32, f32)]> = points.into();
-        let ranges: Arc<[(Range<f32>, Range<f32>)]> = ranges.into();
-        let times: Arc<[f32]> = times.into();
+        let Dataset {
+            points,
+            times,
+            extents,
+            monotonic,
+        } = Dataset::new(points);
 
         let x_unit: Arc<str> = x_unit.into();
         let y_unit: Arc<str> = y_unit.into();
-        let caption: Arc<str> = caption.into();
-
-        let grid_style = ShapeStyle {
-            color: GREY.to_rgba(),
-            filled: false,
-            stroke_width: 2,
-        };
-
-        let subgrid_style = ShapeStyle {
-            color: GREY_700.to_rgba(),
-            filled: false,
-            stroke_width: 1,
-        };
+        let caption: Option<Arc<str>> = Some(caption.into());
 
         let axes_style = ShapeStyle {
             color: BLACK.to_rgba(),
@@ -171,32 +415,112 @@ impl XyTimeData {
         let text_color = BLACK.to_rgba();
 
         let config = XyTimeConfig {
-            points: points.clone(),
-            range: ranges.last().unwrap().clone(),
+            all_points: points.clone(),
+            visible: points.len(),
+            times: times.clone(),
+            range: extents.total().unwrap(),
+            autoscale: AutoscaleMode::default(),
             line_style,
-            grid_style,
-            subgrid_style,
+            trail: false,
+            ghost_style: None,
+            playhead_style: None,
+            x_grid: GridConfig::default(),
+            y_grid: GridConfig::default(),
             axes_style,
             text_color,
             background_color,
             x_unit,
             y_unit,
+            x_si_unit: None,
+            y_si_unit: None,
             ratio: DEFAULT_RATIO,
+            y_break: None,
             caption,
+            text_fit: TextFit::default(),
+            x_margin: DEFAULT_X_MARGIN,
+            y_margin: DEFAULT_Y_MARGIN,
+            x_label_area: DEFAULT_LABEL_AREA,
+            y_label_area: DEFAULT_LABEL_AREA,
+            x_axis: XAxisPosition::default(),
+            y_axis: YAxisPosition::default(),
+            tick_size: DEFAULT_TICK_SIZE,
+            decimation: false,
+            degraded: false,
+            envelope: true,
+            envelope_style: None,
+            monotonic,
+            line_colormap: None,
+            bookmarks: Vec::new(),
+            fitted_view: None,
+            mapping: Rc::new(Cell::new(None)),
         };
 
+        let time_map = TimeMap::new(&times, TimeMapping::default());
+        let member = Member::new(times[0], time_map.duration());
+        let playback = Rc::new(RefCell::new(Playback::new(0.0)));
+
+        playback.borrow_mut().join(&member);
+
+        let shared = Rc::new(RefCell::new(SharedSeries {
+            playback: playback.clone(),
+            member: member.clone(),
+            points: points.clone(),
+            times: times.clone(),
+            time_map: time_map.clone(),
+        }));
+
         let chart = Chart::new(config)
             .mouse(MouseConfig::enabled())
-            .builder_cb(Box::new(|area, _t, data| {
+            .on_reset(|data| data.fitted_view = None)
+            .csv_cb(Box::new(|transform, data| {
+                // Only the points in view are exported once the chart has been drawn
+                let view = data.mapping.get().map(|mapping| mapping.view(transform));
+                let in_view = |&(_, (x, y)): &(f32, (f32, f32))| match &view {
+                    Some((x_view, y_view)) => {
+                        (x_view.start..=x_view.end).contains(&x)
+                            && (y_view.start..=y_view.end).contains(&y)
+                    }
+                    None => true,
+                };
+
+                let rows = data
+                    .times
+                    .iter()
+                    .copied()
+                    .zip(data.points().iter().copied())
+                    .filter(in_view);
+
+                csv_lines(locale(), &data.x_unit, &data.y_unit, rows).collect()
+            }))
+            .builder_cb(Box::new(|area, transform, data| {
+                data.text_fit.clear();
+
+                // Hidden axes don't get any space reserved for their labels
+                let x_label_area = data
+                    .x_axis
+                    .label_area()
+                    .map(|position| (position, data.x_label_area));
+                let y_label_area = data
+                    .y_axis
+                    .label_area()
+                    .map(|position| (position, data.y_label_area));
+
                 let area_ratio = {
                     let (x_range, y_range) = area.get_pixel_range();
 
-                    let x_delta =
-                        ((x_range.end - x_range.start).abs() - (X_MARGIN * 2) - LABEL_AREA) as f32;
+                    let caption_size = match data.caption {
+                        Some(_) => CAPTION_SIZE,
+                        None => 0,
+                    };
+
+                    let x_delta = ((x_range.end - x_range.start).abs()
+                        - (data.x_margin * 2)
+                        - y_label_area.map_or(0, |(_, size)| size))
+                        as f32;
                     let y_delta = ((y_range.end - y_range.start).abs()
-                        - (Y_MARGIN * 2)
-                        - LABEL_AREA
-                        - CAPTION_SIZE) as f32;
+                        - (data.y_margin * 2)
+                        - x_label_area.map_or(0, |(_, size)| size)
+                        - caption_size) as f32;
 
                     x_delta / y_delta
                 };
@@ -212,19 +536,32 @@ impl XyTimeData {
                 // want to make sure the X axis is expanded to compensate
                 let data_ratio = {
                     let x_delta = (x_range.end - x_range.start).abs();
-                    let y_delta = (y_range.end - y_range.start).abs();
+                    // The values skipped by the break take up no space
+                    let y_delta = data
+                        .y_break
+                        .as_ref()
+                        .and_then(|skipped| AxisBreak::new(&y_range, skipped))
+                        .map_or(y_range.end, |y_break| y_break.fold(y_range.end))
+                        - y_range.start;
 
                     y_delta / x_delta
                 };
 
                 let display_ratio = data.ratio * data_ratio * area_ratio;
 
-                let (x_range, y_range) =
-                    match display_ratio.partial_cmp(&1.0).unwrap_or(Ordering::Equal) {
-                        Ordering::Equal => (x_range, y_range),
-                        Ordering::Greater => (mult_range(x_range, display_ratio), y_range),
-                        Ordering::Less => (x_range, mult_range(y_range, 1.0 / display_ratio)),
-                    };
+                // A Y axis fit to the window is left as it is, with only the X axis giving way
+                let fit_y = data.autoscale == AutoscaleMode::VisibleWindow;
+
+                let (x_range, y_range) = match display_ratio
+                    .partial_cmp(&1.0)
+                    .unwrap_or(Ordering::Equal)
+                {
+                    Ordering::Greater => (mult_range(x_range, display_ratio), y_range),
+                    Ordering::Less if !fit_y => (x_range, mult_range(y_range, 1.0 / display_ratio)),
+                    _ => (x_range, y_range),
+                };
+
+                let (x_range, y_range) = data.fitted_view.clone().unwrap_or((x_range, y_range));
 
                 let font_style = FontStyle::Normal;
                 let font_family = FontFamily::Monospace;
@@ -234,61 +571,305 @@ impl XyTimeData {
 
                 let text_style = TextStyle::from(font_desc).color(&data.text_color);
 
-                let mut chart = ChartBuilder::on(area)
-                    .caption(data.caption.clone(), text_style.clone())
-                    .x_label_area_size(LABEL_AREA)
-                    .y_label_area_size(LABEL_AREA)
-                    .margin_left(X_MARGIN)
-                    .margin_right(X_MARGIN)
-                    .margin_top(Y_MARGIN)
-                    .margin_bottom(Y_MARGIN)
-                    .build_cartesian_2d(x_range, y_range)
-                    .unwrap();
-
-                chart
-                    .configure_mesh()
+                let mut builder = ChartBuilder::on(area);
+
+                for (position, size) in x_label_area.into_iter().chain(y_label_area) {
+                    builder.set_label_area_size(position, size);
+                }
+
+                builder
+                    .margin_left(data.x_margin)
+                    .margin_right(data.x_margin)
+                    .margin_top(data.y_margin)
+                    .margin_bottom(data.y_margin);
+
+                if let Some(caption) = &data.caption {
+                    let margins = (data.x_margin, data.y_margin);
+                    let caption = data.text_fit.caption(area, margins, caption, &text_style);
+
+                    builder.caption(caption, text_style.clone());
+                }
+
+                let y_axis = BrokenAxis::new(y_range, data.y_break.as_ref());
+                let y_break = y_axis.get_break();
+
+                let mut chart = builder.build_cartesian_2d(x_range, y_axis.clone()).unwrap();
+
+                let (x_pixels, y_pixels) = chart.plotting_area().get_pixel_range();
+                let (x_range, y_range) = (chart.x_range(), chart.y_range());
+                let (width, height) = area.dim_in_pixel();
+
+                let mapping = PlotMapping {
+                    x: (x_range.start, x_range.end),
+                    y: (y_axis.fold(y_range.start), y_axis.fold(y_range.end)),
+                    y_break,
+                    x_pixels: (x_pixels.start, x_pixels.end),
+                    y_pixels: (y_pixels.start, y_pixels.end),
+                    size: (width as f32, height as f32),
+                };
+
+                data.mapping.set(Some(mapping));
+
+                draw_grid(&mut chart, &data.x_grid, &data.y_grid);
+
+                // Prefix and precision follow the range in view as the chart is zoomed
+                let (x_view, y_view) = mapping.view(transform);
+                let (x_scale, x_desc) = si_axis(&x_view, &data.x_unit, data.x_si_unit.as_deref());
+                let (y_scale, y_desc) = si_axis(&y_view, &data.y_unit, data.y_si_unit.as_deref());
+
+                let plot = chart.plotting_area();
+                let fit_desc = |desc, label_area: Option<(LabelAreaPosition, i32)>| {
+                    label_area.map_or(Cow::Borrowed(desc), |(position, size)| {
+                        data.text_fit
+                            .axis_desc(plot, position, size, desc, &text_style)
+                    })
+                };
+                let (x_desc, y_desc) = (
+                    fit_desc(&x_desc, x_label_area),
+                    fit_desc(&y_desc, y_label_area),
+                );
+
+                let locale = locale();
+                let x_label = |x: &f32| {
+                    x_scale.map_or_else(|| locale.format_tick(*x as f64), |scale| scale.format(*x))
+                };
+                let y_label = |y: &f32| {
+                    y_scale.map_or_else(|| locale.format_tick(*y as f64), |scale| scale.format(*y))
+                };
+
+                let mut mesh = chart.configure_mesh();
+
+                mesh.disable_mesh()
+                    .x_labels(data.x_grid.get_major_lines())
+                    .y_labels(data.y_grid.get_major_lines())
                     .label_style(text_style.clone())
-                    .bold_line_style(data.grid_style)
-                    .light_line_style(data.subgrid_style)
                     .axis_style(data.axes_style)
-                    .x_desc(&data.x_unit.to_string())
-                    .set_all_tick_mark_size(4)
-                    .y_desc(&data.y_unit.to_string())
-                    .draw()
-                    .unwrap();
-
-                chart
-                    .draw_series(LineSeries::new(data.points.to_vec(), data.line_style))
-                    .unwrap();
+                    .x_desc(x_desc)
+                    .set_all_tick_mark_size(data.tick_size)
+                    .y_desc(y_desc)
+                    .x_label_formatter(&x_label)
+                    .y_label_formatter(&y_label);
+
+                if x_label_area.is_none() {
+                    mesh.disable_x_axis();
+                }
+
+                if y_label_area.is_none() {
+                    mesh.disable_y_axis();
+                }
+
+                mesh.draw().unwrap();
+
+                // Values above the break are drawn higher up, so its end is its top
+                if let Some(y_break) = y_break {
+                    let (_, top) = chart.backend_coord(&(x_range.start, y_break.end()));
+                    let (_, bottom) = chart.backend_coord(&(x_range.start, y_break.start()));
+
+                    draw_break(
+                        area,
+                        x_pixels.clone(),
+                        top..bottom,
+                        data.axes_style,
+                        data.background_color,
+                    );
+                }
+
+                // Decimate series with more points than there are pixel columns to draw them in
+                let (columns, _) = chart.plotting_area().dim_in_pixel();
+                let x_range = chart.x_range();
+
+                let decimated = |points: &[(f32, f32)]| {
+                    let columns = columns as usize;
+
+                    ((data.decimation || data.degraded)
+                        && data.monotonic
+                        && points.len() > columns * 2)
+                        .then(|| decimate(points, x_range.clone(), columns))
+                };
+
+                // Only show the trail while there are points left to be played
+                let trail = data.trail && data.points().len() < data.all_points.len();
+
+                if trail {
+                    let ghost_style = data.ghost_style.unwrap_or(ShapeStyle {
+                        color: data.line_style.color.mix(GHOST_OPACITY),
+                        ..data.line_style
+                    });
+
+                    // Start the ghost at the current point so the line stays connected
+                    let ghost = &data.all_points[data.points().len().saturating_sub(1)..];
+
+                    let ghost = match decimated(ghost) {
+                        Some(decimated) => decimated
+                            .line()
+                            .into_iter()
+                            .map(|index| ghost[index])
+                            .collect(),
+                        None => ghost.to_vec(),
+                    };
+
+                    chart
+                        .draw_series(LineSeries::new(ghost, ghost_style))
+                        .unwrap();
+                }
+
+                let line = match decimated(data.points()) {
+                    Some(decimated) => {
+                        // Show the range of every column so spikes aren't lost
+                        if data.envelope {
+                            let envelope_style = data
+                                .envelope_style
+                                .unwrap_or(data.line_style.color.mix(ENVELOPE_OPACITY).filled());
+
+                            let envelope = decimated.columns.iter().map(|column| {
+                                Rectangle::new(
+                                    [(column.x.start, column.min), (column.x.end, column.max)],
+                                    envelope_style,
+                                )
+                            });
+
+                            chart.draw_series(envelope).unwrap();
+                        }
+
+                        decimated.line()
+                    }
+                    None => (0..data.points().len()).collect(),
+                };
+
+                match &data.line_colormap {
+                    // Color each segment by the time of the point it ends at
+                    Some(colormap) => {
+                        let time_start = data.times.first().copied().unwrap_or(0.0);
+                        let time_end = data.times.last().copied().unwrap_or(0.0);
+                        let duration = time_end - time_start;
+
+                        let segments = line.windows(2).map(|pair| {
+                            let position = match duration > 0.0 {
+                                true => (data.times[pair[1]] - time_start) / duration,
+                                false => 1.0,
+                            };
+
+                            let style = ShapeStyle {
+                                color: colormap.color(position),
+                                ..data.line_style
+                            };
+
+                            PathElement::new(
+                                vec![data.points()[pair[0]], data.points()[pair[1]]],
+                                style,
+                            )
+                        });
+
+                        chart.draw_series(segments).unwrap();
+                    }
+                    None => {
+                        let line = line.into_iter().map(|index| data.points()[index]);
+
+                        chart
+                            .draw_series(LineSeries::new(line, data.line_style))
+                            .unwrap();
+                    }
+                }
+
+                // Mark the bookmarks reached so far at the points shown at their times
+                let bookmark_style = ShapeStyle {
+                    color: data.text_color,
+                    filled: false,
+                    stroke_width: 1,
+                };
+
+                let bookmarks = data.bookmarks.iter().filter_map(|(name, time)| {
+                    let point = *data.points().get(time_index(&data.times, *time))?;
+
+                    Some(
+                        EmptyElement::at(point)
+                            + Circle::new((0, 0), BOOKMARK_SIZE, bookmark_style)
+                            + Text::new(
+                                name.to_string(),
+                                (BOOKMARK_SIZE, -BOOKMARK_SIZE - CAPTION_SIZE),
+                                text_style.clone(),
+                            ),
+                    )
+                });
+
+                chart.draw_series(bookmarks).unwrap();
+
+                if trail {
+                    let playhead_style = data.playhead_style.unwrap_or(ShapeStyle {
+                        filled: true,
+                        ..data.line_style
+                    });
+
+                    if let Some(point) = data.points().last() {
+                        chart
+                            .draw_series([Circle::new(*point, PLAYHEAD_SIZE, playhead_style)])
+                            .unwrap();
+                    }
+                }
             }));
 
         Self {
-            playback_start: None,
-            pause_start: None,
-            playback_speed: 1.0,
+            playback,
+            member,
             points,
-            ranges,
+            extents,
             times,
+            time_cache: TimeIndexCache::default(),
+            time_map,
+            shared,
+            zoom_limits: None,
+            pan_bounds: None,
+            minimap: None,
+            minimap_height: DEFAULT_MINIMAP_HEIGHT,
+            data_table: DataTableMode::default(),
+            data_table_width: DEFAULT_TABLE_WIDTH,
+            clip_indicators: false,
+            axis_autoscale: true,
+            overlay_cb: None,
+            link: None,
             chart,
         }
     }
 
-    /// Set the time to resume playback at. Time is in seconds.
+    /// Replace the points with a new dataset, keeping the styling, view, bookmarks, and playback
+    /// settings of the chart. Playback continues from the same time in the new data. Empty
+    /// datasets are ignored, as the chart needs at least one point.
+    pub fn set_points(&mut self, points: &[(f32, f32, f32)]) {
+        let Dataset {
+            points,
+            times,
+            extents,
+            monotonic,
+        } = Dataset::new(points);
+
+        let Some(range) = extents.total() else {
+            return;
+        };
+
+        let config = self.chart.get_data_mut();
+
+        config.all_points = points.clone();
+        config.visible = points.len();
+        config.times = times.clone();
+        config.range = range;
+        config.monotonic = monotonic;
+
+        self.time_map = TimeMap::new(&times, self.time_map.mapping());
+        self.points = points;
+        self.times = times;
+        self.extents = extents;
+        self.time_cache = TimeIndexCache::default();
+
+        self.fit_playback_duration();
+        self.share();
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds since the start of the data.
     pub fn set_time(&mut self, time: f32) {
-        let start_time = Some(Instant::now() - Duration::from_secs_f32(time));
-        match self.playback_start {
-            Some(_) => {
-                if let Some(_) = self.pause_start {
-                    self.pause_start = Some(Instant::now());
-                }
+        let time = self.member.clock_time(self.time_map.to_playback(time));
 
-                self.playback_start = start_time;
-            }
-            None => {
-                self.playback_start = start_time;
-                self.pause_start = Some(Instant::now());
-            }
-        }
+        self.playback.borrow_mut().set_time(time)
     }
 
     #[inline]
@@ -299,10 +880,18 @@ impl XyTimeData {
         self
     }
 
+    /// Move playback forward, or backward if negative, by some seconds of data time from the
+    /// current time. Starts playback paused if it wasn't underway.
+    pub fn seek_relative(&mut self, delta: f32) {
+        let time = self.current_time() - self.start_time() + delta;
+
+        self.set_time(time.max(0.0))
+    }
+
     #[inline]
     /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half.
     pub fn set_playback_speed(&mut self, speed: f32) {
-        self.playback_speed = speed;
+        self.playback.borrow_mut().set_speed(speed)
     }
 
     #[inline]
@@ -326,32 +915,66 @@ impl XyTimeData {
         self
     }
 
-    /// Set the style of the grid.
+    /// Set the style of the major grid lines on both axes.
     pub fn set_grid_style(&mut self, grid_style: ShapeStyle) {
-        self.chart.get_data_mut().grid_style = grid_style
+        let config = self.chart.get_data_mut();
+
+        config.x_grid.set_major_style(grid_style);
+        config.y_grid.set_major_style(grid_style);
     }
 
     #[inline]
-    /// Set the style of the grid. Consumes self.
+    /// Set the style of the major grid lines on both axes. Consumes self.
     pub fn grid_style(mut self, grid_style: ShapeStyle) -> Self {
         self.set_grid_style(grid_style);
 
         self
     }
 
-    /// Set the style of the subgrid.
+    /// Set the style of the minor grid lines on both axes.
     pub fn set_subgrid_style(&mut self, subgrid_style: ShapeStyle) {
-        self.chart.get_data_mut().subgrid_style = subgrid_style
+        let config = self.chart.get_data_mut();
+
+        config.x_grid.set_minor_style(subgrid_style);
+        config.y_grid.set_minor_style(subgrid_style);
     }
 
     #[inline]
-    /// Set the style of the subgrid. Consumes self.
+    /// Set the style of the minor grid lines on both axes. Consumes self.
     pub fn subgrid_style(mut self, subgrid_style: ShapeStyle) -> Self {
         self.set_subgrid_style(subgrid_style);
 
         self
     }
 
+    #[inline]
+    /// Configure the grid lines of the X axis.
+    pub fn set_x_grid(&mut self, x_grid: GridConfig) {
+        self.chart.get_data_mut().x_grid = x_grid
+    }
+
+    #[inline]
+    /// Configure the grid lines of the X axis. Consumes self.
+    pub fn x_grid(mut self, x_grid: GridConfig) -> Self {
+        self.set_x_grid(x_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Configure the grid lines of the Y axis.
+    pub fn set_y_grid(&mut self, y_grid: GridConfig) {
+        self.chart.get_data_mut().y_grid = y_grid
+    }
+
+    #[inline]
+    /// Configure the grid lines of the Y axis. Consumes self.
+    pub fn y_grid(mut self, y_grid: GridConfig) -> Self {
+        self.set_y_grid(y_grid);
+
+        self
+    }
+
     /// Set the style of the axes.
     pub fn set_axes_style(&mut self, axes_style: ShapeStyle) {
         self.chart.get_data_mut().axes_style = axes_style
@@ -408,82 +1031,1351 @@ impl XyTimeData {
     }
 
     #[inline]
-    /// Set the ratio between X and Y values, default being 1 x unit to 1 y unit.
-    pub fn set_ratio(&mut self, ratio: f32) {
-        self.chart.get_data_mut().ratio = ratio
+    /// Enable/disable the playback trail. When enabled the points yet to be played are shown as
+    /// a faint ghost, a playhead marks the current point, and the chart is kept at the range of
+    /// the full dataset during playback.
+    pub fn set_trail(&mut self, trail: bool) {
+        self.chart.get_data_mut().trail = trail
     }
 
     #[inline]
-    /// Set the ratio between X and Y values, default being 1 x unit to 1 y unit. Consumes self.
-    pub fn ratio(mut self, ratio: f32) -> Self {
-        self.set_ratio(ratio);
+    /// Enable/disable the playback trail. Consumes self.
+    pub fn trail(mut self, trail: bool) -> Self {
+        self.set_trail(trail);
 
         self
     }
 
-    /// Draw the chart to a Ui. Will also proceed to animate the chart if playback is currently
-    /// enabled.
-    pub fn draw(&mut self, ui: &mut Ui) {
-        if let Some(_) = self.playback_start {
-            let time = self.current_time();
-
-            let time_index = match self
-                .times
-                .binary_search_by(|probe| probe.partial_cmp(&time).unwrap_or(Ordering::Equal))
-            {
-                Ok(index) => index,
-                Err(index) => self.points.len().min(index),
-            };
+    #[inline]
+    /// Set how the ranges of the axes follow playback, fitting every point, growing with the
+    /// points played, or fitting the Y axis to the points in view. See [`AutoscaleMode`].
+    pub fn set_autoscale_mode(&mut self, autoscale: AutoscaleMode) {
+        self.chart.get_data_mut().autoscale = autoscale;
 
-            // The time index is always a valid index, so ensure the range is inclusive
-            let points = &self.points[..=time_index];
-            let range = self.ranges[time_index].clone();
+        self.update_range();
+    }
 
-            let config = self.chart.get_data_mut();
-            config.points = points.into();
-            config.range = range;
-        }
+    #[inline]
+    /// Set how the ranges of the axes follow playback. Consumes self.
+    pub fn autoscale_mode(mut self, autoscale: AutoscaleMode) -> Self {
+        self.set_autoscale_mode(autoscale);
 
-        self.chart.draw(ui);
+        self
     }
 
     #[inline]
-    /// Start/enable playback of the chart.
-    pub fn start_playback(&mut self) {
-        self.playback_start = Some(Instant::now());
-        self.pause_start = None;
+    /// Get how the ranges of the axes follow playback.
+    pub fn get_autoscale_mode(&self) -> AutoscaleMode {
+        self.chart.get_data().autoscale
     }
 
-    #[inline]
-    /// Stop/disable playback of the chart.
-    pub fn stop_playback(&mut self) {
-        self.playback_start = None;
-        self.pause_start = None;
+    /// Set the style of the ghost trail. Defaults to a faint version of the line style.
+    pub fn set_ghost_style(&mut self, ghost_style: ShapeStyle) {
+        self.chart.get_data_mut().ghost_style = Some(ghost_style)
     }
 
-    /// Toggle playback of the chart.
-    pub fn toggle_playback(&mut self) {
-        match self.playback_start {
-            Some(playback_start) => match self.pause_start {
-                Some(pause_start) => {
-                    let delta = Instant::now().duration_since(pause_start);
+    #[inline]
+    /// Set the style of the ghost trail. Consumes self.
+    pub fn ghost_style(mut self, ghost_style: ShapeStyle) -> Self {
+        self.set_ghost_style(ghost_style);
 
-                    self.pause_start = None;
-                    self.playback_start = Some(playback_start + delta);
-                }
-                None => self.pause_start = Some(Instant::now()),
-            },
+        self
+    }
 
-            None => {
-                self.start_playback();
-            }
-        }
+    /// Set the style of the playhead. Defaults to a filled version of the line style.
+    pub fn set_playhead_style(&mut self, playhead_style: ShapeStyle) {
+        self.chart.get_data_mut().playhead_style = Some(playhead_style)
+    }
+
+    #[inline]
+    /// Set the style of the playhead. Consumes self.
+    pub fn playhead_style(mut self, playhead_style: ShapeStyle) -> Self {
+        self.set_playhead_style(playhead_style);
+
+        self
+    }
+
+    /// Set the caption of the chart, or hide it with None.
+    pub fn set_caption(&mut self, caption: Option<&str>) {
+        self.chart.get_data_mut().caption = caption.map(|caption| caption.into())
+    }
+
+    #[inline]
+    /// Set the caption of the chart, or hide it with None. Consumes self.
+    pub fn caption(mut self, caption: Option<&str>) -> Self {
+        self.set_caption(caption);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable shortening the caption and axis descriptions too long for
+    /// the chart with an ellipsis. Their full text is shown when hovered. Enabled by default.
+    pub fn set_text_truncation(&mut self, enabled: bool) {
+        self.chart.get_data_mut().text_fit.set_enabled(enabled)
+    }
+
+    #[inline]
+    /// Enable/disable shortening text too long for the chart. Consumes self.
+    pub fn text_truncation(mut self, enabled: bool) -> Self {
+        self.set_text_truncation(enabled);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if text too long for the chart is shortened.
+    pub fn get_text_truncation(&self) -> bool {
+        self.chart.get_data().text_fit.is_enabled()
+    }
+
+    /// Set the margins around the chart in pixels. The X margin is applied to the left and
+    /// right, and the Y margin to the top and bottom.
+    pub fn set_margins(&mut self, x_margin: i32, y_margin: i32) {
+        let config = self.chart.get_data_mut();
+
+        config.x_margin = x_margin;
+        config.y_margin = y_margin;
+    }
+
+    #[inline]
+    /// Set the margins around the chart in pixels. Consumes self.
+    pub fn margins(mut self, x_margin: i32, y_margin: i32) -> Self {
+        self.set_margins(x_margin, y_margin);
+
+        self
+    }
+
+    /// Set the size of the label areas in pixels. The X label area is the one under the X axis,
+    /// and the Y label area the one beside the Y axis.
+    pub fn set_label_area_size(&mut self, x_label_area: i32, y_label_area: i32) {
+        let config = self.chart.get_data_mut();
+
+        config.x_label_area = x_label_area;
+        config.y_label_area = y_label_area;
+    }
+
+    #[inline]
+    /// Set the size of the label areas in pixels. Consumes self.
+    pub fn label_area_size(mut self, x_label_area: i32, y_label_area: i32) -> Self {
+        self.set_label_area_size(x_label_area, y_label_area);
+
+        self
+    }
+
+    #[inline]
+    /// Set the side of the chart the X axis and its labels are placed on, or hide it.
+    pub fn set_x_axis(&mut self, x_axis: XAxisPosition) {
+        self.chart.get_data_mut().x_axis = x_axis
+    }
+
+    #[inline]
+    /// Set the side of the chart the X axis and its labels are placed on, or hide it. Consumes
+    /// self.
+    pub fn x_axis(mut self, x_axis: XAxisPosition) -> Self {
+        self.set_x_axis(x_axis);
+
+        self
+    }
+
+    #[inline]
+    /// Set the side of the chart the Y axis and its labels are placed on, or hide it.
+    pub fn set_y_axis(&mut self, y_axis: YAxisPosition) {
+        self.chart.get_data_mut().y_axis = y_axis
+    }
+
+    #[inline]
+    /// Set the side of the chart the Y axis and its labels are placed on, or hide it. Consumes
+    /// self.
+    pub fn y_axis(mut self, y_axis: YAxisPosition) -> Self {
+        self.set_y_axis(y_axis);
+
+        self
+    }
+
+    #[inline]
+    /// Set the size of the tick marks in pixels.
+    pub fn set_tick_size(&mut self, tick_size: i32) {
+        self.chart.get_data_mut().tick_size = tick_size
+    }
+
+    #[inline]
+    /// Set the size of the tick marks in pixels. Consumes self.
+    pub fn tick_size(mut self, tick_size: i32) -> Self {
+        self.set_tick_size(tick_size);
+
+        self
+    }
+
+    /// Color the line along time with a colormap, going from the start of the colormap at the
+    /// first point to its end at the last. Set to None to go back to the line style's color.
+    pub fn set_line_colormap<C>(&mut self, colormap: Option<C>)
+    where
+        C: Colormap + 'static,
+    {
+        self.chart.get_data_mut().line_colormap =
+            colormap.map(|colormap| Arc::new(colormap) as Arc<dyn Colormap>)
+    }
+
+    #[inline]
+    /// Color the line along time with a colormap. Consumes self.
+    pub fn line_colormap<C>(mut self, colormap: Option<C>) -> Self
+    where
+        C: Colormap + 'static,
+    {
+        self.set_line_colormap(colormap);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable decimation. When enabled and there are more points than pixel columns,
+    /// the points in each column are reduced to a single point of the line and, unless disabled
+    /// with `.set_envelope()`, an envelope showing their range. Only applies to charts whose
+    /// X values never decrease over time.
+    pub fn set_decimation(&mut self, decimation: bool) {
+        self.chart.get_data_mut().decimation = decimation
+    }
+
+    #[inline]
+    /// Enable/disable decimation. Consumes self.
+    pub fn decimation(mut self, decimation: bool) -> Self {
+        self.set_decimation(decimation);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide the min/max envelope drawn around decimated lines. Shown by default.
+    pub fn set_envelope(&mut self, envelope: bool) {
+        self.chart.get_data_mut().envelope = envelope
+    }
+
+    #[inline]
+    /// Show/hide the min/max envelope drawn around decimated lines. Consumes self.
+    pub fn envelope(mut self, envelope: bool) -> Self {
+        self.set_envelope(envelope);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the min/max envelope. Defaults to a faint fill of the line color.
+    pub fn set_envelope_style(&mut self, envelope_style: ShapeStyle) {
+        self.chart.get_data_mut().envelope_style = Some(envelope_style)
+    }
+
+    #[inline]
+    /// Set the style of the min/max envelope. Consumes self.
+    pub fn envelope_style(mut self, envelope_style: ShapeStyle) -> Self {
+        self.set_envelope_style(envelope_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the ratio between X and Y values, default being 1 x unit to 1 y unit.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.chart.get_data_mut().ratio = ratio
+    }
+
+    #[inline]
+    /// Set the ratio between X and Y values, default being 1 x unit to 1 y unit. Consumes self.
+    pub fn ratio(mut self, ratio: f32) -> Self {
+        self.set_ratio(ratio);
+
+        self
+    }
+
+    #[inline]
+    /// Skip a range of Y values, such as 100.0..10_000.0, drawing the Y axis with a break in
+    /// their place. Keeps widely separated clusters of values readable without a log scale. The
+    /// values skipped are squeezed into the break, so lines crossing it stay connected. Not
+    /// broken if None.
+    pub fn set_y_break(&mut self, y_break: Option<Range<f32>>) {
+        self.chart.get_data_mut().y_break = y_break
+    }
+
+    #[inline]
+    /// Skip a range of Y values, drawing the Y axis with a break in their place. Consumes self.
+    pub fn y_break(mut self, y_break: Option<Range<f32>>) -> Self {
+        self.set_y_break(y_break);
+
+        self
+    }
+
+    #[inline]
+    /// Get the range of Y values skipped by the Y axis, if any.
+    pub fn get_y_break(&self) -> Option<Range<f32>> {
+        self.chart.get_data().y_break.clone()
+    }
+
+    #[inline]
+    /// Set the SI units of the X and Y axes, such as "s", "B", or "V". Tick labels of an axis
+    /// with a unit are scaled with an SI prefix to fit its range, such as ms, kB, or MV, and the
+    /// prefixed unit is added to its description. Axes with None are left unscaled.
+    pub fn set_si_units(&mut self, x_si_unit: Option<&str>, y_si_unit: Option<&str>) {
+        let config = self.chart.get_data_mut();
+
+        config.x_si_unit = x_si_unit.map(|unit| unit.into());
+        config.y_si_unit = y_si_unit.map(|unit| unit.into());
+    }
+
+    #[inline]
+    /// Set the SI units of the X and Y axes. Consumes self.
+    pub fn si_units(mut self, x_si_unit: Option<&str>, y_si_unit: Option<&str>) -> Self {
+        self.set_si_units(x_si_unit, y_si_unit);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide an overview strip below the chart. The strip shows every point, with a window
+    /// over the part of the chart in view that can be dragged to pan the chart, or resized by
+    /// its edges to zoom it.
+    pub fn set_minimap(&mut self, minimap: bool) {
+        self.minimap = minimap.then(|| Minimap::new(self.chart.get_id()))
+    }
+
+    #[inline]
+    /// Show/hide an overview strip below the chart. Consumes self.
+    pub fn minimap(mut self, minimap: bool) -> Self {
+        self.set_minimap(minimap);
+
+        self
+    }
+
+    #[inline]
+    /// Set the height of the overview strip, in pixels.
+    pub fn set_minimap_height(&mut self, minimap_height: f32) {
+        self.minimap_height = minimap_height
+    }
+
+    #[inline]
+    /// Set the height of the overview strip, in pixels. Consumes self.
+    pub fn minimap_height(mut self, minimap_height: f32) -> Self {
+        self.set_minimap_height(minimap_height);
+
+        self
+    }
+
+    #[inline]
+    /// Set where a table of the points is shown, with a row of time, X, and Y per point. The
+    /// table follows playback, dimming the points yet to be played, and shares hovered and
+    /// selected points with the chart's link group. See [`DataTableMode`].
+    pub fn set_data_table(&mut self, data_table: DataTableMode) {
+        self.data_table = data_table
+    }
+
+    #[inline]
+    /// Set where a table of the points is shown. Consumes self.
+    pub fn data_table(mut self, data_table: DataTableMode) -> Self {
+        self.set_data_table(data_table);
+
+        self
+    }
+
+    #[inline]
+    /// Get where the table of the points is shown.
+    pub fn get_data_table(&self) -> DataTableMode {
+        self.data_table
+    }
+
+    #[inline]
+    /// Set the width of the table when shown beside the chart, in pixels. Negative and
+    /// non-finite widths are ignored. The table is narrowed to fit if the chart is too narrow.
+    pub fn set_data_table_width(&mut self, data_table_width: f32) {
+        if data_table_width.is_finite() && data_table_width >= 0.0 {
+            self.data_table_width = data_table_width
+        }
+    }
+
+    #[inline]
+    /// Set the width of the table when shown beside the chart, in pixels. Consumes self.
+    pub fn data_table_width(mut self, data_table_width: f32) -> Self {
+        self.set_data_table_width(data_table_width);
+
+        self
+    }
+
+    /// Limit how far the chart can be zoomed, as the smallest and largest span of X and Y values
+    /// that can be shown. Keeps the user from zooming into numerical noise or so far out the data
+    /// disappears. Use 0.0 and `f32::INFINITY` to leave a limit open.
+    pub fn set_zoom_limits(
+        &mut self,
+        min_span_x: f32,
+        max_span_x: f32,
+        min_span_y: f32,
+        max_span_y: f32,
+    ) {
+        self.zoom_limits = Some((min_span_x..max_span_x, min_span_y..max_span_y))
+    }
+
+    #[inline]
+    /// Limit how far the chart can be zoomed. Consumes self.
+    pub fn zoom_limits(
+        mut self,
+        min_span_x: f32,
+        max_span_x: f32,
+        min_span_y: f32,
+        max_span_y: f32,
+    ) -> Self {
+        self.set_zoom_limits(min_span_x, max_span_x, min_span_y, max_span_y);
+
+        self
+    }
+
+    #[inline]
+    /// Set how carefully the chart is drawn. See [`RenderQuality`].
+    pub fn set_render_quality(&mut self, quality: RenderQuality) {
+        self.chart.set_render_quality(quality)
+    }
+
+    #[inline]
+    /// Set how carefully the chart is drawn. Consumes self.
+    pub fn render_quality(mut self, quality: RenderQuality) -> Self {
+        self.set_render_quality(quality);
+
+        self
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Set the controller lowering the chart's quality while it's too slow to draw, during
+    /// playback as well as while it's moved. While lowered, the chart is drawn in fast mode and
+    /// its points are decimated. See [`AdaptiveQuality`].
+    pub fn set_adaptive_quality(&mut self, adaptive: Option<AdaptiveQuality>) {
+        self.chart.set_adaptive_quality(adaptive)
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Set the controller lowering the chart's quality while it's too slow to draw. Consumes
+    /// self.
+    pub fn adaptive_quality(mut self, adaptive: Option<AdaptiveQuality>) -> Self {
+        self.set_adaptive_quality(adaptive);
+
+        self
+    }
+
+    #[cfg(feature = "adaptive")]
+    #[inline]
+    /// Get the controller lowering the chart's quality, if any, along with how long the chart
+    /// takes to draw.
+    pub fn get_adaptive_quality(&self) -> Option<&AdaptiveQuality> {
+        self.chart.get_adaptive_quality()
+    }
+
+    #[inline]
+    /// Remove the zoom limits.
+    pub fn clear_zoom_limits(&mut self) {
+        self.zoom_limits = None;
+        self.chart.set_scale_limits(None);
+    }
+
+    #[inline]
+    /// Limit how far the chart can be dragged, keeping the center of the view within the given
+    /// X and Y ranges.
+    pub fn set_pan_bounds(&mut self, x_range: Range<f32>, y_range: Range<f32>) {
+        self.pan_bounds = Some((x_range, y_range))
+    }
+
+    #[inline]
+    /// Limit how far the chart can be dragged. Consumes self.
+    pub fn pan_bounds(mut self, x_range: Range<f32>, y_range: Range<f32>) -> Self {
+        self.set_pan_bounds(x_range, y_range);
+
+        self
+    }
+
+    #[inline]
+    /// Remove the pan bounds.
+    pub fn clear_pan_bounds(&mut self) {
+        self.pan_bounds = None;
+        self.chart.set_pan_bounds(None);
+    }
+
+    #[inline]
+    /// Show/hide badges at the edges of the plot counting the points clipped out of view past
+    /// them. Clicking a badge fits the view to include those points.
+    pub fn set_clip_indicators(&mut self, clip_indicators: bool) {
+        self.clip_indicators = clip_indicators
+    }
+
+    #[inline]
+    /// Show/hide badges counting the points clipped out of view. Consumes self.
+    pub fn clip_indicators(mut self, clip_indicators: bool) -> Self {
+        self.set_clip_indicators(clip_indicators);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable fitting an axis to the data in view when its labels are double-clicked.
+    pub fn set_axis_autoscale(&mut self, axis_autoscale: bool) {
+        self.axis_autoscale = axis_autoscale
+    }
+
+    #[inline]
+    /// Enable/disable fitting an axis to the data in view when its labels are double-clicked.
+    /// Consumes self.
+    pub fn axis_autoscale(mut self, axis_autoscale: bool) -> Self {
+        self.set_axis_autoscale(axis_autoscale);
+
+        self
+    }
+
+    #[inline]
+    /// Set the callback drawing on top of the chart after it is drawn. The callback is given a
+    /// painter clipped to the chart and a [`ScreenTransform`] between X/Y values and the screen.
+    pub fn set_on_overlay(&mut self, overlay_cb: OverlayCallback) {
+        self.overlay_cb = Some(overlay_cb)
+    }
+
+    #[inline]
+    /// Set the callback drawing on top of the chart after it is drawn. Consumes self.
+    pub fn on_overlay(mut self, overlay_cb: OverlayCallback) -> Self {
+        self.set_on_overlay(overlay_cb);
+
+        self
+    }
+
+    /// Set the callback drawing beneath the chart before its axes and points are drawn. The
+    /// callback is given a painter clipped to the chart, the rect of the plot between the axes
+    /// on screen, and a [`ScreenTransform`] between X/Y values and the screen.
+    ///
+    /// The plot is laid out as it was the last frame, so the callback isn't called until the
+    /// chart has been drawn once.
+    pub fn set_on_background(&mut self, mut background_cb: BackgroundCallback) {
+        let mapping = self.chart.get_data().mapping.clone();
+
+        self.chart
+            .set_on_background(Box::new(move |painter, _area, transform| {
+                if let Some(mapping) = mapping.get() {
+                    let (plot, transform) = mapping.screen_layout(*transform);
+
+                    background_cb(painter, plot, &transform);
+                }
+            }));
+    }
+
+    #[inline]
+    /// Set the callback drawing beneath the chart before it is drawn. Consumes self.
+    pub fn on_background(mut self, background_cb: BackgroundCallback) -> Self {
+        self.set_on_background(background_cb);
+
+        self
+    }
+
+    #[inline]
+    /// Zoom the X axis to fit the plotted points within the Y range in view, leaving the Y axis
+    /// as it is.
+    pub fn zoom_to_fit_x(&mut self) {
+        self.fit_axis(FitAxis::X, 0.0, 100.0)
+    }
+
+    #[inline]
+    /// Zoom the Y axis to fit the plotted points within the X range in view, leaving the X axis
+    /// as it is.
+    pub fn zoom_to_fit_y(&mut self) {
+        self.fit_axis(FitAxis::Y, 0.0, 100.0)
+    }
+
+    #[inline]
+    /// Zoom the X axis to fit the plotted points within the Y range in view, from the `low` to
+    /// the `high` percentile of their X values. Leaves outliers out of view, such as with
+    /// `zoom_to_fit_x_percentile(1.0, 99.0)`.
+    pub fn zoom_to_fit_x_percentile(&mut self, low: f32, high: f32) {
+        self.fit_axis(FitAxis::X, low, high)
+    }
+
+    #[inline]
+    /// Zoom the Y axis to fit the plotted points within the X range in view, from the `low` to
+    /// the `high` percentile of their Y values. Leaves outliers out of view, such as with
+    /// `zoom_to_fit_y_percentile(1.0, 99.0)`.
+    pub fn zoom_to_fit_y_percentile(&mut self, low: f32, high: f32) {
+        self.fit_axis(FitAxis::Y, low, high)
+    }
+
+    #[inline]
+    /// Undo any zooming to fit, dragging, and zooming, showing the whole range of the data again.
+    pub fn reset_view(&mut self) {
+        self.chart.reset_view()
+    }
+
+    /// Capture the view, playback position, parts shown, and bookmarks of the chart. See
+    /// [`TimeChartState`].
+    pub fn state(&self) -> TimeChartState {
+        let playback = self.playback.borrow();
+        let time = playback
+            .is_active()
+            .then(|| self.time_at(playback.peek_elapsed()));
+
+        let data = self.chart.get_data();
+        let bookmarks = data
+            .bookmarks
+            .iter()
+            .map(|(name, time)| (name.to_string(), *time))
+            .collect();
+
+        TimeChartState {
+            chart: self.chart.state(),
+            time,
+            playing: playback.is_playing(),
+            fitted_view: data.fitted_view.clone(),
+            trail: data.trail,
+            minimap: self.minimap.is_some(),
+            clip_indicators: self.clip_indicators,
+            data_table: self.data_table,
+            bookmarks,
+        }
+    }
+
+    /// Restore the chart to a captured state. Playback is moved to the time captured, along
+    /// with the rest of the chart's playback group.
+    pub fn restore(&mut self, time_chart: &TimeChartState) {
+        self.chart.restore(&time_chart.chart);
+
+        // Keep the overview strip as it is if still shown, so it isn't reset
+        if time_chart.minimap != self.minimap.is_some() {
+            self.set_minimap(time_chart.minimap);
+        }
+
+        self.clip_indicators = time_chart.clip_indicators;
+        self.data_table = time_chart.data_table;
+
+        let config = self.chart.get_data_mut();
+
+        config.trail = time_chart.trail;
+        config.fitted_view = time_chart.fitted_view.clone();
+
+        self.clear_bookmarks();
+
+        for (name, time) in &time_chart.bookmarks {
+            self.add_bookmark(name, *time);
+        }
+
+        match time_chart.time {
+            Some(time) => {
+                self.set_time(time - self.start_time());
+
+                if time_chart.playing != self.is_playing() {
+                    self.toggle_playback();
+                }
+
+                self.seek_points(time);
+            }
+            None => {
+                self.stop_playback();
+                self.seek_points(self.end_time());
+            }
+        }
+    }
+
+    #[inline]
+    /// Get the rect of the plot between the axes on screen, as laid out the last frame drawn.
+    /// Useful for lining other widgets up with the plot. None if the chart hasn't been drawn yet.
+    pub fn get_plot_rect(&self) -> Option<Rect> {
+        self.chart.get_plot_rect()
+    }
+
+    /// Draw the chart to a Ui. Will also proceed to animate the chart if playback is currently
+    /// enabled.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        if self.playback.borrow().is_active() {
+            let time = self.current_time();
+
+            self.seek_points(time);
+        }
+
+        self.autoscale_window();
+
+        self.constrain_navigation();
+
+        self.draw_chart(ui);
+    }
+
+    /// Draw the chart to any plotters backend, such as a `BitMapBackend` or `SVGBackend`,
+    /// without egui. Only the chart itself is drawn, without the overview strip or clip
+    /// indicators.
+    pub fn draw_headless<DB: DrawingBackend>(
+        &mut self,
+        backend: DB,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        if self.playback.borrow().is_active() {
+            let time = self.current_time();
+
+            self.seek_points(time);
+        }
+
+        self.autoscale_window();
+
+        self.constrain_navigation();
+
+        self.chart.draw_headless(backend)
+    }
+
+    /// Draw the chart to a Ui as it would appear at the given time, bypassing the chart's own
+    /// playback clock. Useful for driving several charts from an external timeline, such as a
+    /// video player. Time is in the same units as the points and is clamped to the start and
+    /// end times of the chart.
+    pub fn draw_at_time(&mut self, ui: &mut Ui, time: f32) {
+        self.seek_points(time);
+
+        self.autoscale_window();
+
+        self.constrain_navigation();
+
+        self.draw_chart(ui);
+    }
+
+    /// Draw the chart, along with the table of the points beside it or in its place if enabled.
+    fn draw_chart(&mut self, ui: &mut Ui) {
+        match self.data_table {
+            DataTableMode::Hidden => self.draw_plot_area(ui),
+            DataTableMode::Instead => {
+                let id = self.chart.get_id().with("data_table");
+
+                ui.push_id(id, |ui| self.draw_data_table(ui));
+            }
+            DataTableMode::Beside => {
+                let rect = ui.max_rect();
+
+                // Narrow the table to fit, so the plot never gets an inverted rect
+                let width = self
+                    .data_table_width
+                    .min(rect.width() - TABLE_SPACING)
+                    .max(0.0);
+                let table_left = rect.right() - width;
+                let plot_right = (table_left - TABLE_SPACING).max(rect.left());
+
+                let plot_rect = Rect::from_min_max(rect.min, (plot_right, rect.bottom()).into());
+                let table_rect = Rect::from_min_max((table_left, rect.top()).into(), rect.max);
+
+                let id = self.chart.get_id();
+
+                self.draw_plot_area(&mut child_ui(ui, plot_rect, id.with("plot_area")));
+                self.draw_data_table(&mut child_ui(ui, table_rect, id.with("data_table")));
+            }
+        }
+    }
+
+    /// Draw the table of the points, sharing the hovered and clicked rows with the link group.
+    fn draw_data_table(&mut self, ui: &mut Ui) {
+        let data = self.chart.get_data();
+        let selected = self
+            .link
+            .as_ref()
+            .and_then(|(group, _)| group.get_selected())
+            .map(|time| time_index(&self.times, time));
+        let following = self.playback.borrow().is_playing();
+
+        let (hovered, clicked) = draw_table(
+            ui,
+            ["Time", &data.x_unit, &data.y_unit],
+            &self.points,
+            &self.times,
+            data.visible,
+            selected,
+            following,
+        );
+
+        let Some((group, member)) = &mut self.link else {
+            return;
+        };
+
+        // Beside the plot, the plot clears the hovered time once the pointer leaves both
+        if hovered.is_some() || self.data_table == DataTableMode::Instead {
+            group.hover(*member, hovered);
+        }
+
+        if clicked.is_some() {
+            group.set_selected(clicked);
+        }
+    }
+
+    /// Draw the chart, along with the overview strip below it if enabled.
+    fn draw_plot_area(&mut self, ui: &mut Ui) {
+        if self.minimap.is_none() {
+            self.draw_plot(ui);
+
+            return;
+        }
+
+        let rect = ui.max_rect();
+        let strip_top = rect.bottom() - self.minimap_height;
+
+        let chart_rect =
+            Rect::from_min_max(rect.min, (rect.right(), strip_top - MINIMAP_SPACING).into());
+        let strip_rect = Rect::from_min_max((rect.left(), strip_top).into(), rect.max);
+
+        let mut chart_ui = child_ui(ui, chart_rect, self.chart.get_id().with("chart_area"));
+
+        self.draw_plot(&mut chart_ui);
+
+        let view = self.view_x();
+
+        let (Some(minimap), Some(view)) = (&mut self.minimap, view) else {
+            return;
+        };
+
+        let data = self.chart.get_data();
+        let extent = self.extents.total().unwrap_or((0.0..1.0, 0.0..1.0));
+
+        let mut strip_ui = child_ui(ui, strip_rect, self.chart.get_id().with("minimap"));
+
+        let markers = data
+            .bookmarks
+            .iter()
+            .map(|(_, time)| data.all_points[time_index(&data.times, *time)].0)
+            .collect();
+
+        minimap.set_markers(markers);
+
+        let new_view = minimap.draw(
+            &mut strip_ui,
+            &data.all_points,
+            data.monotonic,
+            extent,
+            view,
+            data.line_style,
+        );
+
+        if let Some(view) = new_view {
+            self.set_view_x(view);
+        }
+    }
+
+    /// Draw the chart along with what's shown over it, and handle the gestures on its axes.
+    fn draw_plot(&mut self, ui: &mut Ui) {
+        // Playback moves the chart's contents without moving its transform
+        #[cfg(feature = "adaptive")]
+        {
+            if self.is_playing() {
+                self.chart.mark_active();
+            }
+
+            let degraded = self
+                .chart
+                .get_adaptive_quality()
+                .is_some_and(|adaptive| adaptive.is_degraded());
+
+            self.chart.get_data_mut().degraded = degraded;
+        }
+        self.chart.draw(ui);
+
+        let transform = self.chart.get_transform();
+
+        self.chart.get_data().text_fit.show_full_text(ui, transform);
+
+        // Keep the mouse from moving the plot from its margins and label areas
+        if let Some(mapping) = self.chart.get_data().mapping.get() {
+            let (width, height) = mapping.size;
+
+            self.chart.set_plot_insets(Margin {
+                left: mapping.x_pixels.0 as f32,
+                right: width - mapping.x_pixels.1 as f32,
+                top: mapping.y_pixels.0 as f32,
+                bottom: height - mapping.y_pixels.1 as f32,
+            });
+        }
+
+        if self.clip_indicators {
+            self.draw_clip_indicators(ui);
+        }
+
+        self.draw_link(ui);
+
+        self.draw_overlay(ui);
+
+        self.autoscale_on_double_click(ui);
+    }
+
+    /// Share the time of the point under the pointer with the link group, selecting it when
+    /// clicked, and highlight the points at the times hovered and selected in the group.
+    fn draw_link(&mut self, ui: &Ui) {
+        let Some((group, member)) = &mut self.link else {
+            return;
+        };
+
+        let (Some(mapping), Some(plot)) = (
+            self.chart.get_data().mapping.get(),
+            self.chart.get_plot_rect(),
+        ) else {
+            return;
+        };
+
+        let rect = ui.max_rect();
+        let (_, transform) =
+            mapping.screen_layout(ScreenTransform::new(rect, self.chart.get_transform()));
+
+        let data = self.chart.get_data();
+        let (pointer, clicked) = ui.input(|input| {
+            (
+                input.pointer.hover_pos().filter(|pos| plot.contains(*pos)),
+                input.pointer.primary_clicked(),
+            )
+        });
+
+        // Closest plotted point to the pointer
+        let hovered = pointer.and_then(|pointer| {
+            data.points()
+                .iter()
+                .enumerate()
+                .map(|(index, (x, y))| {
+                    let distance = transform.to_screen(Pos2::new(*x, *y)).distance(pointer);
+
+                    (index, distance)
+                })
+                .filter(|(_, distance)| *distance <= LINK_HOVER_RADIUS)
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                .map(|(index, _)| self.times[index])
+        });
+
+        group.hover(*member, hovered);
+
+        if clicked && hovered.is_some() {
+            group.set_selected(hovered);
+        }
+
+        let color: Color32 =
+            EguiBackendColor::from(data.line_style.color.to_backend_color()).into();
+        let painter = ui.painter_at(plot);
+
+        let highlight = |time: f32| {
+            let index = time_index(&self.times, time);
+
+            data.points()
+                .get(index)
+                .map(|(x, y)| transform.to_screen(Pos2::new(*x, *y)))
+        };
+
+        if let Some(pos) = group.get_selected().and_then(highlight) {
+            painter.circle_filled(pos, LINK_HIGHLIGHT_SIZE, color);
+        }
+
+        if let Some(pos) = group.get_hovered().and_then(highlight) {
+            painter.circle_stroke(pos, LINK_HIGHLIGHT_SIZE * 1.5, Stroke::new(2.0, color));
+        }
+    }
+
+    /// Call the overlay callback, mapping X/Y values to where they are drawn on screen.
+    fn draw_overlay(&mut self, ui: &Ui) {
+        let (Some(overlay_cb), Some(mapping)) =
+            (&mut self.overlay_cb, self.chart.get_data().mapping.get())
+        else {
+            return;
+        };
+
+        let rect = ui.max_rect();
+        let (_, transform) =
+            mapping.screen_layout(ScreenTransform::new(rect, self.chart.get_transform()));
+
+        overlay_cb(&ui.painter_at(rect), &transform);
+    }
+
+    /// Show how many plotted points are clipped past each edge of the plot, fitting the view to
+    /// include them when a badge is clicked.
+    fn draw_clip_indicators(&mut self, ui: &Ui) {
+        let (Some(view), Some(plot)) = (self.view(), self.chart.get_plot_rect()) else {
+            return;
+        };
+
+        let data = self.chart.get_data();
+        let counts = ClipCounts::new(data.points(), &view);
+        let color: Color32 =
+            EguiBackendColor::from(data.line_style.color.to_backend_color()).into();
+
+        match draw_clip_indicators(ui, self.chart.get_id(), plot, &counts, color) {
+            Some(ClipEdge::Top | ClipEdge::Bottom) => self.zoom_to_fit_y(),
+            Some(ClipEdge::Left | ClipEdge::Right) => self.zoom_to_fit_x(),
+            None => {}
+        }
+    }
+
+    /// Ranges of X and Y values between the edges of the plot, as it is currently zoomed and
+    /// dragged. None if the chart hasn't been drawn yet.
+    fn view(&self) -> Option<(Range<f32>, Range<f32>)> {
+        let mapping = self.chart.get_data().mapping.get()?;
+
+        Some(mapping.view(&self.chart.get_transform()))
+    }
+
+    #[inline]
+    /// Range of X values between the left and right edges of the plot. None if the chart hasn't
+    /// been drawn yet.
+    fn view_x(&self) -> Option<Range<f32>> {
+        self.view().map(|(x, _)| x)
+    }
+
+    /// Zoom and drag the chart so the given range of X values spans the plot, keeping the
+    /// center of the view vertically where it is.
+    fn set_view_x(&mut self, view: Range<f32>) {
+        let Some(mapping) = self.chart.get_data().mapping.get() else {
+            return;
+        };
+
+        let mut transform = self.chart.get_transform();
+
+        let center = mapping.size.0 / 2.0;
+        let (left, right) = (mapping.x_pixels.0 as f32, mapping.x_pixels.1 as f32);
+        let to_pixel =
+            |x: f32| left + (x - mapping.x.0) / (mapping.x.1 - mapping.x.0) * (right - left);
+
+        let (start, end) = (to_pixel(view.start), to_pixel(view.end));
+        let scale = (right - left) / (end - start);
+
+        if !scale.is_finite() || scale <= 0.0 {
+            return;
+        }
+
+        let old_scale = transform.scale as f32;
+
+        transform.scale = scale as f64;
+        transform.x = (left - center - (start - center) * scale) as i32;
+        // Keep the pixel at the vertical center of the view in place
+        transform.y = (transform.y as f32 * scale / old_scale) as i32;
+
+        self.chart.set_transform(transform);
+    }
+
+    /// Zoom one axis to fit the percentiles of the plotted values within the range in view along
+    /// the other axis. Does nothing before the chart is first drawn or if no points are in view.
+    fn fit_axis(&mut self, axis: FitAxis, low: f32, high: f32) {
+        let Some((x_view, y_view)) = self.view() else {
+            return;
+        };
+
+        let mut values: Vec<f32> = self
+            .chart
+            .get_data()
+            .points()
+            .iter()
+            .filter_map(|&(x, y)| match axis {
+                FitAxis::X => y_view.contains(&y).then_some(x),
+                FitAxis::Y => x_view.contains(&x).then_some(y),
+            })
+            .filter(|value| value.is_finite())
+            .collect();
+
+        let Some(fitted) = percentile_range(&mut values, low, high) else {
+            return;
+        };
+
+        self.chart.get_data_mut().fitted_view = Some(match axis {
+            FitAxis::X => (fitted, y_view),
+            FitAxis::Y => (x_view, fitted),
+        });
+
+        // The fitted view spans the whole plot, so drop the zoom and drag on top of it
+        let transform = self.chart.get_transform();
+
+        self.chart.set_transform(Transform {
+            scale: 1.0,
+            x: 0,
+            y: 0,
+            ..transform
+        });
+    }
+
+    /// Fit the axis whose labels were double-clicked to the data in view along the other axis.
+    fn autoscale_on_double_click(&mut self, ui: &Ui) {
+        if !self.axis_autoscale {
+            return;
+        }
+
+        let double_clicked =
+            ui.input(|input| input.pointer.button_double_clicked(PointerButton::Primary));
+
+        let Some((x, y)) = hover_pos(ui).filter(|_| double_clicked) else {
+            return;
+        };
+
+        let data = self.chart.get_data();
+
+        let Some(mapping) = data.mapping.get() else {
+            return;
+        };
+
+        let transform = self.chart.get_transform();
+
+        let (width, height) = mapping.size;
+        let scale = transform.scale as f32;
+        let (left, right) = (mapping.x_pixels.0 as f32, mapping.x_pixels.1 as f32);
+        let (top, bottom) = (mapping.y_pixels.0 as f32, mapping.y_pixels.1 as f32);
+
+        // Undo the transform to find where the untransformed chart was clicked
+        let x = (x - transform.x as f32 - width / 2.0) / scale + width / 2.0;
+        let y = (y - transform.y as f32 - height / 2.0) / scale + height / 2.0;
+
+        let on_x_axis = (left..=right).contains(&x)
+            && match data.x_axis {
+                XAxisPosition::Bottom => y > bottom,
+                XAxisPosition::Top => y < top,
+                XAxisPosition::Hidden => false,
+            };
+        let on_y_axis = (top..=bottom).contains(&y)
+            && match data.y_axis {
+                YAxisPosition::Left => x < left,
+                YAxisPosition::Right => x > right,
+                YAxisPosition::Hidden => false,
+            };
+
+        if on_x_axis {
+            self.zoom_to_fit_x();
+        } else if on_y_axis {
+            self.zoom_to_fit_y();
+        }
+    }
+
+    /// Convert the zoom limits and pan bounds from data units to the scale and pixels the chart
+    /// is transformed by, based on how the plot was laid out last frame.
+    fn constrain_navigation(&mut self) {
+        let Some(mapping) = self.chart.get_data().mapping.get() else {
+            return;
+        };
+
+        let span_x = mapping.x.1 - mapping.x.0;
+        let span_y = mapping.y.1 - mapping.y.0;
+
+        if let Some((x_limits, y_limits)) = &self.zoom_limits {
+            // Zooming in by a scale shows the span divided by that scale
+            let max_scale = (span_x / x_limits.start).min(span_y / y_limits.start);
+            let min_scale = (span_x / x_limits.end).max(span_y / y_limits.end);
+
+            self.chart
+                .set_scale_limits(Some((min_scale.max(0.0) as f64, max_scale as f64)));
+        }
+
+        if let Some((x_bounds, y_bounds)) = &self.pan_bounds {
+            let to_pixel = |value: f32, range: (f32, f32), pixels: (i32, i32)| {
+                let fraction = (value - range.0) / (range.1 - range.0);
+
+                pixels.0 as f32 + fraction * (pixels.1 - pixels.0) as f32
+            };
+
+            let x_pixels = mapping.x_pixels;
+            // Pixels go down while values go up
+            let y_pixels = (mapping.y_pixels.1, mapping.y_pixels.0);
+
+            let x_bounds = to_pixel(x_bounds.start, mapping.x, x_pixels)
+                ..to_pixel(x_bounds.end, mapping.x, x_pixels);
+            let y_bounds = to_pixel(mapping.fold_y(y_bounds.end), mapping.y, y_pixels)
+                ..to_pixel(mapping.fold_y(y_bounds.start), mapping.y, y_pixels);
+
+            let valid = [&x_bounds, &y_bounds]
+                .iter()
+                .all(|bounds| bounds.start.is_finite() && bounds.end.is_finite());
+
+            self.chart
+                .set_pan_bounds(valid.then_some((x_bounds, y_bounds)));
+        }
+    }
+
+    /// Update the plotted points and range to those shown at the given time.
+    fn seek_points(&mut self, time: f32) {
+        // Every point is shown from the end on, even if several share the last time, so
+        // recordings without a duration are shown whole
+        let time_index = match time >= self.end_time() {
+            true => self.points.len() - 1,
+            false => self.time_cache.lookup(&self.times, time),
+        };
+
+        self.chart.get_data_mut().visible = time_index + 1;
+
+        self.update_range();
+    }
+
+    /// Update the range to the points plotted, as set by the autoscale mode.
+    fn update_range(&mut self) {
+        let config = self.chart.get_data_mut();
+
+        // The trail previews the whole dataset, so keep the full range in view
+        let range = match (config.autoscale, config.trail) {
+            (AutoscaleMode::Full, _) | (_, true) => self.extents.total().unwrap(),
+            (AutoscaleMode::GrowOnly | AutoscaleMode::VisibleWindow, false) => {
+                self.extents.prefix(&self.points, config.visible - 1)
+            }
+        };
+
+        config.range = range;
+    }
+
+    /// Fit the Y axis to the plotted points within the X range in view, if autoscaling to the
+    /// visible window. A window zoomed or dragged to is kept as a fitted view, while otherwise
+    /// the X axis is left to grow with playback.
+    fn autoscale_window(&mut self) {
+        if self.chart.get_data().autoscale != AutoscaleMode::VisibleWindow {
+            return;
+        }
+
+        let transform = self.chart.get_transform();
+        let moved = transform.scale != 1.0 || transform.x != 0 || transform.y != 0;
+
+        let window = match (moved, &self.chart.get_data().fitted_view) {
+            (true, _) => self.view_x(),
+            (false, Some((x, _))) => Some(x.clone()),
+            (false, None) => None,
+        };
+
+        let Some(window) = window else {
+            return;
+        };
+
+        let data = self.chart.get_data();
+        let points = match data.trail {
+            true => &data.all_points[..],
+            false => data.points(),
+        };
+
+        let fitted = points
+            .iter()
+            .filter(|(x, y)| window.contains(x) && y.is_finite())
+            .fold(None, |range: Option<Range<f32>>, (_, y)| match range {
+                Some(range) => Some(range.start.min(*y)..range.end.max(*y)),
+                None => Some(*y..*y),
+            });
+
+        let Some(fitted) = fitted else {
+            return;
+        };
+
+        // Widen a flat line around its value, as when zooming to fit
+        let fitted = match fitted.is_empty() {
+            true => (fitted.start - FLAT_FIT_SPAN / 2.0)..(fitted.end + FLAT_FIT_SPAN / 2.0),
+            false => fitted,
+        };
+
+        self.chart.get_data_mut().fitted_view = Some((window, fitted));
+
+        // The fitted view spans the whole plot, so drop the zoom and drag on top of it
+        self.chart.set_transform(Transform {
+            scale: 1.0,
+            x: 0,
+            y: 0,
+            ..transform
+        });
+    }
+
+    /// Bookmark a time, marking the point shown at it on the chart and the overview strip. Time
+    /// is in the same units as the points. Replaces any bookmark with the same name.
+    pub fn add_bookmark(&mut self, name: &str, time: f32) {
+        let bookmarks = &mut self.chart.get_data_mut().bookmarks;
+
+        bookmarks.retain(|(bookmark, _)| &**bookmark != name);
+
+        let index = bookmarks.partition_point(|(_, bookmark_time)| *bookmark_time <= time);
+
+        bookmarks.insert(index, (name.into(), time));
+    }
+
+    #[inline]
+    /// Bookmark a time. Consumes self.
+    pub fn bookmark(mut self, name: &str, time: f32) -> Self {
+        self.add_bookmark(name, time);
+
+        self
+    }
+
+    #[inline]
+    /// Remove the bookmark with the given name, if there is one.
+    pub fn remove_bookmark(&mut self, name: &str) {
+        self.chart
+            .get_data_mut()
+            .bookmarks
+            .retain(|(bookmark, _)| &**bookmark != name)
+    }
+
+    #[inline]
+    /// Remove every bookmark.
+    pub fn clear_bookmarks(&mut self) {
+        self.chart.get_data_mut().bookmarks.clear()
+    }
+
+    #[inline]
+    /// Get the time of the bookmark with the given name.
+    pub fn get_bookmark(&self, name: &str) -> Option<f32> {
+        self.chart
+            .get_data()
+            .bookmarks
+            .iter()
+            .find(|(bookmark, _)| &**bookmark == name)
+            .map(|(_, time)| *time)
+    }
+
+    /// Move playback to the time of the bookmark with the given name, pausing there if playback
+    /// isn't underway. Returns false if there's no such bookmark.
+    pub fn jump_to_bookmark(&mut self, name: &str) -> bool {
+        let Some(time) = self.get_bookmark(name) else {
+            return false;
+        };
+
+        let start_time = self.start_time();
+
+        self.set_time(time - start_time);
+
+        true
+    }
+
+    #[inline]
+    /// Return the currently plotted points in view as CSV, with a header row made from the time
+    /// and the X/Y units.
+    pub fn visible_data_csv(&self) -> String {
+        self.chart.visible_data_csv().unwrap_or_default()
+    }
+
+    /// Write the points to a CSV file at `path`, with a header row made from the time and the
+    /// X/Y units. If a time range is provided only the points within it are written.
+    pub fn export_csv<P>(&self, path: P, range: Option<Range<f32>>) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let config = self.chart.get_data();
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let rows = self
+            .times
+            .iter()
+            .copied()
+            .zip(self.points.iter().copied())
+            .filter(|(time, _)| range.as_ref().is_none_or(|range| range.contains(time)));
+
+        for line in csv_lines(locale(), &config.x_unit, &config.y_unit, rows) {
+            writer.write_all(line.as_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    #[inline]
+    /// Start/enable playback of the chart. Does nothing if the chart has nothing to animate.
+    pub fn start_playback(&mut self) {
+        self.playback.borrow_mut().start()
+    }
+
+    #[inline]
+    /// Start/enable playback of the chart, returning [`PlaybackError::ZeroDuration`] if the
+    /// chart has nothing to animate, such as when every point is at the same time.
+    pub fn try_start_playback(&mut self) -> Result<(), PlaybackError> {
+        self.playback.borrow_mut().try_start()
+    }
+
+    #[inline]
+    /// Stop/disable playback of the chart.
+    pub fn stop_playback(&mut self) {
+        self.playback.borrow_mut().stop()
+    }
+
+    #[inline]
+    /// Toggle playback of the chart.
+    pub fn toggle_playback(&mut self) {
+        self.playback.borrow_mut().toggle()
     }
 
     #[inline]
     /// Return true if playback is currently enabled & underway.
     pub fn is_playing(&self) -> bool {
-        self.playback_start != None && self.pause_start == None
+        self.playback.borrow().is_playing()
     }
 
     #[inline]
@@ -496,35 +2388,24 @@ impl XyTimeData {
 
     /// Return the current time to be animated when playback is enabled.
     pub fn current_time(&mut self) -> f32 {
-        if let Some(playback_start) = self.playback_start {
-            let now = Instant::now();
+        let elapsed = self.playback.borrow_mut().elapsed();
 
-            let time_start = self.start_time();
-            let time_end = self.end_time();
-
-            let base_delta = time_end - time_start;
+        self.time_at(elapsed)
+    }
 
-            // Ensure deltas are over 10us, otherwise they can cause overflows
-            // in the plotters library
-            let current_delta = MIN_DELTA
-                + self.playback_speed
-                    * match self.pause_start {
-                        Some(pause_start) => {
-                            pause_start.duration_since(playback_start).as_secs_f32()
-                        }
-                        None => now.duration_since(playback_start).as_secs_f32(),
-                    };
+    /// Return the time of the data shown after the group's clock has played some seconds.
+    fn time_at(&self, elapsed: Option<f32>) -> f32 {
+        let time_start = self.start_time();
+        let time_end = self.end_time();
 
-            match base_delta > current_delta {
-                true => current_delta + time_start,
-                false => {
-                    self.playback_start = None;
+        // Members of a playback group may finish before the group does
+        match elapsed {
+            Some(elapsed) => {
+                let elapsed = self.member.elapsed(elapsed);
 
-                    time_end
-                }
+                (self.time_map.to_data(elapsed) + time_start).min(time_end)
             }
-        } else {
-            self.start_time()
+            None => time_start,
         }
     }
 
@@ -539,6 +2420,415 @@ impl XyTimeData {
     #[inline]
     /// Return the speed the chart is animated at.
     pub fn get_playback_speed(&self) -> f32 {
-        self.playback_speed
+        self.playback.borrow().speed()
+    }
+
+    #[inline]
+    /// Points, time mapping, and playback clock of the chart, for binding gauges to.
+    pub(crate) fn shared(&self) -> &Rc<RefCell<SharedSeries>> {
+        &self.shared
+    }
+
+    /// Update the series shared with bound gauges to the chart's current one.
+    fn share(&self) {
+        *self.shared.borrow_mut() = SharedSeries {
+            playback: self.playback.clone(),
+            member: self.member.clone(),
+            points: self.points.clone(),
+            times: self.times.clone(),
+            time_map: self.time_map.clone(),
+        };
+    }
+
+    /// Join a playback group, sharing its playback clock with every other member.
+    pub fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        let playback = group.playback().clone();
+
+        self.leave_playback();
+
+        playback.borrow_mut().join(&self.member);
+
+        self.playback = playback;
+        self.share();
+    }
+
+    #[inline]
+    /// Join a playback group, sharing its playback clock with every other member. Consumes self.
+    pub fn playback_group(mut self, group: &PlaybackGroup) -> Self {
+        self.set_playback_group(group);
+
+        self
+    }
+
+    /// Leave the current playback group, if any, and go back to an independent playback clock.
+    pub fn leave_playback_group(&mut self) {
+        let playback = Rc::new(RefCell::new(Playback::new(0.0)));
+
+        self.leave_playback();
+
+        playback.borrow_mut().join(&self.member);
+
+        self.playback = playback;
+        self.share();
+    }
+
+    /// Stop counting the chart as a member of its playback clock, fitting the clock to the
+    /// members left.
+    fn leave_playback(&mut self) {
+        self.member = self.member.detach();
+
+        // Bound gauges hold on to the old timing too, keeping it on the clock until updated
+        self.share();
+        self.playback.borrow_mut().fit_duration();
+    }
+
+    /// Set the seconds of playback before the chart starts animating, delaying it behind the
+    /// other members of its playback group. Negative offsets skip the start of the chart instead.
+    /// See also [`PlaybackGroup::align_starts`].
+    pub fn set_playback_offset(&mut self, offset: f32) {
+        self.member.set_offset(offset);
+
+        self.fit_playback_duration();
+    }
+
+    #[inline]
+    /// Set the seconds of playback before the chart starts animating. Consumes self.
+    pub fn playback_offset(mut self, offset: f32) -> Self {
+        self.set_playback_offset(offset);
+
+        self
+    }
+
+    #[inline]
+    /// Get the seconds of playback before the chart starts animating.
+    pub fn get_playback_offset(&self) -> f32 {
+        self.member.offset()
+    }
+
+    /// Join a link group, highlighting the points at the times hovered and selected in any chart
+    /// of the group.
+    pub fn set_link_group(&mut self, group: &LinkGroup) {
+        self.leave_link_group();
+
+        self.link = Some((group.clone(), group.join()));
+    }
+
+    #[inline]
+    /// Join a link group. Consumes self.
+    pub fn link_group(mut self, group: &LinkGroup) -> Self {
+        self.set_link_group(group);
+
+        self
+    }
+
+    /// Leave the current link group, if any.
+    pub fn leave_link_group(&mut self) {
+        if let Some((group, member)) = self.link.take() {
+            group.hover(member, None);
+        }
+    }
+
+    /// Set how the time of the data is compressed during playback. See [`TimeMapping`].
+    pub fn set_time_mapping(&mut self, mapping: TimeMapping) {
+        self.time_map = TimeMap::new(&self.times, mapping);
+
+        self.fit_playback_duration();
+        self.share();
+    }
+
+    /// Fit the duration of the playback clock to the time mapped data and the playback offset.
+    fn fit_playback_duration(&mut self) {
+        self.member
+            .set_extent(self.start_time(), self.time_map.duration());
+
+        // The clock stays long enough for the other members of a playback group
+        self.playback.borrow_mut().fit_duration();
+    }
+
+    #[inline]
+    /// Set how the time of the data is compressed during playback. Consumes self.
+    pub fn time_mapping(mut self, mapping: TimeMapping) -> Self {
+        self.set_time_mapping(mapping);
+
+        self
+    }
+
+    #[inline]
+    /// Return how the time of the data is compressed during playback.
+    pub fn get_time_mapping(&self) -> TimeMapping {
+        self.time_map.mapping()
+    }
+}
+
+/// Range between two percentiles of the values, interpolated between the values closest to them.
+/// Ranges with no width, such as from a flat line, are widened around their center. None if there
+/// are no values.
+fn percentile_range(values: &mut [f32], low: f32, high: f32) -> Option<Range<f32>> {
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let last = (values.len() - 1) as f32;
+    let percentile = |percent: f32| {
+        let rank = percent.clamp(0.0, 100.0) / 100.0 * last;
+        let (below, above) = (values[rank.floor() as usize], values[rank.ceil() as usize]);
+
+        below + (above - below) * rank.fract()
+    };
+
+    let start = percentile(low.min(high));
+    let end = percentile(low.max(high));
+
+    match start < end {
+        true => Some(start..end),
+        false => Some((start - FLAT_FIT_SPAN / 2.0)..(end + FLAT_FIT_SPAN / 2.0)),
+    }
+}
+
+/// Lines of CSV, each ending with a newline, for rows of times and points, led by a header row
+/// made from the X/Y units. Numbers and separators follow the locale.
+fn csv_lines<'a>(
+    locale: Locale,
+    x_unit: &str,
+    y_unit: &str,
+    rows: impl Iterator<Item = (f32, (f32, f32))> + 'a,
+) -> impl Iterator<Item = String> + 'a {
+    let separator = locale.csv_separator();
+
+    let header = format!(
+        "time{separator}{}{separator}{}\n",
+        escape_csv_field(x_unit),
+        escape_csv_field(y_unit)
+    );
+
+    let rows = rows.map(move |(time, (x, y))| {
+        format!(
+            "{}{separator}{}{separator}{}\n",
+            locale.format_csv_number(time),
+            locale.format_csv_number(x),
+            locale.format_csv_number(y)
+        )
+    });
+
+    std::iter::once(header).chain(rows)
+}
+
+/// Quote a CSV field if it contains anything that would break the row apart.
+fn escape_csv_field(field: &str) -> Cow<'_, str> {
+    match field.contains([',', ';', '"', '\n', '\r']) {
+        true => Cow::Owned(format!("\"{}\"", field.replace('"', "\"\""))),
+        false => Cow::Borrowed(field),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points one second apart, apart from a gap of 100 seconds in the middle.
+    fn gapped_points() -> Vec<(f32, f32, f32)> {
+        [0.0, 1.0, 2.0, 102.0, 103.0]
+            .into_iter()
+            .map(|time| (time, time, time))
+            .collect()
+    }
+
+    #[test]
+    fn compressing_time_shortens_a_shared_clock() {
+        let group = PlaybackGroup::new();
+        let mut chart = XyTimeData::new(&gapped_points(), "", "", "").playback_group(&group);
+
+        // Other holders of the clock, such as a bound gauge, don't keep it from shrinking
+        let _held = chart.shared().borrow().playback.clone();
+
+        assert_eq!(group.duration(), 103.0);
+
+        chart.set_time_mapping(TimeMapping::SkipGaps { max_gap: 1.0 });
+        assert_eq!(group.duration(), 4.0);
+
+        chart.set_time_mapping(TimeMapping::Linear);
+        assert_eq!(group.duration(), 103.0);
+    }
+
+    #[test]
+    fn clock_fits_the_members_left_in_a_group() {
+        let group = PlaybackGroup::new();
+        let mut long = XyTimeData::new(&gapped_points(), "", "", "").playback_group(&group);
+        let _short =
+            XyTimeData::new(&[(0.0, 0.0, 0.0), (2.0, 2.0, 2.0)], "", "", "").playback_group(&group);
+
+        assert_eq!(group.duration(), 103.0);
+
+        long.leave_playback_group();
+        assert_eq!(group.duration(), 2.0);
+        assert_eq!(long.playback.borrow().duration(), 103.0);
+    }
+
+    #[test]
+    fn state_round_trips_playback_and_parts_shown() {
+        let mut chart = XyTimeData::new(&gapped_points(), "", "", "").time(2.0);
+        chart.add_bookmark("gap", 2.0);
+        chart.set_trail(true);
+
+        let state = chart.state();
+
+        let time = state.time.unwrap();
+
+        assert!((time - 2.0).abs() < 1e-3);
+        assert!(!state.playing);
+
+        let mut restored = XyTimeData::new(&gapped_points(), "", "", "");
+        restored.restore(&state);
+
+        let restored = restored.state();
+
+        // Playback keeps a tiny lead on the time set
+        assert!((restored.time.unwrap() - time).abs() < 1e-3);
+        assert_eq!(
+            TimeChartState {
+                time: None,
+                ..restored
+            },
+            TimeChartState {
+                time: None,
+                ..state
+            }
+        );
+    }
+
+    #[test]
+    fn resetting_the_underlying_chart_undoes_fitting() {
+        let mut chart = XyTimeData::new(&gapped_points(), "", "", "");
+
+        // Fitting starts from the view laid out when drawn
+        let _ = egui::Context::default().run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| chart.draw(ui));
+        });
+
+        chart.zoom_to_fit_y_percentile(10.0, 90.0);
+        assert!(chart.chart.get_data().fitted_view.is_some());
+
+        // As the context menu's "Reset View" entry does
+        chart.chart.reset_view();
+        assert!(chart.chart.get_data().fitted_view.is_none());
+    }
+
+    #[test]
+    fn view_narrows_as_the_chart_is_zoomed() {
+        let mapping = PlotMapping {
+            x: (0.0, 1000.0),
+            y: (0.0, 10.0),
+            y_break: None,
+            x_pixels: (0, 100),
+            y_pixels: (0, 100),
+            size: (100.0, 100.0),
+        };
+
+        assert_eq!(
+            mapping.view(&Transform::default()),
+            (0.0..1000.0, 0.0..10.0)
+        );
+
+        let zoomed = Transform {
+            scale: 4.0,
+            ..Default::default()
+        };
+
+        assert_eq!(mapping.view(&zoomed), (375.0..625.0, 3.75..6.25));
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_they_would_break_the_row() {
+        assert_eq!(escape_csv_field("meters"), "meters");
+        assert_eq!(escape_csv_field("x, in m"), "\"x, in m\"");
+        assert_eq!(escape_csv_field("x; in m"), "\"x; in m\"");
+        assert_eq!(escape_csv_field("the \"x\""), "\"the \"\"x\"\"\"");
+        assert_eq!(escape_csv_field("x\nin m"), "\"x\nin m\"");
+    }
+
+    #[test]
+    fn csv_follows_the_locale() {
+        let rows = || [(0.5, (1.0, -2.25)), (1.5, (1000.0, 3.0))].into_iter();
+
+        let csv: String = csv_lines(Locale::EN_US, "x, in m", "y", rows()).collect();
+
+        assert_eq!(csv, "time,\"x, in m\",y\n0.5,1,-2.25\n1.5,1000,3\n");
+
+        // Decimal commas separate fields with semicolons instead
+        let csv: String = csv_lines(Locale::DE_DE, "x \"a\"", "y", rows()).collect();
+
+        assert_eq!(csv, "time;\"x \"\"a\"\"\";y\n0,5;1;-2,25\n1,5;1000;3\n");
+    }
+
+    #[test]
+    fn exported_csv_only_holds_the_points_in_view() {
+        let mut chart = XyTimeData::new(&gapped_points(), "", "", "");
+
+        // Everything is exported before the chart is drawn
+        assert_eq!(chart.visible_data_csv().lines().count(), 6);
+
+        chart.chart.get_data().mapping.set(Some(PlotMapping {
+            x: (0.0, 4.0),
+            y: (0.0, 4.0),
+            y_break: None,
+            x_pixels: (0, 100),
+            y_pixels: (0, 100),
+            size: (100.0, 100.0),
+        }));
+
+        assert_eq!(chart.visible_data_csv().lines().count(), 4);
+
+        chart.chart.set_transform(Transform {
+            scale: 2.0,
+            ..Default::default()
+        });
+
+        // Zoomed in to 1..3 on both axes
+        let csv = chart.visible_data_csv();
+        let times: Vec<_> = csv
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').next())
+            .collect();
+
+        assert_eq!(times, [Some("1"), Some("2")]);
+    }
+
+    #[test]
+    fn charts_with_tables_sharing_a_ui_keep_their_own_ids() {
+        let mut charts = [DataTableMode::Beside, DataTableMode::Instead].map(|mode| {
+            [
+                XyTimeData::new(&gapped_points(), "", "", "").data_table(mode),
+                XyTimeData::new(&gapped_points(), "", "", "")
+                    .data_table(mode)
+                    .minimap(true),
+            ]
+        });
+
+        let ctx = egui::Context::default();
+
+        ctx.options_mut(|options| options.warn_on_id_clash = true);
+
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                for row in &mut charts {
+                    ui.columns(2, |columns| {
+                        for (ui, chart) in columns.iter_mut().zip(row.iter_mut()) {
+                            chart.draw(ui);
+                        }
+                    });
+                }
+            });
+        });
+
+        // egui paints a warning over widgets whose ids clash
+        let warnings = output.shapes.iter().any(|shape| match &shape.shape {
+            egui::Shape::Text(text) => text.galley.job.text.contains(" ID "),
+            _ => false,
+        });
+
+        assert!(!warnings);
     }
 }
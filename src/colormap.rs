@@ -0,0 +1,291 @@
+//! Colormaps used to turn scalar values into colors.
+//!
+//! Includes the perceptually uniform [`VIRIDIS`], [`MAGMA`], and [`INFERNO`] colormaps, the
+//! rainbow-like [`TURBO`], and the diverging [`COOLWARM`] and [`RDBU`]. Every built-in colormap
+//! can also be looked up by name with [`colormap`], along with any registered with
+//! [`register_colormap`].
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use plotters::style::{Color, RGBAColor, RGBColor};
+
+/// Maps a position between 0.0 and 1.0 to a color.
+///
+/// ## Usage
+/// Charts that color their elements by value, like [`Contour`](crate::charts::Contour), take
+/// any type implementing this trait. Values are normalized to the 0.0 to 1.0 range before
+/// being passed to `.color()`, and positions outside of it should be clamped.
+///
+/// Custom colormaps can either implement this trait, be built from a list of colors with
+/// [`Gradient`], or be a closure taking the position and returning a color.
+pub trait Colormap: Send + Sync {
+    /// Color at a position between 0.0 and 1.0.
+    fn color(&self, position: f32) -> RGBAColor;
+}
+
+impl<C> Colormap for Arc<C>
+where
+    C: Colormap + ?Sized,
+{
+    #[inline]
+    fn color(&self, position: f32) -> RGBAColor {
+        (**self).color(position)
+    }
+}
+
+impl<F> Colormap for F
+where
+    F: Fn(f32) -> RGBAColor + Send + Sync,
+{
+    #[inline]
+    fn color(&self, position: f32) -> RGBAColor {
+        self(position.clamp(0.0, 1.0))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Colormap interpolating linearly between evenly spaced colors.
+pub struct Gradient {
+    stops: &'static [RGBColor],
+}
+
+impl Gradient {
+    #[inline]
+    /// Create a gradient from evenly spaced colors, the first at 0.0 and the last at 1.0.
+    pub const fn new(stops: &'static [RGBColor]) -> Self {
+        Self { stops }
+    }
+}
+
+impl Colormap for Gradient {
+    fn color(&self, position: f32) -> RGBAColor {
+        let Some(last) = self.stops.len().checked_sub(1) else {
+            return RGBColor(0, 0, 0).to_rgba();
+        };
+
+        let position = match position.is_nan() {
+            true => 0.0,
+            false => position.clamp(0.0, 1.0) * last as f32,
+        };
+
+        let index = (position as usize).min(last.saturating_sub(1));
+        let fraction = position - index as f32;
+
+        let RGBColor(r1, g1, b1) = self.stops[index];
+        let RGBColor(r2, g2, b2) = self.stops[(index + 1).min(last)];
+
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * fraction).round() as u8;
+
+        RGBColor(mix(r1, r2), mix(g1, g2), mix(b1, b2)).to_rgba()
+    }
+}
+
+/// Perceptually uniform colormap going from dark purple to yellow.
+pub const VIRIDIS: Gradient = Gradient::new(&[
+    RGBColor(0x44, 0x01, 0x54),
+    RGBColor(0x48, 0x28, 0x78),
+    RGBColor(0x3e, 0x4a, 0x89),
+    RGBColor(0x31, 0x68, 0x8e),
+    RGBColor(0x26, 0x82, 0x8e),
+    RGBColor(0x1f, 0x9e, 0x89),
+    RGBColor(0x35, 0xb7, 0x79),
+    RGBColor(0x6d, 0xcd, 0x59),
+    RGBColor(0xb4, 0xde, 0x2c),
+    RGBColor(0xfd, 0xe7, 0x25),
+]);
+
+/// Perceptually uniform colormap going from black through purple to pale yellow.
+pub const MAGMA: Gradient = Gradient::new(&[
+    RGBColor(0x00, 0x00, 0x04),
+    RGBColor(0x14, 0x0e, 0x36),
+    RGBColor(0x3b, 0x0f, 0x70),
+    RGBColor(0x64, 0x1a, 0x80),
+    RGBColor(0x8c, 0x29, 0x81),
+    RGBColor(0xb7, 0x37, 0x79),
+    RGBColor(0xde, 0x49, 0x68),
+    RGBColor(0xf7, 0x70, 0x5c),
+    RGBColor(0xfe, 0x9f, 0x6d),
+    RGBColor(0xfe, 0xcf, 0x92),
+    RGBColor(0xfc, 0xfd, 0xbf),
+]);
+
+/// Perceptually uniform colormap going from black through red to pale yellow.
+pub const INFERNO: Gradient = Gradient::new(&[
+    RGBColor(0x00, 0x00, 0x04),
+    RGBColor(0x16, 0x0b, 0x39),
+    RGBColor(0x42, 0x0a, 0x68),
+    RGBColor(0x6a, 0x17, 0x6e),
+    RGBColor(0x93, 0x26, 0x67),
+    RGBColor(0xbc, 0x37, 0x54),
+    RGBColor(0xdd, 0x51, 0x3a),
+    RGBColor(0xf3, 0x78, 0x19),
+    RGBColor(0xfc, 0xa5, 0x0a),
+    RGBColor(0xf6, 0xd7, 0x46),
+    RGBColor(0xfc, 0xff, 0xa4),
+]);
+
+/// Rainbow-like colormap going from dark blue through green to dark red, with smoother
+/// lightness than a plain rainbow.
+pub const TURBO: Gradient = Gradient::new(&[
+    RGBColor(0x30, 0x12, 0x3b),
+    RGBColor(0x41, 0x45, 0xab),
+    RGBColor(0x46, 0x75, 0xed),
+    RGBColor(0x39, 0xa2, 0xfc),
+    RGBColor(0x1b, 0xcf, 0xd4),
+    RGBColor(0x24, 0xec, 0xa6),
+    RGBColor(0x61, 0xfc, 0x6c),
+    RGBColor(0xa4, 0xfc, 0x3b),
+    RGBColor(0xd1, 0xe8, 0x34),
+    RGBColor(0xf3, 0xc6, 0x3a),
+    RGBColor(0xfe, 0x9b, 0x2d),
+    RGBColor(0xf3, 0x63, 0x15),
+    RGBColor(0xd9, 0x38, 0x06),
+    RGBColor(0xb1, 0x19, 0x01),
+    RGBColor(0x7a, 0x04, 0x02),
+]);
+
+/// Diverging colormap going from blue through light grey to red.
+pub const COOLWARM: Gradient = Gradient::new(&[
+    RGBColor(0x3b, 0x4c, 0xc0),
+    RGBColor(0x68, 0x8a, 0xef),
+    RGBColor(0x99, 0xba, 0xff),
+    RGBColor(0xc9, 0xd8, 0xef),
+    RGBColor(0xed, 0xd1, 0xc2),
+    RGBColor(0xf7, 0xa7, 0x89),
+    RGBColor(0xe3, 0x6a, 0x53),
+    RGBColor(0xb4, 0x04, 0x26),
+]);
+
+/// Diverging colormap going from dark red through white to dark blue.
+pub const RDBU: Gradient = Gradient::new(&[
+    RGBColor(0x67, 0x00, 0x1f),
+    RGBColor(0xb2, 0x18, 0x2b),
+    RGBColor(0xd6, 0x60, 0x4d),
+    RGBColor(0xf4, 0xa5, 0x82),
+    RGBColor(0xfd, 0xdb, 0xc7),
+    RGBColor(0xf7, 0xf7, 0xf7),
+    RGBColor(0xd1, 0xe5, 0xf0),
+    RGBColor(0x92, 0xc5, 0xde),
+    RGBColor(0x43, 0x93, 0xc3),
+    RGBColor(0x21, 0x66, 0xac),
+    RGBColor(0x05, 0x30, 0x61),
+]);
+
+type Registry = RwLock<HashMap<String, Arc<dyn Colormap>>>;
+
+/// Colormaps that can be looked up by name, starting with the built-in ones.
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| {
+        let builtins: [(&str, Gradient); 6] = [
+            ("viridis", VIRIDIS),
+            ("magma", MAGMA),
+            ("inferno", INFERNO),
+            ("turbo", TURBO),
+            ("coolwarm", COOLWARM),
+            ("rdbu", RDBU),
+        ];
+
+        let colormaps = builtins
+            .into_iter()
+            .map(|(name, colormap)| (name.to_string(), Arc::new(colormap) as Arc<dyn Colormap>))
+            .collect();
+
+        RwLock::new(colormaps)
+    })
+}
+
+/// Register a colormap under a name so it can be looked up with [`colormap`], replacing any
+/// colormap already registered under it. Names are case-insensitive.
+pub fn register_colormap<C>(name: &str, colormap: C)
+where
+    C: Colormap + 'static,
+{
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(name.to_lowercase(), Arc::new(colormap));
+}
+
+/// Look up a built-in or registered colormap by name, such as `"viridis"`. Names are
+/// case-insensitive.
+pub fn colormap(name: &str) -> Option<Arc<dyn Colormap>> {
+    registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&name.to_lowercase())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUILTINS: [(&str, Gradient); 6] = [
+        ("viridis", VIRIDIS),
+        ("magma", MAGMA),
+        ("inferno", INFERNO),
+        ("turbo", TURBO),
+        ("coolwarm", COOLWARM),
+        ("rdbu", RDBU),
+    ];
+
+    #[test]
+    fn built_in_maps_end_on_their_first_and_last_colors() {
+        for (name, gradient) in BUILTINS {
+            let first = gradient.stops[0].to_rgba();
+            let last = gradient.stops[gradient.stops.len() - 1].to_rgba();
+
+            assert_eq!(gradient.color(0.0), first, "{name}");
+            assert_eq!(gradient.color(1.0), last, "{name}");
+
+            // Positions outside of the range are clamped to its ends
+            assert_eq!(gradient.color(-0.5), first, "{name}");
+            assert_eq!(gradient.color(7.0), last, "{name}");
+            assert_eq!(gradient.color(f32::NAN), first, "{name}");
+        }
+    }
+
+    #[test]
+    fn gradients_interpolate_between_stops() {
+        let gradient = Gradient::new(&[RGBColor(0, 0, 0), RGBColor(200, 100, 50)]);
+
+        assert_eq!(gradient.color(0.5), RGBColor(100, 50, 25).to_rgba());
+        assert_eq!(gradient.color(0.25), RGBColor(50, 25, 13).to_rgba());
+
+        let single = Gradient::new(&[RGBColor(1, 2, 3)]);
+
+        assert_eq!(single.color(0.7), RGBColor(1, 2, 3).to_rgba());
+        assert_eq!(Gradient::new(&[]).color(0.5), RGBColor(0, 0, 0).to_rgba());
+    }
+
+    #[test]
+    fn built_in_maps_are_looked_up_by_name() {
+        for (name, gradient) in BUILTINS {
+            let found = colormap(&name.to_uppercase()).unwrap();
+
+            assert_eq!(found.color(0.3), gradient.color(0.3), "{name}");
+        }
+
+        assert!(colormap("no such colormap").is_none());
+    }
+
+    #[test]
+    fn registered_maps_are_looked_up_by_name() {
+        register_colormap("Test Ramp", |position: f32| {
+            let value = (position * 255.0) as u8;
+
+            RGBColor(value, value, value).to_rgba()
+        });
+
+        let ramp = colormap("test ramp").unwrap();
+
+        assert_eq!(ramp.color(1.0), RGBColor(255, 255, 255).to_rgba());
+        assert_eq!(ramp.color(2.0), RGBColor(255, 255, 255).to_rgba());
+        assert_eq!(ramp.color(-1.0), RGBColor(0, 0, 0).to_rgba());
+    }
+}
@@ -0,0 +1,39 @@
+//! Shims over the few egui APIs that differ between the versions the crate can be built against,
+//! selected with the `egui27` and `egui28` features. Everything else is used from egui directly.
+
+use std::hash::Hash;
+
+use egui::{Margin, Rect, Ui};
+
+#[inline]
+/// Shrink a rect by a margin on each side.
+pub(crate) fn shrink_rect(rect: Rect, margin: Margin) -> Rect {
+    Rect::from_min_max(
+        rect.min + margin.left_top(),
+        rect.max - margin.right_bottom(),
+    )
+}
+
+#[inline]
+/// Create a child UI covering a rect, with the same layout as its parent.
+pub(crate) fn child_ui(ui: &mut Ui, rect: Rect, id_source: impl Hash) -> Ui {
+    let layout = *ui.layout();
+
+    #[cfg(feature = "egui28")]
+    let child = ui.child_ui_with_id_source(rect, layout, id_source, None);
+    #[cfg(not(feature = "egui28"))]
+    let child = ui.child_ui_with_id_source(rect, layout, id_source);
+
+    child
+}
+
+#[inline]
+/// Show a tooltip with some text next to the pointer, on top of the UI.
+pub(crate) fn show_tooltip_text(ui: &Ui, id_source: impl Hash, text: String) {
+    let id = ui.id().with(id_source);
+
+    #[cfg(feature = "egui28")]
+    egui::show_tooltip_text(ui.ctx(), ui.layer_id(), id, text);
+    #[cfg(not(feature = "egui28"))]
+    egui::show_tooltip_text(ui.ctx(), id, text);
+}
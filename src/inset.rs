@@ -0,0 +1,251 @@
+//! Magnifying inset shown in a corner of a chart
+
+use egui::{Color32, Pos2, Rect, Stroke, Ui, Vec2};
+use plotters::style::{full_palette::GREY_700, Color, RGBAColor, WHITE};
+
+use crate::{backend::EguiBackendColor, Transform};
+
+/// Default magnification of the inset, relative to the chart.
+pub const DEFAULT_MAGNIFICATION: f32 = 3.0;
+/// Default size of the inset, relative to the chart.
+pub const DEFAULT_INSET_SIZE: f32 = 0.3;
+/// Space between the inset and the edges of the chart, in pixels.
+const INSET_MARGIN: f32 = 8.0;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+/// Corner of the chart an inset is shown in.
+pub enum InsetCorner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl InsetCorner {
+    /// Corner on the other side of the chart, horizontally.
+    fn opposite(self) -> Self {
+        match self {
+            Self::TopLeft => Self::TopRight,
+            Self::TopRight => Self::TopLeft,
+            Self::BottomLeft => Self::BottomRight,
+            Self::BottomRight => Self::BottomLeft,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Picture-in-picture magnifier for a chart.
+///
+/// ## Usage
+/// The inset redraws the chart with the same builder callback, zoomed in around the pointer, in
+/// a small panel in one corner of the chart. It can be configured in the following ways:
+///  * `magnification` - How far the inset is zoomed in relative to the chart.
+///  * `size` - Width and height of the inset, relative to the chart.
+///  * `corner` - Corner of the chart the inset is shown in. The inset moves to the opposite
+///    corner while the pointer is over it.
+///  * `pinned` - Pixel of the chart to magnify instead of following the pointer, relative to
+///    the top left of the chart before it is transformed. The inset is hidden while not pinned
+///    and the pointer is outside of the chart.
+///  * `background_color` & `border_color` - Colors the inset is filled and outlined with.
+pub struct Inset {
+    magnification: f32,
+    size: f32,
+    corner: InsetCorner,
+    pinned: Option<(f32, f32)>,
+    background_color: RGBAColor,
+    border_color: RGBAColor,
+}
+
+impl Default for Inset {
+    fn default() -> Self {
+        Self {
+            magnification: DEFAULT_MAGNIFICATION,
+            size: DEFAULT_INSET_SIZE,
+            corner: InsetCorner::default(),
+            pinned: None,
+            background_color: WHITE.to_rgba(),
+            border_color: GREY_700.to_rgba(),
+        }
+    }
+}
+
+impl Inset {
+    #[inline]
+    /// Create a new inset with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Set the magnification of the inset, relative to the chart.
+    pub fn set_magnification(&mut self, magnification: f32) {
+        self.magnification = magnification
+    }
+
+    #[inline]
+    /// Set the magnification of the inset, relative to the chart. Consumes self.
+    pub fn magnification(mut self, magnification: f32) -> Self {
+        self.set_magnification(magnification);
+
+        self
+    }
+
+    #[inline]
+    /// Set the size of the inset, relative to the chart.
+    pub fn set_size(&mut self, size: f32) {
+        self.size = size
+    }
+
+    #[inline]
+    /// Set the size of the inset, relative to the chart. Consumes self.
+    pub fn size(mut self, size: f32) -> Self {
+        self.set_size(size);
+
+        self
+    }
+
+    #[inline]
+    /// Set the corner of the chart the inset is shown in.
+    pub fn set_corner(&mut self, corner: InsetCorner) {
+        self.corner = corner
+    }
+
+    #[inline]
+    /// Set the corner of the chart the inset is shown in. Consumes self.
+    pub fn corner(mut self, corner: InsetCorner) -> Self {
+        self.set_corner(corner);
+
+        self
+    }
+
+    #[inline]
+    /// Pin the inset to a pixel of the chart instead of following the pointer. Set to None to
+    /// follow the pointer again.
+    pub fn set_pinned(&mut self, pinned: Option<(f32, f32)>) {
+        self.pinned = pinned
+    }
+
+    #[inline]
+    /// Pin the inset to a pixel of the chart. Consumes self.
+    pub fn pinned(mut self, pinned: Option<(f32, f32)>) -> Self {
+        self.set_pinned(pinned);
+
+        self
+    }
+
+    #[inline]
+    /// Set the background color of the inset.
+    pub fn set_background_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.background_color = color.into()
+    }
+
+    #[inline]
+    /// Set the background color of the inset. Consumes self.
+    pub fn background_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_background_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the border color of the inset.
+    pub fn set_border_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.border_color = color.into()
+    }
+
+    #[inline]
+    /// Set the border color of the inset. Consumes self.
+    pub fn border_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_border_color(color);
+
+        self
+    }
+
+    /// Where to draw the inset and the transform to draw the chart in it with, given the
+    /// chart's rect and transform. None if there's nothing to magnify.
+    pub(crate) fn layout(&self, ui: &Ui, transform: &Transform) -> Option<(Rect, Transform)> {
+        let rect = ui.max_rect();
+        let center = rect.size() / 2.0;
+        let scale = transform.scale as f32;
+
+        // Pixel of the untransformed chart to magnify
+        let target = match self.pinned {
+            Some((x, y)) => Vec2::new(x, y),
+            None => {
+                let pos = ui
+                    .ctx()
+                    .pointer_hover_pos()
+                    .filter(|pos| rect.contains(*pos) && ui.ui_contains_pointer())?;
+
+                let offset = Vec2::new(transform.x as f32, transform.y as f32);
+
+                (pos - rect.min - offset - center) / scale + center
+            }
+        };
+
+        let size = rect.size() * self.size.clamp(0.0, 1.0);
+        let inset = |corner: InsetCorner| {
+            let x = match corner {
+                InsetCorner::TopLeft | InsetCorner::BottomLeft => rect.left() + INSET_MARGIN,
+                _ => rect.right() - INSET_MARGIN - size.x,
+            };
+            let y = match corner {
+                InsetCorner::TopLeft | InsetCorner::TopRight => rect.top() + INSET_MARGIN,
+                _ => rect.bottom() - INSET_MARGIN - size.y,
+            };
+
+            Rect::from_min_size(Pos2::new(x, y), size)
+        };
+
+        // Get out of the way of the pointer
+        let mut inset_rect = inset(self.corner);
+
+        if let Some(pos) = ui.ctx().pointer_hover_pos() {
+            if inset_rect.contains(pos) {
+                inset_rect = inset(self.corner.opposite());
+            }
+        }
+
+        // Move the target pixel to the center of the inset
+        let inset_scale = scale * self.magnification;
+        let inset_center = inset_rect.center() - rect.min;
+        let offset = inset_center - center - (target - center) * inset_scale;
+
+        let inset_transform = Transform {
+            scale: inset_scale as f64,
+            x: offset.x as i32,
+            y: offset.y as i32,
+            ..*transform
+        };
+
+        Some((inset_rect, inset_transform))
+    }
+
+    /// Fill the inset's background, before the chart is drawn in it.
+    pub(crate) fn draw_background(&self, ui: &Ui, rect: Rect) {
+        let color: Color32 =
+            EguiBackendColor::from(self.background_color.to_backend_color()).into();
+
+        ui.painter().rect_filled(rect, 0.0, color);
+    }
+
+    /// Outline the inset, after the chart is drawn in it.
+    pub(crate) fn draw_border(&self, ui: &Ui, rect: Rect) {
+        let color: Color32 = EguiBackendColor::from(self.border_color.to_backend_color()).into();
+
+        ui.painter().rect_stroke(rect, 0.0, Stroke::new(1.0, color));
+    }
+}
@@ -25,6 +25,15 @@
 //! ### Features
 //!
 //!  * `timechart` - Includes all the pre-made animatable charts like XyTimeData and TimeData.
+//!  * `adaptive` - Includes `AdaptiveQuality`, lowering a chart's drawing quality while it's
+//!    too slow to draw.
+//!  * `snapshot` - Includes the `snapshot` module, rendering charts offscreen to compare them
+//!    against golden images in tests.
+//!  * `serde` - Makes `ChartState`, `TimeChartState`, and the types in them serializable, for
+//!    saving charts' views and playback positions along with the rest of an app's state.
+//!  * `egui28` - Builds against egui 0.28. Enabled by default.
+//!  * `egui27` - Builds against egui 0.27 instead, for apps that haven't upgraded yet. Disable
+//!    the default features to use it, as only one egui version can be enabled.
 //!
 //! ## Examples
 //!
@@ -33,7 +42,7 @@
 //! [eframe](https://docs.rs/eframe/0.22.0/eframe/index.html#usage-native) and
 //! [plotters](https://docs.rs/plotters/0.3.4/plotters/index.html#quick-start).
 //!
-//! ```rust
+//! ```rust,no_run
 //! use eframe::egui::{self, CentralPanel, Visuals};
 //! use egui_plotter::EguiBackend;
 //! use plotters::prelude::*;
@@ -43,7 +52,7 @@
 //!     eframe::run_native(
 //!         "Simple Example",
 //!         native_options,
-//!         Box::new(|cc| Box::new(Simple::new(cc))),
+//!         Box::new(|cc| Ok(Box::new(Simple::new(cc)))),
 //!     )
 //!     .unwrap();
 //! }
@@ -109,7 +118,7 @@
 //! user interactivity with your plotter charts. You can either make your own chart or
 //! use a prebuilt chart type included in the `charts` module.
 //!
-//! ```rust
+//! ```rust,no_run
 //! use eframe::egui::{self, CentralPanel, Key, Visuals};
 //! use egui_plotter::{Chart, MouseConfig};
 //! use plotters::prelude::*;
@@ -120,7 +129,7 @@
 //!     eframe::run_native(
 //!         "ParaChart Example",
 //!         native_options,
-//!         Box::new(|cc| Box::new(ParaChart::new(cc))),
+//!         Box::new(|cc| Ok(Box::new(ParaChart::new(cc)))),
 //!     )
 //!     .unwrap();
 //! }
@@ -212,14 +221,42 @@
 //! }
 //! ```
 
+#[cfg(all(feature = "egui27", feature = "egui28"))]
+compile_error!(
+    "the `egui27` and `egui28` features can't be enabled together, disable the default features \
+     to build against egui 0.27"
+);
+#[cfg(not(any(feature = "egui27", feature = "egui28")))]
+compile_error!("one of the `egui27` or `egui28` features has to be enabled");
+
+// Everything refers to egui by its usual name, whichever version it is
+#[cfg(all(feature = "egui27", not(feature = "egui28")))]
+extern crate egui27 as egui;
+
+#[cfg(feature = "adaptive")]
+mod adaptive;
 mod backend;
 mod chart;
 pub mod charts;
+pub mod colormap;
+mod compat;
+mod inset;
+pub mod locale;
+mod menu;
+pub mod palette;
+pub mod registry;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 
-pub use backend::{EguiBackend, EguiBackendError};
+#[cfg(feature = "adaptive")]
+pub use adaptive::{AdaptiveQuality, DEFAULT_FRAME_BUDGET, DEFAULT_IDLE_DELAY};
+pub use backend::{EguiBackend, EguiBackendError, RenderQuality};
 pub use chart::{
-    Chart, MouseButton, MouseConfig, Transform, DEFAULT_MOVE_SCALE, DEFAULT_SCROLL_SCALE,
+    Chart, ChartState, MouseButton, MouseConfig, ScreenTransform, Transform, DEFAULT_FRICTION,
+    DEFAULT_MOVE_SCALE, DEFAULT_SCROLL_SCALE,
 };
+pub use inset::{Inset, InsetCorner, DEFAULT_INSET_SIZE, DEFAULT_MAGNIFICATION};
+pub use menu::ContextMenu;
 
 #[cfg(feature = "timechart")]
 use std::ops::Range;
@@ -0,0 +1,225 @@
+//! Locale used to format the numbers shown by charts.
+//!
+//! Tick labels, tooltips, and exported CSV all format their numbers with the locale set with
+//! [`set_locale`]. The default locale formats numbers the way Rust does, with a `.` decimal
+//! separator and no thousands grouping, so tick labels are the same as plotters' own.
+
+use std::{fmt::Display, sync::RwLock};
+
+use plotters::data::float::FloatPrettyPrinter;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Number separators of a locale.
+///
+/// ## Usage
+/// Start from one of the presets, or from [`Locale::new`], and change what's needed:
+///  * `decimal_separator` - Separates the integer and fractional parts of numbers.
+///  * `thousands_separator` - Groups the digits of large numbers by the thousand. Numbers aren't
+///    grouped if None.
+///
+/// CSV fields are separated with `;` instead of `,` whenever the decimal separator is `,`, as
+/// spreadsheets in those locales expect.
+pub struct Locale {
+    decimal_separator: char,
+    thousands_separator: Option<char>,
+}
+
+impl Default for Locale {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Locale {
+    /// English as written in the US, such as 1,234.5.
+    pub const EN_US: Self = Self::new().thousands_separator(Some(','));
+    /// German, such as 1.234,5.
+    pub const DE_DE: Self = Self::new()
+        .decimal_separator(',')
+        .thousands_separator(Some('.'));
+    /// French, such as 1 234,5, grouped with narrow no-break spaces.
+    pub const FR_FR: Self = Self::new()
+        .decimal_separator(',')
+        .thousands_separator(Some('\u{202f}'));
+
+    #[inline]
+    /// Create the default locale, formatting numbers the way Rust does.
+    pub const fn new() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: None,
+        }
+    }
+
+    #[inline]
+    /// Set the decimal separator.
+    pub fn set_decimal_separator(&mut self, decimal_separator: char) {
+        self.decimal_separator = decimal_separator
+    }
+
+    #[inline]
+    /// Set the decimal separator. Consumes self.
+    pub const fn decimal_separator(mut self, decimal_separator: char) -> Self {
+        self.decimal_separator = decimal_separator;
+
+        self
+    }
+
+    #[inline]
+    /// Set the thousands separator, or disable grouping with None.
+    pub fn set_thousands_separator(&mut self, thousands_separator: Option<char>) {
+        self.thousands_separator = thousands_separator
+    }
+
+    #[inline]
+    /// Set the thousands separator, or disable grouping with None. Consumes self.
+    pub const fn thousands_separator(mut self, thousands_separator: Option<char>) -> Self {
+        self.thousands_separator = thousands_separator;
+
+        self
+    }
+
+    /// Localize a number already formatted by Rust, such as "-1234.5" or "+12.5".
+    pub fn localize(&self, number: &str) -> String {
+        let (sign, digits) = match number.find(|c: char| c != '-' && c != '+') {
+            Some(index) => number.split_at(index),
+            None => ("", number),
+        };
+
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+
+        let mut localized = String::with_capacity(number.len() + integer.len() / 3);
+
+        localized.push_str(sign);
+
+        // Leave anything that isn't a plain number, such as "inf" or "1e-5", ungrouped
+        match self.thousands_separator {
+            Some(separator) if integer.bytes().all(|byte| byte.is_ascii_digit()) => {
+                for (index, digit) in integer.chars().enumerate() {
+                    if index > 0 && (integer.len() - index) % 3 == 0 {
+                        localized.push(separator);
+                    }
+
+                    localized.push(digit);
+                }
+            }
+            _ => localized.push_str(integer),
+        }
+
+        if let Some(fraction) = fraction {
+            localized.push(self.decimal_separator);
+            localized.push_str(fraction);
+        }
+
+        localized
+    }
+
+    #[inline]
+    /// Format a number as Rust would with `{}`, then localize it.
+    pub fn format_number<T>(&self, value: T) -> String
+    where
+        T: Display,
+    {
+        self.localize(&value.to_string())
+    }
+
+    #[inline]
+    /// Format a number with a fixed number of decimals, then localize it.
+    pub fn format_fixed(&self, value: f64, decimals: usize) -> String {
+        self.localize(&format!("{:.*}", decimals, value))
+    }
+
+    #[inline]
+    /// Label of a tick on an axis, formatted like plotters' default labels and then localized.
+    /// The default locale leaves them exactly as plotters formats them.
+    pub fn format_tick(&self, value: f64) -> String {
+        let printer = FloatPrettyPrinter {
+            allow_scientific: false,
+            min_decimal: 1,
+            max_decimal: 5,
+        };
+
+        self.localize(&printer.print(value))
+    }
+
+    #[inline]
+    /// Separator between the fields of exported CSV.
+    pub fn csv_separator(&self) -> char {
+        match self.decimal_separator {
+            ',' => ';',
+            _ => ',',
+        }
+    }
+
+    #[inline]
+    /// Format a number for exported CSV. Only the decimal separator is localized, as grouped
+    /// numbers can't be read back by spreadsheets.
+    pub fn format_csv_number<T>(&self, value: T) -> String
+    where
+        T: Display,
+    {
+        Self {
+            thousands_separator: None,
+            ..*self
+        }
+        .format_number(value)
+    }
+}
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::new());
+
+/// Set the locale every chart formats its numbers with.
+pub fn set_locale(locale: Locale) {
+    *LOCALE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = locale;
+}
+
+/// Locale every chart formats its numbers with, [`Locale::new`] unless changed with
+/// [`set_locale`].
+pub fn locale() -> Locale {
+    *LOCALE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::coord::{ranged1d::ValueFormatter, types::RangedCoordf32};
+
+    use super::*;
+
+    #[test]
+    fn default_ticks_match_plotters() {
+        for value in [0.0, -0.5, 1.0, 12.25, 1234.5, -98765.4321, 0.000_01, 1e9] {
+            assert_eq!(
+                Locale::new().format_tick(value as f32 as f64),
+                RangedCoordf32::format(&(value as f32))
+            );
+        }
+    }
+
+    #[test]
+    fn numbers_are_grouped_and_separated() {
+        assert_eq!(Locale::EN_US.format_number(-1234567.5), "-1,234,567.5");
+        assert_eq!(Locale::DE_DE.format_number(1234.5), "1.234,5");
+        assert_eq!(Locale::FR_FR.format_fixed(1234.5, 2), "1\u{202f}234,50");
+        assert_eq!(Locale::DE_DE.format_number(f32::INFINITY), "inf");
+        assert_eq!(Locale::new().format_number(1234.5), "1234.5");
+    }
+
+    #[test]
+    fn csv_separator_follows_the_decimal_separator() {
+        assert_eq!(Locale::new().csv_separator(), ',');
+        assert_eq!(Locale::EN_US.csv_separator(), ',');
+        assert_eq!(Locale::DE_DE.csv_separator(), ';');
+        assert_eq!(Locale::FR_FR.csv_separator(), ';');
+
+        // Grouping is left out, so spreadsheets can read the numbers back
+        assert_eq!(Locale::DE_DE.format_csv_number(1234.5), "1234,5");
+    }
+}
@@ -0,0 +1,227 @@
+//! Right-click context menu shared by all charts
+
+use egui::{ColorImage, Event, Rect, Response, Ui, ViewportCommand};
+
+type ImageCallback = Box<dyn FnMut(ColorImage)>;
+type LegendCallback<Data> = Box<dyn FnMut(&mut Data)>;
+type EntriesCallback<Data> = Box<dyn FnMut(&mut Ui, &mut Data)>;
+
+/// Action picked from the context menu that the chart itself has to carry out.
+pub(crate) enum MenuAction {
+    None,
+    ResetView,
+    ExportCsv,
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Progress of an image capture started from the context menu.
+enum Capture {
+    Idle,
+    /// Wait a frame so the menu has closed before the screenshot is taken.
+    Requested(Rect),
+    Waiting(Rect),
+}
+
+/// Right-click context menu for a chart.
+///
+/// ## Usage
+/// The menu can hold any combination of the following built-in entries, each
+/// only being shown once enabled:
+///  * `reset_view` - Restores the pitch, yaw, scale, and offset the chart had when it was first
+///    drawn.
+///  * `copy_image` - Captures the area of the chart and hands it to the provided callback. egui
+///    can't place images on the clipboard itself, so what to do with the image is up to you.
+///  * `export_csv` - Copies the chart's visible data to the clipboard as CSV. See
+///    `Chart::set_csv_cb`.
+///  * `toggle_legend` - Calls the provided callback so you can flip the legend on/off in your
+///    chart's data.
+///
+/// App-specific entries can be appended below the built-in ones with `entries`.
+pub struct ContextMenu<Data> {
+    reset_view: bool,
+    copy_image: Option<ImageCallback>,
+    export_csv: bool,
+    toggle_legend: Option<LegendCallback<Data>>,
+    entries: Option<EntriesCallback<Data>>,
+    capture: Capture,
+}
+
+impl<Data> Default for ContextMenu<Data> {
+    fn default() -> Self {
+        Self {
+            reset_view: false,
+            copy_image: None,
+            export_csv: false,
+            toggle_legend: None,
+            entries: None,
+            capture: Capture::Idle,
+        }
+    }
+}
+
+impl<Data> ContextMenu<Data> {
+    #[inline]
+    /// Create a new, empty context menu.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Show/hide the "Reset View" entry.
+    pub fn set_reset_view(&mut self, reset_view: bool) {
+        self.reset_view = reset_view
+    }
+
+    #[inline]
+    /// Show/hide the "Reset View" entry. Consumes self.
+    pub fn reset_view(mut self, reset_view: bool) -> Self {
+        self.set_reset_view(reset_view);
+
+        self
+    }
+
+    #[inline]
+    /// Show the "Copy Image" entry, handing the captured image to the callback.
+    pub fn set_copy_image(&mut self, copy_image: ImageCallback) {
+        self.copy_image = Some(copy_image)
+    }
+
+    #[inline]
+    /// Show the "Copy Image" entry, handing the captured image to the callback. Consumes self.
+    pub fn copy_image(mut self, copy_image: ImageCallback) -> Self {
+        self.set_copy_image(copy_image);
+
+        self
+    }
+
+    #[inline]
+    /// Show/hide the "Export CSV" entry.
+    pub fn set_export_csv(&mut self, export_csv: bool) {
+        self.export_csv = export_csv
+    }
+
+    #[inline]
+    /// Show/hide the "Export CSV" entry. Consumes self.
+    pub fn export_csv(mut self, export_csv: bool) -> Self {
+        self.set_export_csv(export_csv);
+
+        self
+    }
+
+    #[inline]
+    /// Show the "Toggle Legend" entry, calling the callback when clicked.
+    pub fn set_toggle_legend(&mut self, toggle_legend: LegendCallback<Data>) {
+        self.toggle_legend = Some(toggle_legend)
+    }
+
+    #[inline]
+    /// Show the "Toggle Legend" entry, calling the callback when clicked. Consumes self.
+    pub fn toggle_legend(mut self, toggle_legend: LegendCallback<Data>) -> Self {
+        self.set_toggle_legend(toggle_legend);
+
+        self
+    }
+
+    #[inline]
+    /// Set the callback used to add app-specific entries below the built-in ones.
+    pub fn set_entries(&mut self, entries: EntriesCallback<Data>) {
+        self.entries = Some(entries)
+    }
+
+    #[inline]
+    /// Set the callback used to add app-specific entries below the built-in ones. Consumes self.
+    pub fn entries(mut self, entries: EntriesCallback<Data>) -> Self {
+        self.set_entries(entries);
+
+        self
+    }
+
+    #[inline]
+    /// Return true if the menu has anything to show.
+    pub fn is_empty(&self) -> bool {
+        !self.reset_view
+            && self.copy_image.is_none()
+            && !self.export_csv
+            && self.toggle_legend.is_none()
+            && self.entries.is_none()
+    }
+
+    /// Attach the menu to the chart's response, returning the action the chart needs to take.
+    pub(crate) fn show(&mut self, ui: &Ui, response: &Response, data: &mut Data) -> MenuAction {
+        self.poll_capture(ui);
+
+        if self.is_empty() {
+            return MenuAction::None;
+        }
+
+        let mut action = MenuAction::None;
+
+        response.context_menu(|ui| {
+            if self.reset_view && ui.button("Reset View").clicked() {
+                action = MenuAction::ResetView;
+                ui.close_menu();
+            }
+
+            if self.copy_image.is_some() && ui.button("Copy Image").clicked() {
+                self.capture = Capture::Requested(response.rect);
+                ui.ctx().request_repaint();
+                ui.close_menu();
+            }
+
+            if self.export_csv && ui.button("Export CSV").clicked() {
+                action = MenuAction::ExportCsv;
+                ui.close_menu();
+            }
+
+            if let Some(toggle_legend) = &mut self.toggle_legend {
+                if ui.button("Toggle Legend").clicked() {
+                    toggle_legend(data);
+                    ui.close_menu();
+                }
+            }
+
+            if let Some(entries) = &mut self.entries {
+                ui.separator();
+
+                entries(ui, data);
+            }
+        });
+
+        action
+    }
+
+    /// Drive an in-progress image capture, handing the image off once it arrives.
+    fn poll_capture(&mut self, ui: &Ui) {
+        match self.capture {
+            Capture::Idle => {}
+            Capture::Requested(rect) => {
+                ui.ctx().send_viewport_cmd(ViewportCommand::Screenshot);
+                ui.ctx().request_repaint();
+
+                self.capture = Capture::Waiting(rect);
+            }
+            Capture::Waiting(rect) => {
+                let screenshot = ui.input(|input| {
+                    input.raw.events.iter().find_map(|event| match event {
+                        Event::Screenshot { image, .. } => Some(image.clone()),
+                        _ => None,
+                    })
+                });
+
+                match screenshot {
+                    Some(image) => {
+                        let pixels_per_point = ui.ctx().pixels_per_point();
+                        let image = image.region(&rect, Some(pixels_per_point));
+
+                        if let Some(copy_image) = &mut self.copy_image {
+                            copy_image(image);
+                        }
+
+                        self.capture = Capture::Idle;
+                    }
+                    None => ui.ctx().request_repaint(),
+                }
+            }
+        }
+    }
+}
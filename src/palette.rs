@@ -0,0 +1,116 @@
+//! Palettes of distinct colors given to series that aren't colored explicitly.
+//!
+//! Charts cycle through a palette in the order series are added, so the first series is always
+//! the first color of the palette, the second series the second color, and so on. Charts without
+//! their own palette use the default palette, which can be changed for the whole app with
+//! [`set_default_palette`] so every chart colors its series the same way.
+
+use std::{borrow::Cow, sync::RwLock};
+
+use plotters::style::{RGBAColor, RGBColor};
+
+#[derive(Debug, Clone)]
+/// Colors cycled through when coloring series.
+pub struct Palette {
+    colors: Cow<'static, [RGBColor]>,
+}
+
+impl Palette {
+    #[inline]
+    /// Create a palette from a static list of colors.
+    pub const fn new(colors: &'static [RGBColor]) -> Self {
+        Self {
+            colors: Cow::Borrowed(colors),
+        }
+    }
+
+    /// Create a palette from any list of colors.
+    pub fn from_colors<I>(colors: I) -> Self
+    where
+        I: IntoIterator<Item = RGBColor>,
+    {
+        Self {
+            colors: Cow::Owned(colors.into_iter().collect()),
+        }
+    }
+
+    #[inline]
+    /// Colors of the palette, in order.
+    pub fn colors(&self) -> &[RGBColor] {
+        &self.colors
+    }
+
+    #[inline]
+    /// Color of the series at the given index, wrapping around once the palette runs out. Black
+    /// if the palette is empty.
+    pub fn color(&self, index: usize) -> RGBAColor {
+        let RGBColor(r, g, b) = match self.colors.is_empty() {
+            true => RGBColor(0, 0, 0),
+            false => self.colors[index % self.colors.len()],
+        };
+
+        RGBAColor(r, g, b, 1.0)
+    }
+}
+
+impl Default for Palette {
+    #[inline]
+    fn default() -> Self {
+        OKABE_ITO
+    }
+}
+
+/// Colorblind-safe palette by Okabe and Ito, the default palette.
+pub const OKABE_ITO: Palette = Palette::new(&[
+    RGBColor(0x00, 0x72, 0xb2),
+    RGBColor(0xe6, 0x9f, 0x00),
+    RGBColor(0x00, 0x9e, 0x73),
+    RGBColor(0xd5, 0x5e, 0x00),
+    RGBColor(0x56, 0xb4, 0xe9),
+    RGBColor(0xcc, 0x79, 0xa7),
+    RGBColor(0xf0, 0xe4, 0x42),
+    RGBColor(0x00, 0x00, 0x00),
+]);
+
+/// Colorblind-safe palette by Paul Tol.
+pub const TOL_BRIGHT: Palette = Palette::new(&[
+    RGBColor(0x44, 0x77, 0xaa),
+    RGBColor(0xee, 0x66, 0x77),
+    RGBColor(0x22, 0x88, 0x33),
+    RGBColor(0xcc, 0xbb, 0x44),
+    RGBColor(0x66, 0xcc, 0xee),
+    RGBColor(0xaa, 0x33, 0x77),
+    RGBColor(0xbb, 0xbb, 0xbb),
+]);
+
+/// Ten category palette used by Tableau and matplotlib. Not colorblind-safe.
+pub const TABLEAU10: Palette = Palette::new(&[
+    RGBColor(0x1f, 0x77, 0xb4),
+    RGBColor(0xff, 0x7f, 0x0e),
+    RGBColor(0x2c, 0xa0, 0x2c),
+    RGBColor(0xd6, 0x27, 0x28),
+    RGBColor(0x94, 0x67, 0xbd),
+    RGBColor(0x8c, 0x56, 0x4b),
+    RGBColor(0xe3, 0x77, 0xc2),
+    RGBColor(0x7f, 0x7f, 0x7f),
+    RGBColor(0xbc, 0xbd, 0x22),
+    RGBColor(0x17, 0xbe, 0xcf),
+]);
+
+static DEFAULT_PALETTE: RwLock<Palette> = RwLock::new(OKABE_ITO);
+
+/// Set the palette used by every chart that doesn't have its own.
+pub fn set_default_palette(palette: Palette) {
+    *DEFAULT_PALETTE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = palette;
+}
+
+/// Palette used by every chart that doesn't have its own, [`OKABE_ITO`] unless changed with
+/// [`set_default_palette`].
+pub fn default_palette() -> Palette {
+    DEFAULT_PALETTE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
@@ -0,0 +1,365 @@
+//! Registration of chart types, letting custom charts be drawn, played back, linked, and exported
+//! alongside the built-in ones.
+//!
+//! Every chart in the [`charts`](crate::charts) module, along with [`Chart`] itself, implements
+//! [`ChartWidget`]. Custom chart types implementing it too can be stored and driven next to them
+//! as `Box<dyn ChartWidget>`, and registered by name in a [`ChartRegistry`] so they can be created
+//! from a name, such as one saved in a layout or a settings file.
+
+use std::{collections::HashMap, io, path::Path};
+
+use egui::Ui;
+
+#[cfg(feature = "timechart")]
+use crate::charts::{Gauge, LinkGroup, PlaybackGroup, Sparkline, TimeData, Timeline, XyTimeData};
+#[cfg(feature = "adaptive")]
+use crate::AdaptiveQuality;
+use crate::{
+    charts::{BarChart, Comparison, Contour, Quiver, Waterfall},
+    Chart, RenderQuality,
+};
+
+/// Chart that can be drawn to a UI element, optionally played back, linked, and exported.
+///
+/// ## Usage
+/// Only `.draw()` has to be implemented. Charts that aren't animated can leave the playback
+/// methods to their defaults, which do nothing, and charts without data to export can leave
+/// `.export_csv()` to its default, which returns an [`io::ErrorKind::Unsupported`] error.
+///
+/// Custom charts are usually built on top of a [`Chart`], drawing it from `.draw()`.
+pub trait ChartWidget {
+    /// Draw the chart to a UI element.
+    fn draw(&mut self, ui: &mut Ui);
+
+    /// Start/enable playback of the chart, if it is animated.
+    fn start_playback(&mut self) {}
+
+    /// Stop/disable playback of the chart, if it is animated.
+    fn stop_playback(&mut self) {}
+
+    /// Return true if the chart is being played back.
+    fn is_playing(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "timechart")]
+    /// Join a playback group, sharing its playback clock with every other member. Charts that
+    /// aren't animated ignore it.
+    fn set_playback_group(&mut self, _group: &PlaybackGroup) {}
+
+    #[cfg(feature = "timechart")]
+    /// Join a link group, highlighting the keys hovered and selected in any chart of the group.
+    /// Charts without anything to highlight ignore it.
+    fn set_link_group(&mut self, _group: &LinkGroup) {}
+
+    /// Set how carefully the chart is drawn. Charts that don't support it ignore it.
+    fn set_render_quality(&mut self, _quality: RenderQuality) {}
+
+    #[cfg(feature = "adaptive")]
+    /// Set the controller lowering the chart's quality while it's too slow to draw. Charts that
+    /// don't support it ignore it.
+    fn set_adaptive_quality(&mut self, _adaptive: Option<AdaptiveQuality>) {}
+
+    /// Write the data of the chart to a CSV file at `path`.
+    fn export_csv(&self, _path: &Path) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "chart has no data to export",
+        ))
+    }
+}
+
+/// Function creating a chart registered in a [`ChartRegistry`].
+type ChartFactory = Box<dyn Fn() -> Box<dyn ChartWidget>>;
+
+/// Chart types registered by name.
+///
+/// ## Usage
+/// Register a function creating the chart under a name with `.register()`, then create as many
+/// of them as needed with `.create()`. Names are case-insensitive.
+///
+/// Charts borrow their data from the thread they were created on, so a registry is owned by the
+/// app rather than shared globally like registered [colormaps](crate::colormap).
+#[derive(Default)]
+pub struct ChartRegistry {
+    factories: HashMap<String, ChartFactory>,
+}
+
+impl ChartRegistry {
+    #[inline]
+    /// Create a new, empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a chart type under a name, replacing any chart type already registered under it.
+    pub fn register<F, C>(&mut self, name: &str, factory: F)
+    where
+        F: Fn() -> C + 'static,
+        C: ChartWidget + 'static,
+    {
+        self.factories
+            .insert(name.to_lowercase(), Box::new(move || Box::new(factory())));
+    }
+
+    #[inline]
+    /// Register a chart type under a name. Consumes self.
+    pub fn with<F, C>(mut self, name: &str, factory: F) -> Self
+    where
+        F: Fn() -> C + 'static,
+        C: ChartWidget + 'static,
+    {
+        self.register(name, factory);
+
+        self
+    }
+
+    #[inline]
+    /// Remove the chart type registered under a name, returning true if there was one.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.factories.remove(&name.to_lowercase()).is_some()
+    }
+
+    #[inline]
+    /// Create a chart of the type registered under a name, if any.
+    pub fn create(&self, name: &str) -> Option<Box<dyn ChartWidget>> {
+        self.factories
+            .get(&name.to_lowercase())
+            .map(|factory| factory())
+    }
+
+    #[inline]
+    /// Return true if a chart type is registered under a name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(&name.to_lowercase())
+    }
+
+    /// Names of every registered chart type, sorted.
+    pub fn get_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+
+        names.sort_unstable();
+
+        names
+    }
+}
+
+impl<Data> ChartWidget for Chart<Data> {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Chart::draw(self, ui)
+    }
+
+    #[inline]
+    fn set_render_quality(&mut self, quality: RenderQuality) {
+        Chart::set_render_quality(self, quality)
+    }
+
+    #[inline]
+    #[cfg(feature = "adaptive")]
+    fn set_adaptive_quality(&mut self, adaptive: Option<AdaptiveQuality>) {
+        Chart::set_adaptive_quality(self, adaptive)
+    }
+}
+
+impl ChartWidget for BarChart {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        BarChart::draw(self, ui)
+    }
+}
+
+impl ChartWidget for Comparison {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Comparison::draw(self, ui)
+    }
+}
+
+impl ChartWidget for Contour {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Contour::draw(self, ui)
+    }
+}
+
+impl ChartWidget for Quiver {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Quiver::draw(self, ui)
+    }
+}
+
+impl ChartWidget for Waterfall {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Waterfall::draw(self, ui)
+    }
+}
+
+#[cfg(feature = "timechart")]
+impl ChartWidget for Gauge {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Gauge::draw(self, ui)
+    }
+}
+
+#[cfg(feature = "timechart")]
+impl ChartWidget for Sparkline {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Sparkline::draw(self, ui)
+    }
+
+    #[inline]
+    fn start_playback(&mut self) {
+        Sparkline::start_playback(self)
+    }
+
+    #[inline]
+    fn stop_playback(&mut self) {
+        Sparkline::stop_playback(self)
+    }
+
+    #[inline]
+    fn is_playing(&self) -> bool {
+        Sparkline::is_playing(self)
+    }
+
+    #[inline]
+    fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        Sparkline::set_playback_group(self, group)
+    }
+}
+
+#[cfg(feature = "timechart")]
+impl ChartWidget for Timeline {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        Timeline::draw(self, ui)
+    }
+
+    #[inline]
+    fn start_playback(&mut self) {
+        Timeline::start_playback(self)
+    }
+
+    #[inline]
+    fn stop_playback(&mut self) {
+        Timeline::stop_playback(self)
+    }
+
+    #[inline]
+    fn is_playing(&self) -> bool {
+        Timeline::is_playing(self)
+    }
+
+    #[inline]
+    fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        Timeline::set_playback_group(self, group)
+    }
+
+    #[inline]
+    fn set_link_group(&mut self, group: &LinkGroup) {
+        Timeline::set_link_group(self, group)
+    }
+}
+
+#[cfg(feature = "timechart")]
+impl ChartWidget for XyTimeData {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        XyTimeData::draw(self, ui)
+    }
+
+    #[inline]
+    fn set_render_quality(&mut self, quality: RenderQuality) {
+        XyTimeData::set_render_quality(self, quality)
+    }
+
+    #[inline]
+    #[cfg(feature = "adaptive")]
+    fn set_adaptive_quality(&mut self, adaptive: Option<AdaptiveQuality>) {
+        XyTimeData::set_adaptive_quality(self, adaptive)
+    }
+
+    #[inline]
+    fn start_playback(&mut self) {
+        XyTimeData::start_playback(self)
+    }
+
+    #[inline]
+    fn stop_playback(&mut self) {
+        XyTimeData::stop_playback(self)
+    }
+
+    #[inline]
+    fn is_playing(&self) -> bool {
+        XyTimeData::is_playing(self)
+    }
+
+    #[inline]
+    fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        XyTimeData::set_playback_group(self, group)
+    }
+
+    #[inline]
+    fn set_link_group(&mut self, group: &LinkGroup) {
+        XyTimeData::set_link_group(self, group)
+    }
+
+    #[inline]
+    fn export_csv(&self, path: &Path) -> io::Result<()> {
+        XyTimeData::export_csv(self, path, None)
+    }
+}
+
+#[cfg(feature = "timechart")]
+impl ChartWidget for TimeData {
+    #[inline]
+    fn draw(&mut self, ui: &mut Ui) {
+        TimeData::draw(self, ui)
+    }
+
+    #[inline]
+    fn set_render_quality(&mut self, quality: RenderQuality) {
+        TimeData::set_render_quality(self, quality)
+    }
+
+    #[inline]
+    #[cfg(feature = "adaptive")]
+    fn set_adaptive_quality(&mut self, adaptive: Option<AdaptiveQuality>) {
+        TimeData::set_adaptive_quality(self, adaptive)
+    }
+
+    #[inline]
+    fn start_playback(&mut self) {
+        TimeData::start_playback(self)
+    }
+
+    #[inline]
+    fn stop_playback(&mut self) {
+        TimeData::stop_playback(self)
+    }
+
+    #[inline]
+    fn is_playing(&self) -> bool {
+        TimeData::is_playing(self)
+    }
+
+    #[inline]
+    fn set_playback_group(&mut self, group: &PlaybackGroup) {
+        TimeData::set_playback_group(self, group)
+    }
+
+    #[inline]
+    fn set_link_group(&mut self, group: &LinkGroup) {
+        TimeData::set_link_group(self, group)
+    }
+
+    #[inline]
+    fn export_csv(&self, path: &Path) -> io::Result<()> {
+        TimeData::export_csv(self, path, None)
+    }
+}
@@ -0,0 +1,590 @@
+//! Deterministic offscreen rendering of charts, for regression-testing them against golden
+//! images.
+//!
+//! **Ensure the `snapshot` feature is enabled to use this module.**
+//!
+//! ## Usage
+//! [`Snapshot`] draws a chart into a fresh egui context of a fixed size, with egui's built-in
+//! fonts, feathering disabled, light visuals, and a fixed clock, then rasterizes it in software
+//! so the result doesn't depend on a GPU or window. [`assert_snapshot`] compares the rendered
+//! image against a golden PNG, allowing for a little difference per color channel.
+//!
+//! ```rust,no_run
+//! use egui_plotter::{
+//!     charts::BarChart,
+//!     snapshot::{assert_snapshot, Snapshot},
+//! };
+//!
+//! let mut chart = BarChart::new(&["a", "b", "c"], "Bars").palette_series("Run", &[1.0, 3.0, 2.0]);
+//!
+//! let image = Snapshot::new(400, 300).render(|ui| chart.draw(ui));
+//!
+//! assert_snapshot(&image, "tests/golden/bars.png", 2, 0.001);
+//! ```
+//!
+//! Golden images that don't exist yet are written instead of compared against. Set the
+//! `EGUI_PLOTTER_UPDATE_SNAPSHOTS` environment variable to overwrite them after an intended
+//! change to how charts look.
+//!
+//! The snapshot's time is only what egui is told. Charts animated with playback read the
+//! system clock, which can't be swapped out, so `draw_at_time` is the only deterministic way to
+//! render them while playback is underway. Drawing a chart whose playback hasn't started is
+//! deterministic too, as every point is shown.
+
+use std::{
+    collections::HashMap,
+    env,
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use egui::{
+    epaint::{ClippedPrimitive, ImageData, Mesh, Primitive, Vertex},
+    CentralPanel, Color32, Context, Frame, Pos2, RawInput, Rect, Ui, Vec2, Visuals,
+};
+
+/// Environment variable that makes [`assert_snapshot`] overwrite golden images.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "EGUI_PLOTTER_UPDATE_SNAPSHOTS";
+/// Default time, in seconds, egui is told has passed when the snapshot is rendered.
+pub const DEFAULT_SNAPSHOT_TIME: f64 = 0.0;
+
+#[derive(Debug, Copy, Clone)]
+/// Renders charts offscreen. See the [module documentation](self).
+pub struct Snapshot {
+    width: u32,
+    height: u32,
+    time: f64,
+    background_color: Color32,
+}
+
+impl Snapshot {
+    #[inline]
+    /// Create a new snapshot renderer producing images of the given size, in pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            time: DEFAULT_SNAPSHOT_TIME,
+            background_color: Color32::WHITE,
+        }
+    }
+
+    #[inline]
+    /// Set the time, in seconds, egui is told has passed.
+    pub fn set_time(&mut self, time: f64) {
+        self.time = time
+    }
+
+    #[inline]
+    /// Set the time, in seconds, egui is told has passed. Consumes self.
+    pub fn time(mut self, time: f64) -> Self {
+        self.set_time(time);
+
+        self
+    }
+
+    #[inline]
+    /// Set the color the image is cleared to before the chart is drawn.
+    pub fn set_background_color(&mut self, color: Color32) {
+        self.background_color = color
+    }
+
+    #[inline]
+    /// Set the color the image is cleared to before the chart is drawn. Consumes self.
+    pub fn background_color(mut self, color: Color32) -> Self {
+        self.set_background_color(color);
+
+        self
+    }
+
+    /// Draw to a Ui filling the whole image and rasterize the result.
+    pub fn render<F>(&self, draw: F) -> SnapshotImage
+    where
+        F: FnOnce(&mut Ui),
+    {
+        let ctx = Context::default();
+
+        ctx.set_visuals(Visuals::light());
+        ctx.tessellation_options_mut(|options| options.feathering = false);
+
+        let size = Vec2::new(self.width as f32, self.height as f32);
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, size)),
+            time: Some(self.time),
+            ..Default::default()
+        };
+
+        let output = ctx.run(input, |ctx| {
+            CentralPanel::default().frame(Frame::none()).show(ctx, draw);
+        });
+
+        let mut textures = HashMap::new();
+
+        for (id, delta) in output.textures_delta.set {
+            let (size, pixels): ([usize; 2], Vec<Color32>) = match &delta.image {
+                ImageData::Color(image) => (image.size, image.pixels.clone()),
+                ImageData::Font(image) => (image.size, image.srgba_pixels(None).collect()),
+            };
+
+            let texture = textures.entry(id).or_insert_with(|| Texture {
+                size,
+                pixels: vec![Color32::TRANSPARENT; size[0] * size[1]],
+            });
+
+            let [x, y] = delta.pos.unwrap_or([0, 0]);
+
+            // Full updates replace the texture, partial ones patch it
+            if delta.pos.is_none() {
+                *texture = Texture { size, pixels };
+            } else {
+                for row in 0..size[1] {
+                    let start = (y + row) * texture.size[0] + x;
+
+                    texture.pixels[start..start + size[0]]
+                        .copy_from_slice(&pixels[row * size[0]..(row + 1) * size[0]]);
+                }
+            }
+        }
+
+        let primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+
+        let mut image = SnapshotImage {
+            width: self.width,
+            height: self.height,
+            pixels: vec![self.background_color; (self.width * self.height) as usize],
+        };
+
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in &primitives
+        {
+            // Custom paint callbacks need a GPU, so they are left out
+            if let Primitive::Mesh(mesh) = primitive {
+                image.draw_mesh(mesh, *clip_rect, textures.get(&mesh.texture_id));
+            }
+        }
+
+        image
+    }
+}
+
+/// Texture sampled by meshes, such as the font atlas.
+struct Texture {
+    size: [usize; 2],
+    pixels: Vec<Color32>,
+}
+
+impl Texture {
+    /// Sample the texture bilinearly at the given UV coordinate, as premultiplied channels
+    /// between 0 and 1.
+    fn sample(&self, uv: Pos2) -> [f32; 4] {
+        let [width, height] = self.size;
+
+        if width == 0 || height == 0 {
+            return [1.0; 4];
+        }
+
+        let x = (uv.x * width as f32 - 0.5).clamp(0.0, (width - 1) as f32);
+        let y = (uv.y * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (fx, fy) = (x.fract(), y.fract());
+
+        let texel = |x: usize, y: usize| self.pixels[y * width + x].to_array();
+        let (a, b, c, d) = (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1));
+
+        let mut color = [0.0; 4];
+
+        for (channel, value) in color.iter_mut().enumerate() {
+            let top = a[channel] as f32 * (1.0 - fx) + b[channel] as f32 * fx;
+            let bottom = c[channel] as f32 * (1.0 - fx) + d[channel] as f32 * fx;
+
+            *value = (top * (1.0 - fy) + bottom * fy) / 255.0;
+        }
+
+        color
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Image rendered by a [`Snapshot`], or loaded from a golden PNG.
+pub struct SnapshotImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color32>,
+}
+
+impl SnapshotImage {
+    #[inline]
+    /// Get the width of the image, in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    /// Get the height of the image, in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    /// Get the pixels of the image, row by row from the top left.
+    pub fn pixels(&self) -> &[Color32] {
+        &self.pixels
+    }
+
+    /// Load an image from a PNG file.
+    pub fn load_png<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+        let bytes = &buffer[..info.buffer_size()];
+
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => bytes
+                .chunks_exact(4)
+                .map(|p| Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
+                .collect(),
+            png::ColorType::Rgb => bytes
+                .chunks_exact(3)
+                .map(|p| Color32::from_rgb(p[0], p[1], p[2]))
+                .collect(),
+            png::ColorType::GrayscaleAlpha => bytes
+                .chunks_exact(2)
+                .map(|p| Color32::from_rgba_unmultiplied(p[0], p[0], p[0], p[1]))
+                .collect(),
+            png::ColorType::Grayscale => bytes.iter().map(|p| Color32::from_gray(*p)).collect(),
+            png::ColorType::Indexed => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "indexed PNGs aren't expanded",
+                ))
+            }
+        };
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    /// Save the image as a PNG file.
+    pub fn save_png<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let mut encoder =
+            png::Encoder::new(BufWriter::new(File::create(path)?), self.width, self.height);
+
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let bytes: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|pixel| pixel.to_srgba_unmultiplied())
+            .collect();
+
+        encoder.write_header()?.write_image_data(&bytes)?;
+
+        Ok(())
+    }
+
+    /// Compare the image to another, counting the pixels where any color channel differs by
+    /// more than the tolerance.
+    pub fn compare(&self, other: &SnapshotImage, tolerance: u8) -> SnapshotDiff {
+        if (self.width, self.height) != (other.width, other.height) {
+            return SnapshotDiff {
+                size_matches: false,
+                mismatched_pixels: self.pixels.len().max(other.pixels.len()),
+                total_pixels: self.pixels.len().max(other.pixels.len()),
+                max_difference: u8::MAX,
+            };
+        }
+
+        let mut mismatched_pixels = 0;
+        let mut max_difference = 0;
+
+        for (a, b) in self.pixels.iter().zip(&other.pixels) {
+            let difference = a
+                .to_array()
+                .iter()
+                .zip(b.to_array())
+                .map(|(a, b)| a.abs_diff(b))
+                .max()
+                .unwrap_or(0);
+
+            if difference > tolerance {
+                mismatched_pixels += 1;
+            }
+
+            max_difference = max_difference.max(difference);
+        }
+
+        SnapshotDiff {
+            size_matches: true,
+            mismatched_pixels,
+            total_pixels: self.pixels.len(),
+            max_difference,
+        }
+    }
+
+    /// Rasterize the triangles of a mesh, blending them over the image.
+    fn draw_mesh(&mut self, mesh: &Mesh, clip_rect: Rect, texture: Option<&Texture>) {
+        let bounds =
+            Rect::from_min_size(Pos2::ZERO, Vec2::new(self.width as f32, self.height as f32))
+                .intersect(clip_rect);
+
+        if !bounds.is_positive() {
+            return;
+        }
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|corner| mesh.vertices[triangle[corner] as usize]);
+
+            self.draw_triangle([a, b, c], bounds, texture);
+        }
+    }
+
+    /// Fill the pixels whose centers are within the triangle, interpolating the colors and UVs
+    /// of its vertices.
+    fn draw_triangle(&mut self, vertices: [Vertex; 3], bounds: Rect, texture: Option<&Texture>) {
+        let [a, b, c] = vertices;
+
+        let edge = |from: Pos2, to: Pos2, p: Pos2| {
+            (to.x - from.x) * (p.y - from.y) - (to.y - from.y) * (p.x - from.x)
+        };
+
+        let area = edge(a.pos, b.pos, c.pos);
+
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+
+        let min_x = a.pos.x.min(b.pos.x).min(c.pos.x).max(bounds.left());
+        let max_x = a.pos.x.max(b.pos.x).max(c.pos.x).min(bounds.right());
+        let min_y = a.pos.y.min(b.pos.y).min(c.pos.y).max(bounds.top());
+        let max_y = a.pos.y.max(b.pos.y).max(c.pos.y).min(bounds.bottom());
+
+        if min_x >= max_x || min_y >= max_y {
+            return;
+        }
+
+        for y in (min_y.floor() as u32)..(max_y.ceil() as u32).min(self.height) {
+            for x in (min_x.floor() as u32)..(max_x.ceil() as u32).min(self.width) {
+                let p = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+
+                // Weights are negative outside of the triangle, whichever way it is wound
+                let weights = [
+                    edge(b.pos, c.pos, p) / area,
+                    edge(c.pos, a.pos, p) / area,
+                    edge(a.pos, b.pos, p) / area,
+                ];
+
+                if weights.iter().any(|weight| *weight < 0.0) || !bounds.contains(p) {
+                    continue;
+                }
+
+                let interpolate = |value: fn(&Vertex) -> f32| {
+                    weights[0] * value(&a) + weights[1] * value(&b) + weights[2] * value(&c)
+                };
+
+                let texel = texture.map_or([1.0; 4], |texture| {
+                    texture.sample(Pos2::new(
+                        interpolate(|vertex| vertex.uv.x),
+                        interpolate(|vertex| vertex.uv.y),
+                    ))
+                });
+
+                let vertex_color = [
+                    interpolate(|vertex| vertex.color.r() as f32),
+                    interpolate(|vertex| vertex.color.g() as f32),
+                    interpolate(|vertex| vertex.color.b() as f32),
+                    interpolate(|vertex| vertex.color.a() as f32),
+                ];
+
+                let pixel = &mut self.pixels[(y * self.width + x) as usize];
+                let destination = pixel.to_array();
+
+                // Colors are premultiplied, so blend with "over"
+                let source_alpha = vertex_color[3] * texel[3] / 255.0;
+                let blended: [u8; 4] = std::array::from_fn(|channel| {
+                    let source = vertex_color[channel] * texel[channel];
+
+                    (source + destination[channel] as f32 * (1.0 - source_alpha))
+                        .round()
+                        .clamp(0.0, 255.0) as u8
+                });
+
+                *pixel = Color32::from_rgba_premultiplied(
+                    blended[0], blended[1], blended[2], blended[3],
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Result of comparing two snapshot images.
+pub struct SnapshotDiff {
+    size_matches: bool,
+    mismatched_pixels: usize,
+    total_pixels: usize,
+    max_difference: u8,
+}
+
+impl SnapshotDiff {
+    #[inline]
+    /// Return true if both images have the same size.
+    pub fn size_matches(&self) -> bool {
+        self.size_matches
+    }
+
+    #[inline]
+    /// Get the number of pixels differing by more than the tolerance. Every pixel if the sizes
+    /// differ.
+    pub fn mismatched_pixels(&self) -> usize {
+        self.mismatched_pixels
+    }
+
+    #[inline]
+    /// Get the fraction of pixels differing by more than the tolerance, between 0 and 1.
+    pub fn mismatch_ratio(&self) -> f32 {
+        match self.total_pixels {
+            0 => 0.0,
+            total => self.mismatched_pixels as f32 / total as f32,
+        }
+    }
+
+    #[inline]
+    /// Get the largest difference of any color channel between the images.
+    pub fn max_difference(&self) -> u8 {
+        self.max_difference
+    }
+}
+
+/// Compare an image against the golden PNG at `golden`, panicking if more than
+/// `max_mismatch_ratio` of its pixels differ by more than `tolerance` in any color channel. The
+/// rendered image is saved next to the golden one, with `.actual.png` appended, when they don't
+/// match.
+///
+/// The golden image is written instead if it doesn't exist, or if the
+/// `EGUI_PLOTTER_UPDATE_SNAPSHOTS` environment variable is set.
+pub fn assert_snapshot<P>(image: &SnapshotImage, golden: P, tolerance: u8, max_mismatch_ratio: f32)
+where
+    P: AsRef<Path>,
+{
+    let golden = golden.as_ref();
+
+    if env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() || !golden.exists() {
+        if let Some(parent) = golden.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+
+        image.save_png(golden).unwrap();
+
+        return;
+    }
+
+    let expected = SnapshotImage::load_png(golden).unwrap();
+    let diff = image.compare(&expected, tolerance);
+
+    if diff.size_matches() && diff.mismatch_ratio() <= max_mismatch_ratio {
+        return;
+    }
+
+    let mut actual = PathBuf::from(golden);
+
+    actual.as_mut_os_string().push(".actual.png");
+    image.save_png(&actual).unwrap();
+
+    panic!(
+        "snapshot {} doesn't match: {} of {} pixels differ (largest channel difference {}), \
+         rendered image saved to {}",
+        golden.display(),
+        diff.mismatched_pixels(),
+        diff.total_pixels,
+        diff.max_difference(),
+        actual.display()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(pixels: &[Color32]) -> SnapshotImage {
+        SnapshotImage {
+            width: pixels.len() as u32,
+            height: 1,
+            pixels: pixels.to_vec(),
+        }
+    }
+
+    #[test]
+    fn compare_counts_pixels_beyond_the_tolerance() {
+        let golden = image(&[Color32::BLACK, Color32::WHITE, Color32::from_gray(100)]);
+        let actual = image(&[
+            Color32::BLACK,
+            Color32::from_gray(253),
+            Color32::from_gray(90),
+        ]);
+
+        let diff = actual.compare(&golden, 2);
+
+        assert!(diff.size_matches());
+        assert_eq!(diff.mismatched_pixels(), 1);
+        assert_eq!(diff.max_difference(), 10);
+        assert!((diff.mismatch_ratio() - 1.0 / 3.0).abs() < 1e-6);
+
+        assert_eq!(actual.compare(&golden, 10).mismatched_pixels(), 0);
+        assert_eq!(golden.compare(&golden, 0).mismatched_pixels(), 0);
+    }
+
+    #[test]
+    fn compare_mismatches_every_pixel_of_differently_sized_images() {
+        let diff = image(&[Color32::BLACK; 2]).compare(&image(&[Color32::BLACK; 3]), u8::MAX);
+
+        assert!(!diff.size_matches());
+        assert_eq!(diff.mismatched_pixels(), 3);
+        assert_eq!(diff.mismatch_ratio(), 1.0);
+    }
+
+    #[test]
+    fn assert_snapshot_writes_missing_goldens_and_allows_the_mismatch_ratio() {
+        // Goldens are overwritten rather than compared against while updating them
+        if env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+            return;
+        }
+
+        let dir = env::temp_dir().join(format!("egui_plotter_snapshot_{}", std::process::id()));
+        let golden = dir.join("golden.png");
+        let mut pixels = [Color32::RED; 100];
+
+        assert_snapshot(&image(&pixels), &golden, 0, 0.0);
+        assert_eq!(SnapshotImage::load_png(&golden).unwrap(), image(&pixels));
+
+        pixels[0] = Color32::BLUE;
+        assert_snapshot(&image(&pixels), &golden, 0, 0.01);
+
+        pixels[1] = Color32::BLUE;
+        let result =
+            std::panic::catch_unwind(|| assert_snapshot(&image(&pixels), &golden, 0, 0.01));
+
+        assert!(result.is_err());
+        assert!(dir.join("golden.png.actual.png").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+//! Golden image tests, rendering charts offscreen and comparing them against the PNGs in
+//! `tests/golden`. Set `EGUI_PLOTTER_UPDATE_SNAPSHOTS` to regenerate them after an intended
+//! change to how charts look.
+
+use egui_plotter::{
+    charts::{BarChart, XyTimeData},
+    snapshot::{assert_snapshot, Snapshot},
+};
+
+const WIDTH: u32 = 400;
+const HEIGHT: u32 = 300;
+/// Largest difference of a color channel that isn't counted as a mismatch.
+const TOLERANCE: u8 = 2;
+/// Fraction of the pixels allowed to mismatch, for small differences in how text is laid out.
+const MAX_MISMATCH_RATIO: f32 = 0.001;
+
+fn spiral() -> Vec<(f32, f32, f32)> {
+    (0..100)
+        .map(|i| {
+            let time = i as f32 * 0.1;
+
+            (time.cos() * time, time.sin() * time, time)
+        })
+        .collect()
+}
+
+#[test]
+fn bar_chart() {
+    let mut chart = BarChart::new(&["a", "b", "c"], "Bars")
+        .palette_series("First", &[1.0, 3.0, 2.0])
+        .palette_series("Second", &[2.0, 1.0, 4.0]);
+
+    let image = Snapshot::new(WIDTH, HEIGHT).render(|ui| chart.draw(ui));
+
+    assert_snapshot(
+        &image,
+        "tests/golden/bar_chart.png",
+        TOLERANCE,
+        MAX_MISMATCH_RATIO,
+    );
+}
+
+#[test]
+fn time_chart() {
+    let mut chart = XyTimeData::new(&spiral(), "x", "y", "Spiral");
+
+    let image = Snapshot::new(WIDTH, HEIGHT).render(|ui| chart.draw(ui));
+
+    assert_snapshot(
+        &image,
+        "tests/golden/time_chart.png",
+        TOLERANCE,
+        MAX_MISMATCH_RATIO,
+    );
+}
+
+#[test]
+fn time_chart_at_time() {
+    // The playback clock runs on the system clock, so only drawing at a given time is
+    // deterministic while playback is underway
+    let mut chart = XyTimeData::new(&spiral(), "x", "y", "Spiral");
+
+    chart.start_playback();
+
+    let snapshot = Snapshot::new(WIDTH, HEIGHT);
+    let image = snapshot.render(|ui| chart.draw_at_time(ui, 5.0));
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    assert_eq!(image, snapshot.render(|ui| chart.draw_at_time(ui, 5.0)));
+    assert_ne!(image, snapshot.render(|ui| chart.draw_at_time(ui, 9.9)));
+
+    assert_snapshot(
+        &image,
+        "tests/golden/time_chart_at_time.png",
+        TOLERANCE,
+        MAX_MISMATCH_RATIO,
+    );
+}